@@ -0,0 +1,10 @@
+-- Files attached to an escalation, with an optional note on what each one is
+CREATE TABLE IF NOT EXISTS attachments (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    file_path TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_attachments_escalation ON attachments(escalation_id);
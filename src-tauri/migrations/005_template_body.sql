@@ -0,0 +1,2 @@
+-- Optional handlebars body overriding the default escalation layout
+ALTER TABLE templates ADD COLUMN body TEXT;
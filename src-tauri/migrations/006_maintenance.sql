@@ -0,0 +1,10 @@
+-- History of VACUUM/ANALYZE/integrity_check runs
+CREATE TABLE IF NOT EXISTS maintenance_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    integrity_ok INTEGER NOT NULL,
+    integrity_messages TEXT NOT NULL DEFAULT '[]',
+    size_before_bytes INTEGER NOT NULL,
+    size_after_bytes INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    ran_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,10 @@
+-- Escalations rejected by L2, with the quality score at the time for calibration
+CREATE TABLE IF NOT EXISTS bounces (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    reason TEXT NOT NULL,
+    quality_score INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_bounces_escalation ON bounces(escalation_id);
@@ -0,0 +1,2 @@
+-- Set when a staged attachment file is removed by the retention policy; the row is kept
+ALTER TABLE attachments ADD COLUMN purged_at TEXT;
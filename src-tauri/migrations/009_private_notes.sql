@@ -0,0 +1,2 @@
+-- Agent-only scratchpad; never rendered, posted or exported unless explicitly requested
+ALTER TABLE escalations ADD COLUMN private_notes TEXT;
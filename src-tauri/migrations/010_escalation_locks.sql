@@ -0,0 +1,7 @@
+-- Advisory edit locks so two app instances on a shared machine don't edit the same draft
+CREATE TABLE IF NOT EXISTS escalation_locks (
+    escalation_id INTEGER PRIMARY KEY REFERENCES escalations(id) ON DELETE CASCADE,
+    locked_by TEXT NOT NULL,
+    instance_id TEXT NOT NULL,
+    locked_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
@@ -0,0 +1,9 @@
+-- Cached Ollama embeddings of escalation problem summaries for similarity search.
+-- content_hash detects edits so only new or changed escalations are re-embedded.
+CREATE TABLE IF NOT EXISTS escalation_embeddings (
+    escalation_id INTEGER PRIMARY KEY REFERENCES escalations(id) ON DELETE CASCADE,
+    model TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    embedding BLOB NOT NULL,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
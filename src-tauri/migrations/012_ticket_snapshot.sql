@@ -0,0 +1,2 @@
+-- JSON snapshot of the Jira ticket (summary, status, assignee) when the escalation was created
+ALTER TABLE escalations ADD COLUMN ticket_snapshot TEXT;
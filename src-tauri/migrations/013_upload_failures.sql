@@ -0,0 +1,10 @@
+-- Attachments that failed to upload after the comment was posted, for targeted retries
+CREATE TABLE IF NOT EXISTS attachment_upload_failures (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    file_path TEXT NOT NULL,
+    error TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_upload_failures_escalation ON attachment_upload_failures(escalation_id);
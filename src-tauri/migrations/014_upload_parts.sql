@@ -0,0 +1,9 @@
+-- Parts of split uploads already accepted by Jira, so an interrupted upload resumes
+-- where it stopped. Keyed by the source file's SHA-256 so an edited file starts over.
+CREATE TABLE IF NOT EXISTS upload_parts (
+    ticket_id TEXT NOT NULL,
+    file_sha256 TEXT NOT NULL,
+    part_name TEXT NOT NULL,
+    uploaded_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (ticket_id, file_sha256, part_name)
+);
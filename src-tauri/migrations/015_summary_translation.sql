@@ -0,0 +1,3 @@
+-- Detected language of the problem summary and its machine-translated English version
+ALTER TABLE escalations ADD COLUMN summary_language TEXT;
+ALTER TABLE escalations ADD COLUMN summary_translation TEXT;
@@ -0,0 +1,12 @@
+-- Artifacts a template expects to be attached (JSON array of labels), and
+-- which expected artifact each attachment provides
+ALTER TABLE templates ADD COLUMN expected_artifacts TEXT;
+ALTER TABLE attachments ADD COLUMN artifact TEXT;
+
+-- Backfill the built-in templates on existing installs
+UPDATE templates SET expected_artifacts = '["Screenshot of the VPN client error","VPN client log"]'
+    WHERE name = 'Network/VPN Issues' AND expected_artifacts IS NULL;
+UPDATE templates SET expected_artifacts = '["Screenshot of the error","Application log"]'
+    WHERE name = 'Application Crash' AND expected_artifacts IS NULL;
+UPDATE templates SET expected_artifacts = '["Screenshot of the access denied message"]'
+    WHERE name = 'Access/Permissions Issue' AND expected_artifacts IS NULL;
@@ -0,0 +1,13 @@
+-- Cached Jira /myself result for the configured credentials
+CREATE TABLE IF NOT EXISTS jira_identity (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    account_id TEXT NOT NULL,
+    display_name TEXT NOT NULL,
+    email TEXT,
+    avatar_url TEXT,
+    fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Who performed each audited action (from the cached identity, when known)
+ALTER TABLE audit_log ADD COLUMN actor_account_id TEXT;
+ALTER TABLE audit_log ADD COLUMN actor TEXT;
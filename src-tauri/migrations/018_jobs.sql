@@ -0,0 +1,13 @@
+-- Posts and uploads in flight, so a quit or crash mid-job can be resumed on next launch
+CREATE TABLE IF NOT EXISTS jobs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    kind TEXT NOT NULL,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    file_paths TEXT NOT NULL DEFAULT '[]',
+    instance_id TEXT NOT NULL,
+    state TEXT NOT NULL DEFAULT 'running',
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    interrupted_at TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_state ON jobs(state);
@@ -0,0 +1,10 @@
+-- Knowledge-base articles drafted from resolved escalations
+CREATE TABLE IF NOT EXISTS kb_drafts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    markdown TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_kb_drafts_escalation ON kb_drafts(escalation_id);
@@ -0,0 +1,2 @@
+-- Crop box, arrows and redaction rectangles drawn on staged screenshots (JSON)
+ALTER TABLE attachments ADD COLUMN annotations TEXT;
@@ -0,0 +1,4 @@
+-- zstd-compressed copies of large text columns for old, posted escalations;
+-- when set, the matching text column is NULL
+ALTER TABLE escalations ADD COLUMN markdown_output_zstd BLOB;
+ALTER TABLE escalations ADD COLUMN llm_summary_zstd BLOB;
@@ -0,0 +1,11 @@
+-- What the agent did each day, for the "my day" shift handover summary
+CREATE TABLE IF NOT EXISTS activity_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    day TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    ticket_key TEXT,
+    escalation_id INTEGER,
+    occurred_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_activity_log_day ON activity_log(day);
@@ -0,0 +1,3 @@
+-- A re-escalation points at the escalation it follows up on
+ALTER TABLE escalations ADD COLUMN parent_escalation_id INTEGER REFERENCES escalations(id);
+CREATE INDEX idx_escalations_parent ON escalations(parent_escalation_id);
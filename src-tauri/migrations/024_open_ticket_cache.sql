@@ -0,0 +1,6 @@
+-- Last "assigned to me" ticket list fetched in the background, for an instant ticket picker
+CREATE TABLE IF NOT EXISTS open_ticket_cache (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    tickets TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
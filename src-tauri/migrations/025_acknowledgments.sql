@@ -0,0 +1,3 @@
+-- Set by the acknowledgment sync once L2 replies with an acknowledgment phrase or labels the ticket
+ALTER TABLE escalations ADD COLUMN acknowledged_at TEXT;
+ALTER TABLE escalations ADD COLUMN acknowledged_by TEXT;
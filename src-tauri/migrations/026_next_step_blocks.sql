@@ -0,0 +1,8 @@
+-- Reusable "next steps" text, inserted by name and usable as template partials
+CREATE TABLE IF NOT EXISTS next_step_blocks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    body TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
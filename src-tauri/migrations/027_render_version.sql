@@ -0,0 +1,2 @@
+-- Built-in layout version each escalation was written with; existing rows predate versioning (1)
+ALTER TABLE escalations ADD COLUMN render_version INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,2 @@
+-- L2 teams each escalation is handed to, as JSON [{team, asks}]
+ALTER TABLE escalations ADD COLUMN l2_teams TEXT NOT NULL DEFAULT '[]';
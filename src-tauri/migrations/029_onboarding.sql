@@ -0,0 +1,16 @@
+-- First-run onboarding steps that are done; skipped optional steps are recorded too
+CREATE TABLE IF NOT EXISTS onboarding_steps (
+    step TEXT PRIMARY KEY,
+    skipped INTEGER NOT NULL DEFAULT 0,
+    completed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- Installs that have already posted an escalation are past onboarding
+INSERT INTO onboarding_steps (step)
+SELECT step FROM (
+    SELECT 'jira_config' AS step UNION ALL
+    SELECT 'test_connection' UNION ALL
+    SELECT 'ollama' UNION ALL
+    SELECT 'first_template'
+)
+WHERE EXISTS (SELECT 1 FROM escalations WHERE posted_at IS NOT NULL);
@@ -0,0 +1,6 @@
+-- Last fetch of the shared "needs escalation" queue, polled with a configured JQL query
+CREATE TABLE IF NOT EXISTS escalation_queue_cache (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    tickets TEXT NOT NULL,
+    fetched_at TEXT NOT NULL
+);
@@ -0,0 +1,13 @@
+-- Per-field change history for escalations. Values are kept as SHA-256 hashes:
+-- enough to tell when and whether a field changed without copying its text.
+CREATE TABLE IF NOT EXISTS audit_field_history (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    escalation_id INTEGER NOT NULL REFERENCES escalations(id) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    old_hash TEXT,
+    new_hash TEXT,
+    actor TEXT,
+    changed_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_field_history ON audit_field_history(escalation_id, field);
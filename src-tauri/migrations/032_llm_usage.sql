@@ -0,0 +1,16 @@
+-- One row per request to the LLM, for usage and latency reporting. Only the
+-- prompt's size is kept, never its text.
+CREATE TABLE IF NOT EXISTS llm_usage (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    prompt_chars INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    -- 'ok', 'timeout' or 'error'
+    outcome TEXT NOT NULL,
+    agent TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_llm_usage_created ON llm_usage(created_at);
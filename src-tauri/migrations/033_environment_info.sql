@@ -0,0 +1,2 @@
+-- Machine details captured for L2 (OS version, configured tool versions), as JSON
+ALTER TABLE escalations ADD COLUMN environment_info TEXT;
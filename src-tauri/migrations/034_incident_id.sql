@@ -0,0 +1,3 @@
+-- Major incident an escalation is tied to (PagerDuty, Statuspage or Jira incident ID)
+ALTER TABLE escalations ADD COLUMN incident_id TEXT;
+CREATE INDEX IF NOT EXISTS idx_escalations_incident_id ON escalations(incident_id COLLATE NOCASE);
@@ -0,0 +1,84 @@
+use crate::commands::escalations::write_audit_log;
+use crate::commands::settings::get_jira_client;
+use crate::db;
+use crate::error::AppResult;
+use crate::models::UnacknowledgedEscalation;
+use crate::services::acknowledgment::{self, AckRules};
+use crate::services::field_history;
+use chrono::Utc;
+use tauri::AppHandle;
+
+/// Check posted escalations' tickets for L2 acknowledgments; returns the newly acknowledged ids
+#[tauri::command]
+pub async fn sync_acknowledgments(app: AppHandle) -> Result<Vec<i64>, String> {
+    sync_acknowledgments_impl(app)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Posted escalations still unacknowledged after `older_than_hours` (the preference when omitted)
+#[tauri::command]
+pub fn list_unacknowledged(older_than_hours: Option<u32>) -> Result<Vec<UnacknowledgedEscalation>, String> {
+    list_unacknowledged_impl(older_than_hours).map_err(|e| e.to_string())
+}
+
+async fn sync_acknowledgments_impl(app: AppHandle) -> Result<Vec<i64>, Box<dyn std::error::Error>> {
+    let preferences = db::get_preferences()?;
+    let pending = acknowledgment::pending(&*db::get_connection()?, preferences.ack_sync_days, Utc::now())?;
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let own_name = db::get_jira_identity()?.map(|identity| identity.display_name);
+    let rules = AckRules {
+        phrases: &preferences.ack_phrases,
+        labels: &preferences.ack_labels,
+        marker: &preferences.correlation_marker,
+        own_name: own_name.as_deref(),
+    };
+    let client = get_jira_client(app).await?;
+
+    // One unreachable ticket shouldn't stop the rest from syncing
+    let mut acknowledged = Vec::new();
+    for escalation in pending {
+        let comments = match client.list_comments(&escalation.ticket_id).await {
+            Ok(comments) => comments,
+            Err(e) => {
+                log::warn!("Acknowledgment sync skipped {}: {}", escalation.ticket_id, e);
+                continue;
+            }
+        };
+        let labels = if rules.labels.is_empty() {
+            Vec::new()
+        } else {
+            match client.fetch_issue(&escalation.ticket_id).await {
+                Ok(ticket) => ticket.labels,
+                Err(e) => {
+                    log::warn!("Failed to read labels of {}: {}", escalation.ticket_id, e);
+                    Vec::new()
+                }
+            }
+        };
+
+        if let Some(ack) = acknowledgment::detect(&comments, &labels, &escalation.posted_at, &rules, Utc::now()) {
+            let conn = db::get_connection()?;
+            acknowledgment::mark(&conn, escalation.id, &ack)?;
+            field_history::record(&conn, escalation.id, "acknowledged_at", None, Some(&ack.at))?;
+            write_audit_log(escalation.id, "acknowledged", &serde_json::json!({
+                "acknowledged_by": ack.by,
+                "acknowledged_at": ack.at,
+            }))?;
+            acknowledged.push(escalation.id);
+        }
+    }
+
+    Ok(acknowledged)
+}
+
+fn list_unacknowledged_impl(older_than_hours: Option<u32>) -> AppResult<Vec<UnacknowledgedEscalation>> {
+    let hours = match older_than_hours {
+        Some(hours) => hours,
+        None => db::get_preferences()?.unacknowledged_after_hours,
+    };
+    acknowledgment::unacknowledged(&*db::get_connection()?, hours, Utc::now())
+}
@@ -0,0 +1,47 @@
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::DailySummary;
+use crate::services::activity;
+use crate::services::ollama::OllamaClient;
+
+/// Summary of one day's activity (today when `date` is omitted), optionally narrated by the LLM
+#[tauri::command]
+pub async fn get_daily_summary(date: Option<String>, narrate: Option<bool>) -> Result<DailySummary, String> {
+    get_daily_summary_impl(date, narrate.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn get_daily_summary_impl(date: Option<String>, narrate: bool) -> AppResult<DailySummary> {
+    let date = match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date)))?
+            .to_string(),
+        None => activity::today(),
+    };
+
+    let mut summary = activity::daily_summary(&*db::get_connection()?, &date)?;
+
+    // The plain list is already pasteable, so a missing narrative isn't an error
+    if narrate {
+        match narrate_day(&summary.markdown).await {
+            Ok(narrative) => summary.narrative = narrative,
+            Err(e) => log::warn!("Daily summary narration failed: {}", e),
+        }
+    }
+
+    Ok(summary)
+}
+
+async fn narrate_day(markdown: &str) -> AppResult<Option<String>> {
+    let Some(config) = db::get_api_config()? else {
+        return Ok(None);
+    };
+    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
+    if !client.is_available().await? {
+        return Ok(None);
+    }
+
+    client.narrate_day(markdown).await.map(Some)
+}
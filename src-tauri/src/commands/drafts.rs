@@ -0,0 +1,87 @@
+use crate::commands::escalations::list_escalations_impl;
+use crate::commands::templates::list_templates_impl;
+use crate::commands::tickets::fetch_jira_ticket_impl;
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::{DraftContext, EscalationSummary, JiraTicket, SimilarEscalation};
+use crate::services::ollama::OllamaClient;
+use crate::services::{embeddings, similarity};
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn load_draft_context(app: AppHandle, ticket_key: String) -> Result<DraftContext, String> {
+    load_draft_context_impl(app, ticket_key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Fetch the ticket while templates and past escalations load from the database
+///
+/// Every branch yields an `AppResult`: the boxed errors of the ticket fetch aren't
+/// `Send`, and this future has to be.
+async fn load_draft_context_impl(app: AppHandle, ticket_key: String) -> AppResult<DraftContext> {
+    let (ticket, templates, escalations) = tokio::join!(
+        async {
+            fetch_jira_ticket_impl(app, ticket_key)
+                .await
+                .map_err(|e| AppError::Jira(e.to_string()))
+        },
+        tauri::async_runtime::spawn_blocking(list_templates_impl),
+        tauri::async_runtime::spawn_blocking(list_escalations_impl),
+    );
+
+    let ticket = ticket?;
+    let templates = templates.map_err(join_error)??;
+    let escalations = escalations.map_err(join_error)??;
+
+    let similar_escalations = match db::get_preferences()?.embedding_model {
+        Some(model) => match semantic_rank(&ticket, &escalations, model).await {
+            Ok(ranked) => ranked,
+            Err(e) => {
+                log::warn!("Embedding similarity unavailable, using word overlap: {}", e);
+                similarity::rank(&ticket.key, &ticket.summary, escalations, similarity::DEFAULT_LIMIT)
+            }
+        },
+        None => similarity::rank(&ticket.key, &ticket.summary, escalations, similarity::DEFAULT_LIMIT),
+    };
+
+    Ok(DraftContext {
+        ticket,
+        templates,
+        similar_escalations,
+    })
+}
+
+/// Embed any new or edited escalations, then rank against the ticket summary
+async fn semantic_rank(
+    ticket: &JiraTicket,
+    escalations: &[EscalationSummary],
+    model: String,
+) -> AppResult<Vec<SimilarEscalation>> {
+    let config = db::get_api_config()?
+        .ok_or_else(|| AppError::Validation("No API config found. Please configure Ollama in Settings.".to_string()))?;
+    let client = OllamaClient::new(config.ollama_endpoint, model.clone())?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
+
+    // Store each embedding as soon as it arrives so a later failure keeps the work done so far
+    let stale = embeddings::stale(&*db::get_connection()?, &model)?;
+    for (id, text) in stale {
+        let vector = client.embed(&text).await?;
+        embeddings::store(&*db::get_connection()?, id, &model, &text, &vector)?;
+    }
+
+    let ticket_vector = client.embed(&ticket.summary).await?;
+    let vectors = embeddings::load_all(&*db::get_connection()?, &model)?;
+
+    Ok(similarity::rank_by_embedding(
+        &ticket.key,
+        &ticket_vector,
+        escalations.to_vec(),
+        &vectors,
+        similarity::DEFAULT_LIMIT,
+    ))
+}
+
+fn join_error(e: tauri::Error) -> AppError {
+    AppError::Db(format!("Background load failed: {}", e))
+}
@@ -1,9 +1,27 @@
-use crate::commands::settings::get_jira_client;
+use crate::commands::onboarding::ensure_onboarded;
+use crate::commands::settings::{ensure_jira_writable, ensure_posting_window, get_jira_client};
+use crate::commands::templates;
+use crate::commands::tickets::{attach_file_redacted, attachment_quota_check, ensure_attachments_allowed, normalize_ticket_key};
 use crate::db;
 use crate::error::{AppError, AppResult};
-use crate::models::{ChecklistItem, Escalation, EscalationInput, EscalationStatus, EscalationSummary};
-use crate::services::template_engine;
+use crate::models::{
+    Attachment, AttachmentInput, BackendCapabilities, BounceReport, BundleManifest, ChecklistItem, Escalation,
+    DraftImpact, EnvironmentInfo, EscalationInput, EscalationStatus, EscalationSummary, FieldChange, FreezeWindow, InterruptedJob, KbDraft, LintWarning, OutputFormat,
+    OutputSizeReport, Preferences, PreviousEscalation, PrioritySuggestion, QualityScore, TeamAsk, Template, TemplateImpact, TicketGuardMode, TicketSnapshot,
+};
+use crate::services::jira::JiraClient;
+use crate::services::jobs;
+use crate::services::locks::{self, INSTANCE_ID};
+use crate::services::metrics::METRICS;
+use crate::services::ollama::OllamaClient;
+use crate::services::{
+    activity, anonymized_dataset, attachment_storage, audit, bounces, bundle, cold_storage, correlation, environment_info, field_history, html, incident, kb, line_diff, lint, next_step_blocks, output_format, output_size, priority, quality, redaction, remote_links, share_links, team_routing, template_engine, ticket_guard,
+    watch_folders,
+};
+use rusqlite::OptionalExtension;
+use std::collections::HashMap;
 use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 #[tauri::command]
 pub fn save_escalation(input: EscalationInput) -> Result<i64, String> {
@@ -15,9 +33,16 @@ pub fn get_escalation(id: i64) -> Result<Escalation, String> {
     get_escalation_impl(id).map_err(|e| e.to_string())
 }
 
+/// All escalations, or only those linked to `incident_id` (case-insensitive) for post-incident reviews
 #[tauri::command]
-pub fn list_escalations() -> Result<Vec<EscalationSummary>, String> {
-    list_escalations_impl().map_err(|e| e.to_string())
+pub fn list_escalations(incident_id: Option<String>) -> Result<Vec<EscalationSummary>, String> {
+    list_escalations_filtered(incident_id.as_deref()).map_err(|e| e.to_string())
+}
+
+/// Link an escalation to an incident record, or unlink it with None; allowed after posting
+#[tauri::command]
+pub fn link_incident(escalation_id: i64, incident_id: Option<String>) -> Result<(), String> {
+    link_incident_impl(escalation_id, incident_id.as_deref()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -27,19 +52,232 @@ pub fn delete_escalation(id: i64) -> Result<(), String> {
 
 #[tauri::command]
 pub fn render_markdown(input: EscalationInput) -> Result<String, String> {
-    render_markdown_impl(input).map_err(|e| e.to_string())
+    preview_input(input)
+        .and_then(render_markdown_impl)
+        .map_err(|e| e.to_string())
+}
+
+/// Render in the given flavor, or the configured output format when omitted
+#[tauri::command]
+pub fn render_output(input: EscalationInput, format: Option<OutputFormat>) -> Result<String, String> {
+    preview_input(input)
+        .and_then(|input| render_output_impl(input, format))
+        .map_err(|e| e.to_string())
+}
+
+/// How the drafts using a template would render if it were changed to `proposed`, as a line diff per draft
+#[tauri::command]
+pub fn preview_template_impact(template_id: i64, proposed: Template) -> Result<TemplateImpact, String> {
+    preview_template_impact_impl(template_id, proposed).map_err(|e| e.to_string())
+}
+
+/// Re-render a stored escalation with a given built-in layout version (its own when omitted)
+#[tauri::command]
+pub fn render_with_version(id: i64, version: Option<u32>) -> Result<String, String> {
+    render_with_version_impl(id, version).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn score_escalation(id: i64) -> Result<QualityScore, String> {
+    score_escalation_impl(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn lint_escalation(id: i64) -> Result<Vec<LintWarning>, String> {
+    lint_escalation_impl(id).map_err(|e| e.to_string())
+}
+
+/// Suggest a Jira priority from keywords and checklist state, optionally asking the LLM
+#[tauri::command]
+pub async fn suggest_priority(id: i64, use_llm: Option<bool>) -> Result<PrioritySuggestion, String> {
+    suggest_priority_impl(id, use_llm.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
 }
 
-fn save_escalation_impl(input: EscalationInput) -> AppResult<i64> {
+/// Rendered length against the configured per-section and total budgets
+#[tauri::command]
+pub fn check_output_size(id: i64) -> Result<OutputSizeReport, String> {
+    check_output_size_impl(id).map_err(|e| e.to_string())
+}
+
+/// Template artifacts (e.g. "Client log") with no attachment mapped to them
+#[tauri::command]
+pub fn check_expected_artifacts(id: i64) -> Result<Vec<String>, String> {
+    check_expected_artifacts_impl(id).map_err(|e| e.to_string())
+}
+
+/// Paginated, monochrome HTML for paper handoff records
+#[tauri::command]
+pub fn generate_print_view(id: i64) -> Result<String, String> {
+    generate_print_view_impl(id).map_err(|e| e.to_string())
+}
+
+/// Signed localhost URL to a read-only view of the escalation, valid for `ttl_minutes`
+#[tauri::command]
+pub fn create_share_link(escalation_id: i64, ttl_minutes: Option<i64>) -> Result<String, String> {
+    create_share_link_impl(escalation_id, ttl_minutes).map_err(|e| e.to_string())
+}
+
+/// Start a new draft linked to a posted escalation whose issue came back after L2 closed it
+#[tauri::command]
+pub fn reescalate(original_id: i64) -> Result<i64, String> {
+    reescalate_impl(original_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn mark_bounced(escalation_id: i64, reason: String) -> Result<(), String> {
+    mark_bounced_impl(escalation_id, reason).map_err(|e| e.to_string())
+}
+
+/// Recorded changes to one field of an escalation, oldest first
+#[tauri::command]
+pub fn get_field_history(id: i64, field: String) -> Result<Vec<FieldChange>, String> {
+    get_field_history_impl(id, &field).map_err(|e| e.to_string())
+}
+
+fn get_field_history_impl(id: i64, field: &str) -> AppResult<Vec<FieldChange>> {
+    field_history::history(&*db::get_connection()?, id, field)
+}
+
+#[tauri::command]
+pub fn get_bounce_report() -> Result<BounceReport, String> {
+    get_bounce_report_impl().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn export_handoff_bundle(
+    id: i64,
+    path: String,
+    include_private_notes: Option<bool>,
+) -> Result<BundleManifest, String> {
+    export_handoff_bundle_impl(id, path, include_private_notes.unwrap_or(false)).map_err(|e| e.to_string())
+}
+
+/// Save the image on the clipboard as a PNG attachment of an escalation that hasn't been posted
+#[tauri::command]
+pub async fn save_clipboard_image_as_attachment(app: AppHandle, escalation_id: i64) -> Result<Attachment, String> {
+    save_clipboard_image_as_attachment_impl(app, escalation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Capture OS and configured tool versions; stored on the escalation when `escalation_id` is given
+#[tauri::command]
+pub async fn capture_environment_info(escalation_id: Option<i64>) -> Result<EnvironmentInfo, String> {
+    capture_environment_info_impl(escalation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Draft a knowledge-base article from a resolved escalation
+///
+/// `resolution` describes how the issue was fixed; the escalation's current
+/// status is used when it is omitted.
+#[tauri::command]
+pub async fn draft_kb_article(escalation_id: i64, resolution: Option<String>) -> Result<KbDraft, String> {
+    draft_kb_article_impl(escalation_id, resolution)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_kb_drafts() -> Result<Vec<KbDraft>, String> {
+    list_kb_drafts_impl().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn export_kb_draft(id: i64, path: String) -> Result<(), String> {
+    export_kb_draft_impl(id, path).map_err(|e| e.to_string())
+}
+
+/// Write every escalation, stripped of names, emails, ticket keys and other personal details, as JSON;
+/// returns how many were exported
+#[tauri::command]
+pub fn export_anonymized_dataset(path: String) -> Result<usize, String> {
+    export_anonymized_dataset_impl(&path).map_err(|e| e.to_string())
+}
+
+/// Mark a draft as open in this instance; fails if another instance holds it
+#[tauri::command]
+pub fn lock_escalation(id: i64) -> Result<(), String> {
+    lock_escalation_impl(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn unlock_escalation(id: i64) -> Result<(), String> {
+    unlock_escalation_impl(id).map_err(|e| e.to_string())
+}
+
+/// Break a lock left by another instance (e.g. an agent who walked away)
+#[tauri::command]
+pub fn force_unlock(id: i64) -> Result<bool, String> {
+    force_unlock_impl(id).map_err(|e| e.to_string())
+}
+
+pub fn save_escalation_impl(input: EscalationInput) -> AppResult<i64> {
+    let ticket_snapshot = input.ticket_snapshot.map(|snapshot| TicketSnapshot {
+        captured_at: snapshot.captured_at.or_else(|| Some(chrono::Utc::now().to_rfc3339())),
+        ..snapshot
+    });
+    // Relative attachment paths are taken to be inside a watch folder
+    let watch_folders = db::get_preferences()?.watch_folders;
+    let attachments = input
+        .attachments
+        .into_iter()
+        .map(|attachment| AttachmentInput {
+            file_path: watch_folders::resolve(&watch_folders, &attachment.file_path),
+            ..attachment
+        })
+        .collect();
+    let input = EscalationInput {
+        ticket_id: normalize_ticket_key(&input.ticket_id)?,
+        ticket_snapshot,
+        attachments,
+        ..input
+    };
+
     let conn = db::get_connection()?;
 
+    if let Some(parent_id) = input.parent_escalation_id {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM escalations WHERE id = ?)",
+            [parent_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Escalation {} not found", parent_id)));
+        }
+    }
+
+    team_routing::validate(&input.l2_teams, &db::get_preferences()?.l2_teams)?;
+    template_engine::validate_checklist_links(&input.checklist)?;
+
     let checklist_json = serde_json::to_string(&input.checklist)
         .map_err(|e| AppError::Validation(format!("Failed to serialize checklist: {}", e)))?;
+    let teams_json = serde_json::to_string(&input.l2_teams)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize L2 teams: {}", e)))?;
+    let environment_json = input
+        .environment_info
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Validation(format!("Failed to serialize environment info: {}", e)))?;
+    let snapshot_json = input
+        .ticket_snapshot
+        .as_ref()
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| AppError::Validation(format!("Failed to serialize ticket snapshot: {}", e)))?;
+    let incident_id = incident::normalize_id(input.incident_id.as_deref())?;
+    // Block references are expanded once here, so later edits or deletions of a block
+    // don't change how this escalation renders
+    let next_steps = next_step_blocks::expand(&input.next_steps, &next_step_blocks::list(&conn)?);
 
     let id = conn.query_row(
         "INSERT INTO escalations
-        (ticket_id, template_id, problem_summary, checklist, current_status, next_steps, llm_summary, llm_confidence, status)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        (ticket_id, template_id, problem_summary, checklist, current_status, next_steps, llm_summary, llm_confidence, private_notes, ticket_snapshot, summary_language, summary_translation, parent_escalation_id, render_version, l2_teams, environment_info, incident_id, status)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         RETURNING id",
         rusqlite::params![
             input.ticket_id,
@@ -47,27 +285,47 @@ fn save_escalation_impl(input: EscalationInput) -> AppResult<i64> {
             input.problem_summary,
             checklist_json,
             input.current_status,
-            input.next_steps,
+            next_steps,
             input.llm_summary,
             input.llm_confidence,
+            input.private_notes.as_deref().map(str::trim).filter(|n| !n.is_empty()),
+            snapshot_json,
+            input.summary_language,
+            input.summary_translation,
+            input.parent_escalation_id,
+            template_engine::RENDER_VERSION,
+            teams_json,
+            environment_json,
+            incident_id,
             "draft",
         ],
         |row| row.get(0),
     )?;
 
-    // Write audit log
-    conn.execute(
-        "INSERT INTO audit_log (escalation_id, action, details) VALUES (?, ?, ?)",
-        rusqlite::params![
-            id,
-            "created",
-            serde_json::to_string(&serde_json::json!({
-                "ticket_id": input.ticket_id,
-                "template_id": input.template_id,
-            }))
-            .map_err(|e| AppError::Validation(format!("Failed to serialize audit log: {}", e)))?,
-        ],
-    )?;
+    save_attachments(&conn, id, &input.attachments)?;
+
+    for (field, value) in [
+        ("problem_summary", Some(input.problem_summary.as_str())),
+        ("checklist", Some(checklist_json.as_str())),
+        ("current_status", Some(input.current_status.as_str())),
+        ("next_steps", Some(next_steps.as_str())),
+        ("llm_summary", input.llm_summary.as_deref()),
+        ("private_notes", input.private_notes.as_deref().map(str::trim).filter(|n| !n.is_empty())),
+        ("status", Some("draft")),
+        ("environment_info", environment_json.as_deref()),
+        ("incident_id", incident_id.as_deref()),
+    ] {
+        field_history::record(&conn, id, field, None, value)?;
+    }
+
+    insert_audit_log(&conn, id, "created", &serde_json::json!({
+        "ticket_id": input.ticket_id,
+        "template_id": input.template_id,
+        "parent_escalation_id": input.parent_escalation_id,
+        "incident_id": incident_id,
+    }))?;
+
+    log_activity(&conn, activity::KIND_DRAFT_CREATED, id);
 
     Ok(id)
 }
@@ -75,52 +333,317 @@ fn save_escalation_impl(input: EscalationInput) -> AppResult<i64> {
 fn get_escalation_impl(id: i64) -> AppResult<Escalation> {
     let conn = db::get_connection()?;
 
-    let escalation = conn.query_row(
-        "SELECT id, ticket_id, template_id, problem_summary, checklist, current_status, next_steps,
-        llm_summary, llm_confidence, markdown_output, status, posted_at, created_at, updated_at
-        FROM escalations WHERE id = ?",
+    let row = conn.query_row(
+        &format!("SELECT {} FROM escalations WHERE id = ?", ESCALATION_COLUMNS),
         [id],
-        |row| {
-            let checklist_json: String = row.get(4)?;
-            let checklist: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
-                .map_err(|e| {
-                    log::error!("Corrupted checklist data for escalation {}: {}", id, e);
-                    rusqlite::Error::InvalidQuery
-                })?;
-            let status_str: String = row.get(10)?;
-
-            Ok(Escalation {
-                id: row.get(0)?,
-                ticket_id: row.get(1)?,
-                template_id: row.get(2)?,
-                problem_summary: row.get(3)?,
-                checklist,
-                current_status: row.get(5)?,
-                next_steps: row.get(6)?,
-                llm_summary: row.get(7)?,
-                llm_confidence: row.get(8)?,
-                markdown_output: row.get(9)?,
-                status: EscalationStatus::from_str(&status_str),
-                posted_at: row.get(11)?,
-                created_at: row.get(12)?,
-                updated_at: row.get(13)?,
-            })
-        },
+        escalation_from_row,
     )?;
+    let mut escalation = with_text_columns(row)?;
+    escalation.attachments = load_attachments(&conn, id)?;
+
+    if let Some(holder) = locks::held_by_other(&conn, id, &INSTANCE_ID)? {
+        escalation.locked_by = Some(holder.locked_by);
+        escalation.locked_at = Some(holder.locked_at);
+    }
+
+    Ok(escalation)
+}
+
+/// Every escalation, oldest first, in a single query; attachments and locks are left unloaded
+fn list_all_escalations(conn: &rusqlite::Connection) -> AppResult<Vec<Escalation>> {
+    let rows = conn
+        .prepare(&format!("SELECT {} FROM escalations ORDER BY created_at, id", ESCALATION_COLUMNS))?
+        .query_map([], escalation_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    rows.into_iter().map(with_text_columns).collect()
+}
+
+const ESCALATION_COLUMNS: &str = "id, ticket_id, template_id, problem_summary, checklist, current_status, next_steps,
+    llm_summary, llm_confidence, markdown_output, status, posted_at, created_at, updated_at,
+    private_notes, ticket_snapshot, summary_language, summary_translation,
+    markdown_output_zstd, llm_summary_zstd, parent_escalation_id, acknowledged_at, acknowledged_by,
+    render_version, l2_teams, environment_info, incident_id";
+
+/// An escalation row, with its compressed markdown output and LLM summary still to be applied
+type EscalationRow = (Escalation, Option<Vec<u8>>, Option<Vec<u8>>);
+
+fn escalation_from_row(row: &rusqlite::Row) -> rusqlite::Result<EscalationRow> {
+    let id: i64 = row.get(0)?;
+    let checklist_json: String = row.get(4)?;
+    let checklist: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
+        .map_err(|e| {
+            log::error!("Corrupted checklist data for escalation {}: {}", id, e);
+            rusqlite::Error::InvalidQuery
+        })?;
+    let status_str: String = row.get(10)?;
 
+    let escalation = Escalation {
+        id,
+        ticket_id: row.get(1)?,
+        template_id: row.get(2)?,
+        problem_summary: row.get(3)?,
+        checklist,
+        current_status: row.get(5)?,
+        next_steps: row.get(6)?,
+        llm_summary: row.get(7)?,
+        llm_confidence: row.get(8)?,
+        markdown_output: row.get(9)?,
+        status: EscalationStatus::from_str(&status_str),
+        posted_at: row.get(11)?,
+        created_at: row.get(12)?,
+        updated_at: row.get(13)?,
+        attachments: Vec::new(),
+        private_notes: row.get(14)?,
+        // A corrupt snapshot only loses the optional section, not the escalation
+        ticket_snapshot: row
+            .get::<_, Option<String>>(15)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        summary_language: row.get(16)?,
+        summary_translation: row.get(17)?,
+        locked_by: None,
+        locked_at: None,
+        parent_escalation_id: row.get(20)?,
+        acknowledged_at: row.get(21)?,
+        acknowledged_by: row.get(22)?,
+        render_version: row.get(23)?,
+        l2_teams: serde_json::from_str(&row.get::<_, String>(24)?).unwrap_or_default(),
+        environment_info: row
+            .get::<_, Option<String>>(25)?
+            .and_then(|json| serde_json::from_str(&json).ok()),
+        incident_id: row.get(26)?,
+    };
+    Ok((escalation, row.get(18)?, row.get(19)?))
+}
+
+fn with_text_columns((mut escalation, markdown_zstd, llm_summary_zstd): EscalationRow) -> AppResult<Escalation> {
+    escalation.markdown_output = cold_storage::text_column(escalation.markdown_output.take(), markdown_zstd)?;
+    escalation.llm_summary = cold_storage::text_column(escalation.llm_summary.take(), llm_summary_zstd)?;
     Ok(escalation)
 }
 
-fn list_escalations_impl() -> AppResult<Vec<EscalationSummary>> {
+fn save_attachments(
+    conn: &rusqlite::Connection,
+    escalation_id: i64,
+    attachments: &[AttachmentInput],
+) -> AppResult<()> {
+    let storage = attachment_storage::storage_dir(conn)?;
+
+    for (index, attachment) in attachments.iter().enumerate() {
+        // A purged copy has nothing left to stage; keep its metadata and purge stamp
+        let purged_at = match &storage {
+            Some(dir) if attachment_storage::is_staged(dir, &attachment.file_path) => {
+                attachment_storage::purged_at(conn, &attachment.file_path)?
+            }
+            _ => None,
+        };
+        let file_path = match &storage {
+            Some(_) if purged_at.is_some() => attachment.file_path.clone(),
+            Some(dir) => attachment_storage::stage(dir, escalation_id, index, &attachment.file_path)?
+                .to_string_lossy()
+                .into_owned(),
+            None => attachment.file_path.clone(),
+        };
+
+        let annotations = attachment
+            .annotations
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| AppError::Validation(format!("Failed to serialize annotations: {}", e)))?;
+
+        conn.execute(
+            "INSERT INTO attachments (escalation_id, file_path, description, artifact, annotations, purged_at)
+            VALUES (?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                escalation_id,
+                file_path,
+                attachment.description.as_deref().map(str::trim).filter(|d| !d.is_empty()),
+                attachment.artifact.as_deref().map(str::trim).filter(|a| !a.is_empty()),
+                annotations,
+                purged_at,
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+async fn capture_environment_info_impl(escalation_id: Option<i64>) -> AppResult<EnvironmentInfo> {
+    if let Some(id) = escalation_id {
+        let escalation = get_escalation_impl(id)?;
+        if matches!(escalation.status, EscalationStatus::Posted | EscalationStatus::PostedWithErrors) {
+            return Err(AppError::Validation(format!(
+                "Escalation {} has already been posted; its environment can no longer change",
+                id
+            )));
+        }
+    }
+
+    // The configured commands can take seconds each, so keep them off the async runtime
+    let commands = db::get_preferences()?.environment_commands;
+    let info = tauri::async_runtime::spawn_blocking(move || environment_info::capture(&commands))
+        .await
+        .map_err(|e| AppError::File(format!("Environment capture failed: {}", e)))?;
+
+    if let Some(id) = escalation_id {
+        let json = serde_json::to_string(&info)
+            .map_err(|e| AppError::Validation(format!("Failed to serialize environment info: {}", e)))?;
+        let conn = db::get_connection()?;
+        let previous = field_history::current(&conn, id, "environment_info")?;
+        conn.execute(
+            "UPDATE escalations SET environment_info = ?, updated_at = datetime('now') WHERE id = ?",
+            rusqlite::params![json, id],
+        )?;
+        field_history::record(&conn, id, "environment_info", previous.as_deref(), Some(&json))?;
+        insert_audit_log(&conn, id, "environment_captured", &serde_json::json!({
+            "items": info.items.iter().map(|item| &item.label).collect::<Vec<_>>(),
+        }))?;
+    }
+
+    Ok(info)
+}
+
+/// Reading a large clipboard image and encoding it as PNG takes a while, so it runs off the async runtime
+async fn save_clipboard_image_as_attachment_impl(app: AppHandle, escalation_id: i64) -> AppResult<Attachment> {
+    tauri::async_runtime::spawn_blocking(move || save_clipboard_image(&app, escalation_id))
+        .await
+        .map_err(|e| AppError::File(format!("Clipboard capture failed: {}", e)))?
+}
+
+fn save_clipboard_image(app: &AppHandle, escalation_id: i64) -> AppResult<Attachment> {
+    let escalation = get_escalation_impl(escalation_id)?;
+    if matches!(escalation.status, EscalationStatus::Posted | EscalationStatus::PostedWithErrors) {
+        return Err(AppError::Validation(format!(
+            "Escalation {} has already been posted; attach files to the ticket instead",
+            escalation_id
+        )));
+    }
+
+    let image = app
+        .clipboard()
+        .read_image()
+        .map_err(|e| AppError::File(format!("No image on the clipboard: {}", e)))?;
+    let png = redaction::encode_rgba_png(image.width(), image.height(), image.rgba())?;
+
+    let conn = db::get_connection()?;
+    let Some(dir) = attachment_storage::storage_dir(&conn)? else {
+        return Err(AppError::File("Attachment storage is unavailable for this database".to_string()));
+    };
+    let file_name = format!("clipboard-{}.png", chrono::Utc::now().format("%Y%m%d-%H%M%S"));
+    let path = attachment_storage::stage_bytes(&dir, escalation_id, escalation.attachments.len(), &file_name, &png)?;
+
+    conn.execute(
+        "INSERT INTO attachments (escalation_id, file_path, description) VALUES (?, ?, ?)",
+        rusqlite::params![escalation_id, path.to_string_lossy(), "Pasted screenshot"],
+    )?;
+
+    load_attachments(&conn, escalation_id)?
+        .pop()
+        .ok_or_else(|| AppError::NotFound(format!("Attachment for escalation {} not saved", escalation_id)))
+}
+
+fn load_attachments(conn: &rusqlite::Connection, escalation_id: i64) -> AppResult<Vec<Attachment>> {
+    query_attachments(conn, "escalation_id = ?", escalation_id)
+}
+
+/// Attachments of every escalation for a ticket, so uploads outside an escalation still find their redactions
+pub fn load_ticket_attachments(ticket_id: &str) -> AppResult<Vec<Attachment>> {
+    let conn = db::get_connection()?;
+    query_attachments(&conn, "escalation_id IN (SELECT id FROM escalations WHERE ticket_id = ?)", ticket_id)
+}
+
+fn query_attachments(
+    conn: &rusqlite::Connection,
+    filter: &str,
+    param: impl rusqlite::ToSql,
+) -> AppResult<Vec<Attachment>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, escalation_id, file_path, description, created_at, purged_at, artifact, annotations
+        FROM attachments WHERE {} ORDER BY id",
+        filter
+    ))?;
+
+    let attachments = stmt
+        .query_map([param], |row| {
+            Ok(Attachment {
+                id: row.get(0)?,
+                escalation_id: row.get(1)?,
+                file_path: row.get(2)?,
+                description: row.get(3)?,
+                created_at: row.get(4)?,
+                purged_at: row.get(5)?,
+                artifact: row.get(6)?,
+                // Unreadable annotations must not pass as "no redactions"
+                annotations: json_column(row, 7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(attachments)
+}
+
+/// Decode an optional JSON column; a value that doesn't parse is an error, not None
+fn json_column<T: serde::de::DeserializeOwned>(row: &rusqlite::Row, index: usize) -> rusqlite::Result<Option<T>> {
+    row.get::<_, Option<String>>(index)?
+        .map(|json| {
+            serde_json::from_str(&json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(index, rusqlite::types::Type::Text, Box::new(e))
+            })
+        })
+        .transpose()
+}
+
+/// Rebuild the render input from a stored escalation
+fn escalation_to_input(escalation: &Escalation) -> EscalationInput {
+    EscalationInput {
+        ticket_id: escalation.ticket_id.clone(),
+        template_id: escalation.template_id,
+        problem_summary: escalation.problem_summary.clone(),
+        checklist: escalation.checklist.clone(),
+        current_status: escalation.current_status.clone(),
+        next_steps: escalation.next_steps.clone(),
+        llm_summary: escalation.llm_summary.clone(),
+        llm_confidence: escalation.llm_confidence.clone(),
+        attachments: escalation
+            .attachments
+            .iter()
+            .map(|a| AttachmentInput {
+                file_path: a.file_path.clone(),
+                description: a.description.clone(),
+                artifact: a.artifact.clone(),
+                annotations: a.annotations.clone(),
+            })
+            .collect(),
+        private_notes: escalation.private_notes.clone(),
+        ticket_snapshot: escalation.ticket_snapshot.clone(),
+        summary_language: escalation.summary_language.clone(),
+        summary_translation: escalation.summary_translation.clone(),
+        parent_escalation_id: escalation.parent_escalation_id,
+        previous_escalation: None,
+        // Drafts follow the current layout; the version is pinned when they are posted
+        render_version: (!matches!(escalation.status, EscalationStatus::Draft)).then_some(escalation.render_version),
+        l2_teams: escalation.l2_teams.clone(),
+        environment_info: escalation.environment_info.clone(),
+        incident_id: escalation.incident_id.clone(),
+    }
+}
+
+pub fn list_escalations_impl() -> AppResult<Vec<EscalationSummary>> {
+    list_escalations_filtered(None)
+}
+
+fn list_escalations_filtered(incident_id: Option<&str>) -> AppResult<Vec<EscalationSummary>> {
     let conn = db::get_connection()?;
+    let incident_id = incident::normalize_id(incident_id)?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, ticket_id, problem_summary, status, created_at
+        "SELECT id, ticket_id, problem_summary, status, created_at, incident_id
         FROM escalations
+        WHERE ?1 IS NULL OR incident_id = ?1 COLLATE NOCASE
         ORDER BY created_at DESC"
     )?;
 
-    let summaries = stmt.query_map([], |row| {
+    let summaries = stmt.query_map([incident_id], |row| {
         let status_str: String = row.get(3)?;
         Ok(EscalationSummary {
             id: row.get(0)?,
@@ -128,6 +651,7 @@ fn list_escalations_impl() -> AppResult<Vec<EscalationSummary>> {
             problem_summary: row.get(2)?,
             status: EscalationStatus::from_str(&status_str),
             created_at: row.get(4)?,
+            incident_id: row.get(5)?,
         })
     })?
     .collect::<Result<Vec<_>, _>>()?;
@@ -135,11 +659,45 @@ fn list_escalations_impl() -> AppResult<Vec<EscalationSummary>> {
     Ok(summaries)
 }
 
+fn link_incident_impl(escalation_id: i64, incident_id: Option<&str>) -> AppResult<()> {
+    let conn = db::get_connection()?;
+    let incident_id = incident::normalize_id(incident_id)?;
+
+    let previous: Option<String> = conn
+        .query_row("SELECT incident_id FROM escalations WHERE id = ?", [escalation_id], |row| row.get(0))
+        .optional()?
+        .ok_or_else(|| AppError::NotFound(format!("Escalation {} not found", escalation_id)))?;
+    if previous == incident_id {
+        return Ok(());
+    }
+
+    conn.execute(
+        "UPDATE escalations SET incident_id = ?, updated_at = datetime('now') WHERE id = ?",
+        rusqlite::params![incident_id, escalation_id],
+    )?;
+    field_history::record(&conn, escalation_id, "incident_id", previous.as_deref(), incident_id.as_deref())?;
+    insert_audit_log(&conn, escalation_id, "incident_linked", &serde_json::json!({
+        "previous": previous,
+        "incident_id": incident_id,
+    }))?;
+
+    Ok(())
+}
+
 fn delete_escalation_impl(id: i64) -> AppResult<()> {
     let conn = db::get_connection()?;
 
-    // Delete audit log entries first (FK constraint)
+    // Delete dependent rows first (FK constraint)
     conn.execute("DELETE FROM audit_log WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM attachments WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM bounces WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM escalation_locks WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM attachment_upload_failures WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM escalation_embeddings WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM audit_field_history WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM jobs WHERE escalation_id = ?", [id])?;
+    conn.execute("DELETE FROM kb_drafts WHERE escalation_id = ?", [id])?;
+    conn.execute("UPDATE escalations SET parent_escalation_id = NULL WHERE parent_escalation_id = ?", [id])?;
 
     // Delete escalation
     let rows_affected = conn.execute("DELETE FROM escalations WHERE id = ?", [id])?;
@@ -148,51 +706,623 @@ fn delete_escalation_impl(id: i64) -> AppResult<()> {
         return Err(AppError::NotFound(format!("Escalation {} not found", id)));
     }
 
+    if let Some(dir) = attachment_storage::storage_dir(&conn)? {
+        attachment_storage::remove_escalation_files(&dir, id)?;
+    }
+
+    Ok(())
+}
+
+fn lock_escalation_impl(id: i64) -> AppResult<()> {
+    let conn = db::get_connection()?;
+    locks::acquire(&conn, id, &INSTANCE_ID, &locks::current_user())
+}
+
+fn unlock_escalation_impl(id: i64) -> AppResult<()> {
+    let conn = db::get_connection()?;
+    locks::release(&conn, id, &INSTANCE_ID)
+}
+
+fn force_unlock_impl(id: i64) -> AppResult<bool> {
+    let conn = db::get_connection()?;
+    let holder = locks::held_by_other(&conn, id, &INSTANCE_ID)?;
+    let removed = locks::force_release(&conn, id)?;
+
+    if let Some(holder) = holder {
+        write_audit_log(id, "force_unlocked", &serde_json::json!({
+            "locked_by": holder.locked_by,
+            "locked_at": holder.locked_at,
+            "unlocked_by": locks::current_user(),
+        }))?;
+    }
+
+    Ok(removed)
+}
+
+fn score_escalation_impl(id: i64) -> AppResult<QualityScore> {
+    let escalation = get_escalation_impl(id)?;
+    Ok(quality::score(&escalation))
+}
+
+fn lint_escalation_impl(id: i64) -> AppResult<Vec<LintWarning>> {
+    let escalation = get_escalation_impl(id)?;
+    let terminology = db::get_preferences()?.terminology;
+
+    let fields = [
+        ("problem_summary", Some(escalation.problem_summary.as_str())),
+        ("current_status", Some(escalation.current_status.as_str())),
+        ("next_steps", Some(escalation.next_steps.as_str())),
+        ("llm_summary", escalation.llm_summary.as_deref()),
+    ];
+
+    Ok(fields
+        .iter()
+        .filter_map(|(field, text)| text.map(|text| lint::lint_text(field, text, &terminology)))
+        .flatten()
+        .collect())
+}
+
+async fn suggest_priority_impl(id: i64, use_llm: bool) -> AppResult<PrioritySuggestion> {
+    let escalation = get_escalation_impl(id)?;
+    let heuristic = priority::suggest(&escalation);
+    if !use_llm {
+        return Ok(heuristic);
+    }
+
+    // The LLM is advisory; any failure falls back to the heuristic
+    match llm_priority(&escalation).await {
+        Ok(Some(suggested)) => Ok(PrioritySuggestion {
+            priority: suggested.to_string(),
+            reasons: heuristic.reasons,
+            source: "llm".to_string(),
+        }),
+        Ok(None) => Ok(heuristic),
+        Err(e) => {
+            log::warn!("LLM priority suggestion failed, using heuristic: {}", e);
+            Ok(heuristic)
+        }
+    }
+}
+
+async fn llm_priority(escalation: &Escalation) -> AppResult<Option<&'static str>> {
+    let Some(config) = db::get_api_config()? else {
+        return Ok(None);
+    };
+    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
+    if !client.is_available().await? {
+        return Ok(None);
+    }
+
+    let checked = escalation.checklist.iter().filter(|item| item.checked).count();
+    let text = format!(
+        "Problem: {}\nCurrent status: {}\nNext steps: {}\nChecklist: {} of {} steps completed",
+        escalation.problem_summary,
+        escalation.current_status,
+        escalation.next_steps,
+        checked,
+        escalation.checklist.len()
+    );
+
+    let reply = client.suggest_priority(&text, &priority::PRIORITIES).await?;
+    Ok(priority::parse_priority(&reply))
+}
+
+fn mark_bounced_impl(escalation_id: i64, reason: String) -> AppResult<()> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::Validation("A bounce reason is required".into()));
+    }
+
+    let escalation = get_escalation_impl(escalation_id)?;
+    if !matches!(
+        escalation.status,
+        EscalationStatus::Posted | EscalationStatus::PostedWithErrors
+    ) {
+        return Err(AppError::Validation(format!(
+            "Escalation {} has not been posted, so it cannot be bounced",
+            escalation_id
+        )));
+    }
+
+    let score = quality::score(&escalation).score;
+    let conn = db::get_connection()?;
+
+    conn.execute(
+        "INSERT INTO bounces (escalation_id, reason, quality_score) VALUES (?, ?, ?)",
+        rusqlite::params![escalation_id, reason, score],
+    )?;
+
+    let old_status = field_history::current(&conn, escalation_id, "status")?;
+    // posted_at is kept so bounced escalations still count as posted in reports
+    conn.execute(
+        "UPDATE escalations SET status = 'bounced', updated_at = datetime('now') WHERE id = ?",
+        [escalation_id],
+    )?;
+    field_history::record(&conn, escalation_id, "status", old_status.as_deref(), Some("bounced"))?;
+    log_activity(&conn, activity::KIND_ESCALATION_BOUNCED, escalation_id);
+
+    write_audit_log(escalation_id, "bounced", &serde_json::json!({
+        "reason": reason,
+        "quality_score": score,
+    }))?;
+
+    Ok(())
+}
+
+fn reescalate_impl(original_id: i64) -> AppResult<i64> {
+    let original = get_escalation_impl(original_id)?;
+    if matches!(original.status, EscalationStatus::Draft) {
+        return Err(AppError::Validation(format!(
+            "Escalation {} has not been posted yet; edit it instead of re-escalating",
+            original_id
+        )));
+    }
+
+    // Same ticket and steps to re-check; status and next steps are for the agent to fill in again
+    let checklist = original
+        .checklist
+        .iter()
+        .map(|item| ChecklistItem { checked: false, ..item.clone() })
+        .collect();
+
+    let id = save_escalation_impl(EscalationInput {
+        ticket_id: original.ticket_id.clone(),
+        template_id: original.template_id,
+        problem_summary: original.problem_summary.clone(),
+        checklist,
+        current_status: String::new(),
+        next_steps: String::new(),
+        llm_summary: None,
+        llm_confidence: None,
+        attachments: Vec::new(),
+        private_notes: None,
+        ticket_snapshot: None,
+        summary_language: original.summary_language.clone(),
+        summary_translation: original.summary_translation.clone(),
+        parent_escalation_id: Some(original_id),
+        previous_escalation: None,
+        render_version: None,
+        // Same teams to hand to; what each is asked is for the agent to fill in again
+        l2_teams: original
+            .l2_teams
+            .iter()
+            .map(|ask| TeamAsk { asks: String::new(), ..ask.clone() })
+            .collect(),
+        environment_info: None,
+        // Still the same incident, if there was one
+        incident_id: original.incident_id.clone(),
+    })?;
+
+    write_audit_log(original_id, "reescalated", &serde_json::json!({ "new_escalation_id": id }))?;
+
+    Ok(id)
+}
+
+/// The parent's details for the "Previous escalation" section; a missing parent just drops the section
+fn previous_escalation(parent_id: i64) -> Option<PreviousEscalation> {
+    match get_escalation_impl(parent_id) {
+        Ok(parent) => Some(PreviousEscalation {
+            id: parent.id,
+            ticket_id: parent.ticket_id,
+            problem_summary: parent.problem_summary,
+            current_status: parent.current_status,
+            next_steps: parent.next_steps,
+            posted_at: parent.posted_at,
+        }),
+        Err(e) => {
+            log::warn!("Failed to load previous escalation {}: {}", parent_id, e);
+            None
+        }
+    }
+}
+
+fn get_bounce_report_impl() -> AppResult<BounceReport> {
+    bounces::report(&*db::get_connection()?)
+}
+
+fn export_handoff_bundle_impl(id: i64, path: String, include_private_notes: bool) -> AppResult<BundleManifest> {
+    let escalation = get_escalation_impl(id)?;
+
+    // Prefer what was actually posted; drafts are rendered fresh
+    let markdown = match &escalation.markdown_output {
+        Some(markdown) => markdown.clone(),
+        None => render_markdown_impl(escalation_to_input(&escalation))?,
+    };
+
+    // Private notes stay local unless the agent explicitly opts in
+    let private_notes = escalation
+        .private_notes
+        .as_deref()
+        .filter(|_| include_private_notes);
+    let manifest = bundle::write_bundle(&escalation, &markdown, private_notes, std::path::Path::new(&path))?;
+
+    write_audit_log(id, "exported_bundle", &serde_json::json!({
+        "files": manifest.files.len(),
+        "included_private_notes": private_notes.is_some(),
+        "missing_attachments": manifest.missing_attachments.len(),
+    }))?;
+
+    Ok(manifest)
+}
+
+async fn draft_kb_article_impl(escalation_id: i64, resolution: Option<String>) -> AppResult<KbDraft> {
+    let escalation = get_escalation_impl(escalation_id)?;
+    if matches!(escalation.status, EscalationStatus::Draft) {
+        return Err(AppError::Validation(
+            "Only escalations that have been handed off can be drafted into KB articles".to_string(),
+        ));
+    }
+
+    let resolution = resolution
+        .filter(|r| !r.trim().is_empty())
+        .unwrap_or_else(|| escalation.current_status.clone());
+    if resolution.trim().is_empty() {
+        return Err(AppError::Validation("Describe how the issue was resolved first".to_string()));
+    }
+
+    let config = db::get_api_config()?
+        .ok_or_else(|| AppError::Validation("No API config found. Please configure Ollama in Settings.".to_string()))?;
+    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
+    if !client.is_available().await? {
+        return Err(AppError::Ollama("Ollama is not running. Start it with `ollama serve`.".to_string()));
+    }
+
+    let started = std::time::Instant::now();
+    let article = client.draft_kb_article(&kb::source_text(&escalation, &resolution)).await?;
+    METRICS.record_llm_latency(started.elapsed());
+
+    let (title, markdown) = kb::split_title(&article, &escalation.problem_summary);
+    let draft = kb::save(&*db::get_connection()?, escalation_id, &title, &markdown)?;
+    write_audit_log(escalation_id, "kb_drafted", &serde_json::json!({ "kb_draft_id": draft.id }))?;
+
+    Ok(draft)
+}
+
+fn list_kb_drafts_impl() -> AppResult<Vec<KbDraft>> {
+    kb::list(&*db::get_connection()?)
+}
+
+fn export_kb_draft_impl(id: i64, path: String) -> AppResult<()> {
+    let draft = kb::get(&*db::get_connection()?, id)?;
+    std::fs::write(&path, &draft.markdown)?;
     Ok(())
 }
 
+fn export_anonymized_dataset_impl(path: &str) -> AppResult<usize> {
+    let (escalations, bounce_counts, attachment_counts) = {
+        let conn = db::get_connection()?;
+        let escalations = list_all_escalations(&conn)?;
+        let bounce_counts = conn
+            .prepare("SELECT escalation_id, COUNT(*) FROM bounces GROUP BY escalation_id")?
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        let attachment_counts = conn
+            .prepare("SELECT escalation_id, COUNT(*) FROM attachments GROUP BY escalation_id")?
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, usize>(1)?)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        (escalations, bounce_counts, attachment_counts)
+    };
+    let templates: HashMap<i64, Template> = templates::list_templates_impl()?
+        .into_iter()
+        .map(|template| (template.id, template))
+        .collect();
+
+    let redactor = redaction::TextRedactor::new(&db::get_preferences()?.ticket_key_pattern, &[])?;
+    let dataset = anonymized_dataset::build(&escalations, &bounce_counts, &attachment_counts, &templates, &redactor);
+
+    let json = serde_json::to_vec_pretty(&dataset)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize dataset: {}", e)))?;
+    std::fs::write(path, json)?;
+    Ok(dataset.escalations.len())
+}
+
+fn check_output_size_impl(id: i64) -> AppResult<OutputSizeReport> {
+    let escalation = get_escalation_impl(id)?;
+    let markdown = render_markdown_impl(escalation_to_input(&escalation))?;
+    let preferences = db::get_preferences()?;
+    Ok(output_size::measure(&markdown, &output_budget(&preferences, id)))
+}
+
+/// Budgets from preferences, leaving room for the correlation footer
+fn output_budget(preferences: &Preferences, escalation_id: i64) -> output_size::Budget {
+    let footer = correlation::append_footer("", &preferences.correlation_marker, escalation_id);
+    output_size::Budget {
+        max_chars: preferences.max_comment_chars.saturating_sub(footer.chars().count()),
+        sections: preferences.section_char_limits.clone(),
+    }
+}
+
+/// Trim over-budget output for posting, attaching the full text to the ticket first
+async fn fit_to_budget(
+    client: &JiraClient,
+    escalation: &Escalation,
+    markdown: &str,
+    preferences: &Preferences,
+) -> AppResult<String> {
+    let budget = output_budget(preferences, escalation.id);
+    if !output_size::measure(markdown, &budget).over_budget {
+        return Ok(markdown.to_string());
+    }
+
+    // Named after the content so a retry reuses the file an earlier attempt already attached
+    let digest = bundle::sha256_hex(markdown.as_bytes());
+    let file_name = format!("escalation-{}-full-text-{}.md", escalation.id, &digest[..8]);
+    let existing = client
+        .list_attachments(&escalation.ticket_id)
+        .await?
+        .into_iter()
+        .find(|a| a.filename == file_name);
+    let url = match existing {
+        Some(attachment) => attachment.content,
+        None => attach_full_text(client, &escalation.ticket_id, &file_name, markdown).await?,
+    };
+
+    let note = match url {
+        Some(url) => format!("Shortened to fit the length limit. Full text: [{}]({})", file_name, url),
+        None => format!("Shortened to fit the length limit. Full text attached as {}", file_name),
+    };
+    Ok(output_size::truncate(markdown, &budget, &note))
+}
+
+/// Upload `markdown` as `file_name` from a private temp directory
+async fn attach_full_text(
+    client: &JiraClient,
+    ticket_id: &str,
+    file_name: &str,
+    markdown: &str,
+) -> AppResult<Option<String>> {
+    // create_dir fails if the path exists, so nothing can pre-create or swap the file under a random name
+    let dir = std::env::temp_dir().join(format!("tickethandoff-{:016x}", rand::random::<u64>()));
+    std::fs::create_dir(&dir)?;
+    let path = dir.join(file_name);
+    let url = match std::fs::write(&path, markdown) {
+        Ok(()) => client.attach_file_with_url(ticket_id, &path).await,
+        Err(e) => Err(e.into()),
+    };
+    let _ = std::fs::remove_dir_all(&dir);
+    url
+}
+
+fn check_expected_artifacts_impl(id: i64) -> AppResult<Vec<String>> {
+    let escalation = get_escalation_impl(id)?;
+    missing_artifacts(&escalation)
+}
+
+fn missing_artifacts(escalation: &Escalation) -> AppResult<Vec<String>> {
+    let Some(template_id) = escalation.template_id else {
+        return Ok(vec![]);
+    };
+    let template = templates::get_template_impl(template_id)?;
+    Ok(quality::missing_artifacts(&template.expected_artifacts, &escalation.attachments))
+}
+
+fn generate_print_view_impl(id: i64) -> AppResult<String> {
+    escalation_document(id, "Printed")
+}
+
+/// Page served for share links; the same document as the print view
+pub fn render_shared_view(id: i64) -> AppResult<String> {
+    escalation_document(id, "Shared read-only view generated")
+}
+
+fn create_share_link_impl(escalation_id: i64, ttl_minutes: Option<i64>) -> AppResult<String> {
+    let preferences = db::get_preferences()?;
+    if !preferences.local_api_enabled {
+        return Err(AppError::Validation(
+            "Share links need the local API. Enable it in Settings and restart the app.".to_string(),
+        ));
+    }
+
+    // Fails early for unknown escalations instead of handing out a dead link
+    get_escalation_impl(escalation_id)?;
+
+    let ttl = ttl_minutes
+        .unwrap_or(share_links::DEFAULT_TTL_MINUTES)
+        .clamp(1, share_links::MAX_TTL_MINUTES);
+    let expires = chrono::Utc::now().timestamp() + ttl * 60;
+    let url = share_links::url(&preferences.local_api_bind, escalation_id, expires);
+
+    write_audit_log(escalation_id, "share_link_created", &serde_json::json!({ "ttl_minutes": ttl }))?;
+    Ok(url)
+}
+
+fn escalation_document(id: i64, footer_label: &str) -> AppResult<String> {
+    let escalation = get_escalation_impl(id)?;
+
+    // Print what was posted when there is a posted version, like the bundle export
+    let markdown = match &escalation.markdown_output {
+        Some(markdown) => markdown.clone(),
+        None => render_markdown_impl(escalation_to_input(&escalation))?,
+    };
+
+    let meta = [
+        ("Ticket", escalation.ticket_id.clone()),
+        ("Escalation", format!("#{}", escalation.id)),
+        ("Status", escalation.status.as_str().to_string()),
+        ("Created", escalation.created_at.clone()),
+        ("Posted", escalation.posted_at.clone().unwrap_or_else(|| "Not posted".to_string())),
+    ];
+    let footer = format!(
+        "{} {} from escalation #{}",
+        footer_label,
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        escalation.id
+    );
+
+    Ok(html::render_print_document(
+        &format!("Escalation {}", escalation.ticket_id),
+        &meta,
+        &markdown,
+        &footer,
+    ))
+}
+
+fn render_with_version_impl(id: i64, version: Option<u32>) -> AppResult<String> {
+    let input = escalation_to_input(&get_escalation_impl(id)?);
+    let input = EscalationInput {
+        render_version: version.or(input.render_version),
+        ..input
+    };
+    render_markdown_impl(input)
+}
+
+fn render_output_impl(input: EscalationInput, format: Option<OutputFormat>) -> AppResult<String> {
+    let format = match format {
+        Some(format) => format,
+        None => db::get_preferences()?.output_format,
+    };
+    let markdown = render_markdown_impl(input)?;
+    Ok(output_format::render(&markdown, format))
+}
+
+/// An unsaved draft's block references expanded as saving would expand them
+fn preview_input(input: EscalationInput) -> AppResult<EscalationInput> {
+    let blocks = next_step_blocks::list(&*db::get_connection()?)?;
+    Ok(EscalationInput {
+        next_steps: next_step_blocks::expand(&input.next_steps, &blocks),
+        ..input
+    })
+}
+
 fn render_markdown_impl(input: EscalationInput) -> AppResult<String> {
     // Fetch template if template_id is provided
-    let template = if let Some(template_id) = input.template_id {
-        let conn = db::get_connection()?;
+    let template = input
+        .template_id
+        .and_then(|template_id| templates::get_template_impl(template_id).ok());
+
+    render_with_template(template.as_ref(), input)
+}
+
+fn render_with_template(template: Option<&Template>, mut input: EscalationInput) -> AppResult<String> {
+    if input.previous_escalation.is_none() {
+        input.previous_escalation = input.parent_escalation_id.and_then(previous_escalation);
+    }
+    let preferences = db::get_preferences()?;
+    input.l2_teams = team_routing::with_mentions(&input.l2_teams, &preferences.l2_teams);
+    // The stored translation only reaches the output when bilingual output is on
+    if !preferences.bilingual_output {
+        input.summary_translation = None;
+    }
+
+    let blocks = next_step_blocks::list(&*db::get_connection()?)?;
+    template_engine::render_markdown(template, &input, &blocks)
+}
 
+fn preview_template_impact_impl(template_id: i64, proposed: Template) -> AppResult<TemplateImpact> {
+    let current = templates::get_template_impl(template_id)?;
+    let proposed = Template { id: template_id, ..proposed };
+
+    let draft_ids: Vec<i64> = {
+        let conn = db::get_connection()?;
         let mut stmt = conn.prepare(
-            "SELECT id, name, description, category, checklist_items, l2_team FROM templates WHERE id = ?"
+            "SELECT id FROM escalations WHERE template_id = ? AND status = 'draft' ORDER BY updated_at DESC",
         )?;
+        let ids = stmt
+            .query_map([template_id], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        ids
+    };
 
-        stmt.query_row([template_id], |row| {
-            let checklist_json: String = row.get(4)?;
-            let checklist_items: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
-                .map_err(|e| {
-                    log::error!("Corrupted template checklist data for template {}: {}", template_id, e);
-                    rusqlite::Error::InvalidQuery
-                })?;
+    let mut drafts = Vec::new();
+    for id in draft_ids {
+        let escalation = get_escalation_impl(id)?;
+        let input = escalation_to_input(&escalation);
+        let before = render_with_template(Some(&current), input.clone());
+        let after = render_with_template(Some(&proposed), input);
 
-            Ok(crate::models::Template {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                category: row.get(3)?,
-                checklist_items,
-                l2_team: row.get(5)?,
-            })
-        }).ok()
-    } else {
-        None
-    };
+        drafts.push(match (before, after) {
+            (Ok(before), Ok(after)) => {
+                let diff = line_diff::diff(&before, &after);
+                let changed = line_diff::has_changes(&diff);
+                DraftImpact {
+                    escalation_id: id,
+                    ticket_id: escalation.ticket_id,
+                    changed,
+                    diff: if changed { diff } else { Vec::new() },
+                    error: None,
+                }
+            }
+            (Err(e), _) | (_, Err(e)) => DraftImpact {
+                escalation_id: id,
+                ticket_id: escalation.ticket_id,
+                changed: false,
+                diff: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        });
+    }
+
+    Ok(TemplateImpact { template_id, drafts })
+}
+
+#[tauri::command]
+pub async fn post_escalation(
+    app: AppHandle,
+    id: i64,
+    file_paths: Vec<String>,
+    watch_ticket: Option<bool>,
+    priority: Option<String>,
+    confirm_freeze: Option<bool>,
+) -> Result<(), String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    let freeze = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())?;
+    let job_files = file_paths.clone();
+    let result = tracked(
+        jobs::KIND_POST,
+        id,
+        &job_files,
+        post_escalation_impl(app, id, file_paths, watch_ticket, priority),
+    )
+    .await
+    .map_err(|e| e.to_string());
+    let audited = audit_freeze_override(id, freeze).map_err(|e| e.to_string());
+    result.and(audited)
+}
+
+/// Refetch the ticket and report whether it is closed or already with L2
+#[tauri::command]
+pub async fn check_ticket_status(app: AppHandle, id: i64) -> Result<Vec<String>, String> {
+    check_ticket_status_impl(app, id)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    template_engine::render_markdown(template.as_ref(), &input)
+#[tauri::command]
+pub async fn dry_run_post(
+    app: AppHandle,
+    escalation_id: i64,
+    sandbox_key: Option<String>,
+) -> Result<String, String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    dry_run_post_impl(app, escalation_id, sandbox_key)
+        .await
+        .map_err(|e| e.to_string())
 }
 
+/// Re-upload only the attachments that failed, without reposting the comment
 #[tauri::command]
-pub async fn post_escalation(
+pub async fn retry_failed_attachments(
     app: AppHandle,
-    id: i64,
-    file_paths: Vec<String>,
+    escalation_id: i64,
+    confirm_freeze: Option<bool>,
 ) -> Result<(), String> {
-    post_escalation_impl(app, id, file_paths)
-        .await
-        .map_err(|e| e.to_string())
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    let freeze = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())?;
+    let result = tracked(
+        jobs::KIND_RETRY_ATTACHMENTS,
+        escalation_id,
+        &[],
+        retry_failed_attachments_impl(app, escalation_id),
+    )
+    .await
+    .map_err(|e| e.to_string());
+    let audited = audit_freeze_override(escalation_id, freeze).map_err(|e| e.to_string());
+    result.and(audited)
 }
 
 #[tauri::command]
@@ -200,58 +1330,216 @@ pub async fn retry_post_escalation(
     app: AppHandle,
     id: i64,
     file_paths: Vec<String>,
+    rerender: Option<bool>,
+    watch_ticket: Option<bool>,
+    priority: Option<String>,
+    confirm_freeze: Option<bool>,
 ) -> Result<(), String> {
-    retry_post_escalation_impl(app, id, file_paths)
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    let freeze = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())?;
+    let job_files = file_paths.clone();
+    let result = tracked(
+        jobs::KIND_RETRY_POST,
+        id,
+        &job_files,
+        retry_post_escalation_impl(app, id, file_paths, rerender, watch_ticket, priority),
+    )
+    .await
+    .map_err(|e| e.to_string());
+    let audited = audit_freeze_override(id, freeze).map_err(|e| e.to_string());
+    result.and(audited)
+}
+
+/// Posts and uploads cut short by a quit or crash, for the UI to offer on launch
+#[tauri::command]
+pub fn list_interrupted_jobs() -> Result<Vec<InterruptedJob>, String> {
+    list_interrupted_jobs_impl().map_err(|e| e.to_string())
+}
+
+/// Finish an interrupted job without posting the comment twice
+#[tauri::command]
+pub async fn resume_interrupted_job(app: AppHandle, job_id: i64, confirm_freeze: Option<bool>) -> Result<(), String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    let freeze = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())?;
+    resume_interrupted_job_impl(app, job_id, freeze)
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn dismiss_interrupted_job(job_id: i64) -> Result<(), String> {
+    dismiss_interrupted_job_impl(job_id).map_err(|e| e.to_string())
+}
+
+/// Record a job while `work` runs. Failures are persisted on the escalation,
+/// so the row is only left behind when the app quits mid-job.
+async fn tracked<T>(
+    kind: &str,
+    escalation_id: i64,
+    file_paths: &[String],
+    work: impl std::future::Future<Output = Result<T, Box<dyn std::error::Error>>>,
+) -> Result<T, Box<dyn std::error::Error>> {
+    let job_id = jobs::start(&*db::get_connection()?, kind, escalation_id, file_paths, &INSTANCE_ID)?;
+    let result = work.await;
+    jobs::finish(&*db::get_connection()?, job_id)?;
+    result
+}
+
+fn list_interrupted_jobs_impl() -> AppResult<Vec<InterruptedJob>> {
+    let conn = db::get_connection()?;
+    jobs::list_interrupted(&conn)
+}
+
+fn dismiss_interrupted_job_impl(job_id: i64) -> AppResult<()> {
+    let conn = db::get_connection()?;
+    let job = jobs::get_interrupted(&conn, job_id)?;
+    jobs::finish(&conn, job.id)
+}
+
+async fn resume_interrupted_job_impl(
+    app: AppHandle,
+    job_id: i64,
+    freeze: Option<FreezeWindow>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let job = jobs::get_interrupted(&*db::get_connection()?, job_id)?;
+
+    let result = match job.kind.as_str() {
+        jobs::KIND_RETRY_ATTACHMENTS => retry_failed_attachments_impl(app, job.escalation_id).await,
+        _ => resume_post(app, &job).await,
+    };
+
+    // As with live jobs, a failed resume is recorded on the escalation for the normal retry paths
+    jobs::finish(&*db::get_connection()?, job_id)?;
+    let audited = audit_freeze_override(job.escalation_id, freeze);
+    result.and(audited.map_err(Into::into))
+}
+
+/// The quit may have happened before or after the comment landed; Jira decides which
+async fn resume_post(app: AppHandle, job: &InterruptedJob) -> Result<(), Box<dyn std::error::Error>> {
+    let id = job.escalation_id;
+    let escalation = get_escalation_impl(id)?;
+    if matches!(escalation.status, EscalationStatus::Posted) {
+        return Ok(());
+    }
+
+    let client = get_jira_client(app.clone()).await?;
+    let marker = db::get_preferences()?.correlation_marker;
+    let already_posted = client
+        .list_comments(&escalation.ticket_id)
+        .await?
+        .iter()
+        .any(|c| correlation::extract_escalation_id(&c.body_text, &marker) == Some(id));
+
+    if !already_posted {
+        return retry_post_escalation_impl(app, id, job.file_paths.clone(), None, None, None).await;
+    }
+
+    // Only the uploads are unaccounted for; queue them all for the targeted retry
+    let markdown = match escalation.markdown_output.clone() {
+        Some(markdown) => markdown,
+        None => render_markdown_impl(escalation_to_input(&escalation))?,
+    };
+    if job.file_paths.is_empty() {
+        update_escalation_status(id, "posted", Some(&markdown), None)?;
+        return Ok(());
+    }
+
+    let pending: Vec<(String, String)> = job
+        .file_paths
+        .iter()
+        .map(|path| (path.clone(), "Upload interrupted when the app quit".to_string()))
+        .collect();
+    save_upload_failures(id, &pending)?;
+    update_escalation_status(id, "posted_with_errors", Some(&markdown), Some(&upload_failure_message(&pending)))?;
+
+    retry_failed_attachments_impl(app, id).await
+}
+
 async fn post_escalation_impl(
     app: AppHandle,
     id: i64,
     file_paths: Vec<String>,
+    watch_ticket: Option<bool>,
+    priority: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Load escalation
     let escalation = get_escalation_impl(id)?;
+    // Details added to the draft since it was created render in the current layout,
+    // and retries keep the layout it was first posted with
+    let escalation = Escalation {
+        render_version: pin_render_version(id)?,
+        ..escalation
+    };
+
+    // Enforce the configured quality bar before anything reaches Jira
+    ensure_min_quality(&escalation)?;
+
+    // Missing artifacts only warn; the UI shows them before the agent confirms
+    let missing_artifacts = missing_artifacts(&escalation).unwrap_or_default();
+    if !missing_artifacts.is_empty() {
+        log::warn!(
+            "Posting escalation {} without expected artifacts: {}",
+            id,
+            missing_artifacts.join(", ")
+        );
+    }
+
+    // Screen attachments before anything reaches Jira
+    ensure_attachments_allowed(&file_paths).await?;
 
     // Render markdown
-    let input = EscalationInput {
-        ticket_id: escalation.ticket_id.clone(),
-        template_id: escalation.template_id,
-        problem_summary: escalation.problem_summary.clone(),
-        checklist: escalation.checklist.clone(),
-        current_status: escalation.current_status.clone(),
-        next_steps: escalation.next_steps.clone(),
-        llm_summary: escalation.llm_summary.clone(),
-        llm_confidence: escalation.llm_confidence.clone(),
-    };
-    let markdown = render_markdown_impl(input)?;
+    let markdown = render_markdown_impl(escalation_to_input(&escalation))?;
 
     // Get Jira client
     let client = get_jira_client(app).await?;
+    let capabilities = client.capabilities_for(Some(&escalation.ticket_id)).await?;
+    ensure_backend_accepts(&capabilities, &file_paths)?;
+
+    // A file Jira will reject stops the post before the comment goes out
+    ensure_quota_accepts(&client, &escalation, &file_paths).await?;
 
-    // Post comment
-    match client.post_comment(&escalation.ticket_id, &markdown).await {
-        Ok(_) => {},
+    // Make sure the ticket still needs escalating; the UI has already shown these warnings
+    let preferences = db::get_preferences()?;
+    guard_ticket_status(&client, &escalation, &preferences, &markdown).await?;
+
+    // Over-long output goes up as a file first, then a trimmed comment links to it
+    let body = match fit_to_budget(&client, &escalation, &markdown, &preferences).await {
+        Ok(body) => body,
         Err(e) => {
-            // Update status to post_failed
             update_escalation_status(id, "post_failed", Some(&markdown), Some(&e.to_string()))?;
             return Err(e.into());
         }
-    }
+    };
 
-    // Upload attachments
-    let mut failed_files = Vec::new();
-    for file_path in &file_paths {
-        let path = std::path::Path::new(file_path);
-        if let Err(e) = client.attach_file(&escalation.ticket_id, path).await {
-            failed_files.push(format!("{}: {}", file_path, e));
+    // Post comment with correlation footer
+    let comment = correlation::append_footer(&body, &preferences.correlation_marker, id);
+    match client.post_comment(&escalation.ticket_id, &comment).await {
+        Ok(_) => METRICS.record_post(),
+        Err(e) => {
+            // Update status to post_failed
+            update_escalation_status(id, "post_failed", Some(&markdown), Some(&e.to_string()))?;
+            return Err(e.into());
         }
     }
 
+    // The comment is up: finish the ticket, then upload; failed uploads are kept for a targeted retry
+    let (follow_ups, failed_files) = deliver(
+        &client,
+        &escalation,
+        &preferences,
+        &capabilities,
+        watch_ticket,
+        priority.as_deref(),
+        &file_paths,
+    )
+    .await;
+    save_upload_failures(id, &failed_files)?;
+
     if !failed_files.is_empty() {
-        let error_msg = format!("Failed to attach {} file(s):\n{}", failed_files.len(), failed_files.join("\n"));
-        update_escalation_status(id, "post_failed", Some(&markdown), Some(&error_msg))?;
+        let error_msg = upload_failure_message(&failed_files);
+        update_escalation_status(id, "posted_with_errors", Some(&markdown), Some(&error_msg))?;
         return Err(error_msg.into());
     }
 
@@ -263,60 +1551,238 @@ async fn post_escalation_impl(
         "ticket_id": escalation.ticket_id,
         "files_attached": file_paths.len(),
         "had_llm_summary": escalation.llm_summary.is_some(),
+        "remote_links_added": follow_ups.remote_links_added,
+        "watching": follow_ups.watching,
+        "priority_set": follow_ups.priority_set,
+        "missing_artifacts": missing_artifacts,
+        "l2_teams": escalation.l2_teams.iter().map(|t| &t.team).collect::<Vec<_>>(),
+        "components_added": follow_ups.components_added,
+        "assigned_to": follow_ups.assigned_to,
+    }))?;
+
+    Ok(())
+}
+
+async fn check_ticket_status_impl(app: AppHandle, id: i64) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let escalation = get_escalation_impl(id)?;
+    let preferences = db::get_preferences()?;
+    if preferences.ticket_status_guard == TicketGuardMode::Off {
+        return Ok(vec![]);
+    }
+
+    let client = get_jira_client(app).await?;
+    Ok(ticket_status_warnings(&client, &escalation, &preferences).await?)
+}
+
+/// Apply the ticket status guard before posting; Block mode refuses a closed or already-escalated
+/// ticket, Warn mode only logs
+async fn guard_ticket_status(
+    client: &JiraClient,
+    escalation: &Escalation,
+    preferences: &Preferences,
+    markdown: &str,
+) -> AppResult<()> {
+    if preferences.ticket_status_guard == TicketGuardMode::Off {
+        return Ok(());
+    }
+
+    // Warn mode is advisory, so a failed refetch shouldn't stop the post; Block mode can't vouch for the ticket
+    let id = escalation.id;
+    let warnings = match ticket_status_warnings(client, escalation, preferences).await {
+        Ok(warnings) => warnings,
+        Err(e) if preferences.ticket_status_guard == TicketGuardMode::Block => {
+            let message = format!("Could not check the ticket status: {}", e);
+            update_escalation_status(id, "post_failed", Some(markdown), Some(&message))?;
+            return Err(AppError::Jira(message));
+        }
+        Err(e) => {
+            log::warn!("Skipping ticket status check for escalation {}: {}", id, e);
+            Vec::new()
+        }
+    };
+    if !warnings.is_empty() {
+        if preferences.ticket_status_guard == TicketGuardMode::Block {
+            return Err(AppError::Validation(format!(
+                "Not posting: {}. Change the ticket status guard in Settings to post anyway.",
+                warnings.join("; ")
+            )));
+        }
+        log::warn!("Posting escalation {} despite: {}", id, warnings.join("; "));
+    }
+    Ok(())
+}
+
+async fn ticket_status_warnings(
+    client: &JiraClient,
+    escalation: &Escalation,
+    preferences: &Preferences,
+) -> AppResult<Vec<String>> {
+    let ticket = client.fetch_issue(&escalation.ticket_id).await?;
+
+    // The template's L2 team name counts too, for teams that assign to a shared account
+    let mut l2_assignees = preferences.l2_assignees.clone();
+    if let Some(team) = escalation
+        .template_id
+        .and_then(|id| templates::get_template_impl(id).ok())
+        .and_then(|t| t.l2_team)
+    {
+        l2_assignees.push(team);
+    }
+
+    Ok(ticket_guard::check(&ticket, &preferences.closed_statuses, &l2_assignees))
+}
+
+/// Post the rendered comment to a sandbox ticket without touching the escalation's status
+async fn dry_run_post_impl(
+    app: AppHandle,
+    escalation_id: i64,
+    sandbox_key: Option<String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let escalation = get_escalation_impl(escalation_id)?;
+    let preferences = db::get_preferences()?;
+
+    let sandbox_key = sandbox_key
+        .or(preferences.sandbox_ticket_key.clone())
+        .filter(|k| !k.trim().is_empty())
+        .ok_or("No sandbox ticket configured. Set one in Settings or pass a sandbox key.")?;
+    let sandbox_key = normalize_ticket_key(&sandbox_key)?;
+
+    if sandbox_key == escalation.ticket_id {
+        return Err(AppError::Validation(format!(
+            "Sandbox ticket {} is the escalation's own ticket; pick a dedicated sandbox ticket",
+            sandbox_key
+        ))
+        .into());
+    }
+
+    let markdown = render_markdown_impl(escalation_to_input(&escalation))?;
+    let comment = correlation::append_footer(&markdown, &preferences.correlation_marker, escalation_id);
+
+    let client = get_jira_client(app).await?;
+    let comment_id = client.post_comment_with_id(&sandbox_key, &comment).await?;
+    let url = client.comment_url(&sandbox_key, &comment_id);
+
+    write_audit_log(escalation_id, "dry_run_posted", &serde_json::json!({
+        "sandbox_key": sandbox_key,
+        "comment_id": comment_id,
+    }))?;
+
+    Ok(url)
+}
+
+async fn retry_failed_attachments_impl(
+    app: AppHandle,
+    escalation_id: i64,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let escalation = get_escalation_impl(escalation_id)?;
+    if !matches!(escalation.status, EscalationStatus::PostedWithErrors) {
+        return Err(AppError::Validation(format!(
+            "Escalation {} has no failed attachments to retry",
+            escalation_id
+        ))
+        .into());
+    }
+
+    let file_paths = load_upload_failures(escalation_id)?;
+    ensure_attachments_allowed(&file_paths).await?;
+
+    let client = get_jira_client(app).await?;
+    ensure_quota_accepts(&client, &escalation, &file_paths).await?;
+    let failed_files = upload_files(&client, &escalation, &file_paths).await;
+    save_upload_failures(escalation_id, &failed_files)?;
+
+    write_audit_log(escalation_id, "retried_attachments", &serde_json::json!({
+        "ticket_id": escalation.ticket_id,
+        "attempted": file_paths.len(),
+        "still_failing": failed_files.len(),
     }))?;
 
+    if !failed_files.is_empty() {
+        return Err(upload_failure_message(&failed_files).into());
+    }
+
+    update_escalation_status(escalation_id, "posted", escalation.markdown_output.as_deref(), None)?;
     Ok(())
 }
 
+/// `rerender` overrides the `rerender_on_retry` preference for this call
 async fn retry_post_escalation_impl(
     app: AppHandle,
     id: i64,
     file_paths: Vec<String>,
+    rerender: Option<bool>,
+    watch_ticket: Option<bool>,
+    priority: Option<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Load escalation
     let escalation = get_escalation_impl(id)?;
 
-    // Use existing markdown if available, otherwise render
-    let markdown = if let Some(existing_markdown) = escalation.markdown_output {
-        existing_markdown
-    } else {
-        let input = EscalationInput {
-            ticket_id: escalation.ticket_id.clone(),
-            template_id: escalation.template_id,
-            problem_summary: escalation.problem_summary.clone(),
-            checklist: escalation.checklist.clone(),
-            current_status: escalation.current_status.clone(),
-            next_steps: escalation.next_steps.clone(),
-            llm_summary: escalation.llm_summary.clone(),
-            llm_confidence: escalation.llm_confidence.clone(),
-        };
-        render_markdown_impl(input)?
+    // A retry is for a post that never landed (or a draft whose post was interrupted),
+    // and it holds to the same quality bar as the first attempt
+    if !matches!(escalation.status, EscalationStatus::PostFailed | EscalationStatus::Draft) {
+        return Err(AppError::Validation(format!(
+            "Escalation {} has already been posted; retry its failed attachments instead",
+            id
+        ))
+        .into());
+    }
+    ensure_min_quality(&escalation)?;
+
+    // Reuse the stored output unless asked to re-render (or there is none)
+    let rerender = rerender.unwrap_or(db::get_preferences()?.rerender_on_retry);
+    let (markdown, markdown_source) = match escalation.markdown_output.clone() {
+        Some(stored) if !rerender => (stored, "stored"),
+        _ => (render_markdown_impl(escalation_to_input(&escalation))?, "rerendered"),
     };
 
+    // Screen attachments before anything reaches Jira
+    ensure_attachments_allowed(&file_paths).await?;
+
     // Get Jira client
     let client = get_jira_client(app).await?;
+    let capabilities = client.capabilities_for(Some(&escalation.ticket_id)).await?;
+    ensure_backend_accepts(&capabilities, &file_paths)?;
+    ensure_quota_accepts(&client, &escalation, &file_paths).await?;
+
+    // The ticket may have moved on since the first attempt
+    let preferences = db::get_preferences()?;
+    guard_ticket_status(&client, &escalation, &preferences, &markdown).await?;
 
-    // Post comment
-    match client.post_comment(&escalation.ticket_id, &markdown).await {
-        Ok(_) => {},
+    // Over-long output goes up as a file first, then a trimmed comment links to it
+    let body = match fit_to_budget(&client, &escalation, &markdown, &preferences).await {
+        Ok(body) => body,
         Err(e) => {
             update_escalation_status(id, "post_failed", Some(&markdown), Some(&e.to_string()))?;
             return Err(e.into());
         }
-    }
+    };
 
-    // Upload attachments
-    let mut failed_files = Vec::new();
-    for file_path in &file_paths {
-        let path = std::path::Path::new(file_path);
-        if let Err(e) = client.attach_file(&escalation.ticket_id, path).await {
-            failed_files.push(format!("{}: {}", file_path, e));
+    // Post comment with correlation footer
+    let comment = correlation::append_footer(&body, &preferences.correlation_marker, id);
+    match client.post_comment(&escalation.ticket_id, &comment).await {
+        Ok(_) => METRICS.record_post(),
+        Err(e) => {
+            update_escalation_status(id, "post_failed", Some(&markdown), Some(&e.to_string()))?;
+            return Err(e.into());
         }
     }
 
+    // The comment is up: finish the ticket, then upload; failed uploads are kept for a targeted retry
+    let (follow_ups, failed_files) = deliver(
+        &client,
+        &escalation,
+        &preferences,
+        &capabilities,
+        watch_ticket,
+        priority.as_deref(),
+        &file_paths,
+    )
+    .await;
+    save_upload_failures(id, &failed_files)?;
+
     if !failed_files.is_empty() {
-        let error_msg = format!("Failed to attach {} file(s):\n{}", failed_files.len(), failed_files.join("\n"));
-        update_escalation_status(id, "post_failed", Some(&markdown), Some(&error_msg))?;
+        let error_msg = upload_failure_message(&failed_files);
+        update_escalation_status(id, "posted_with_errors", Some(&markdown), Some(&error_msg))?;
         return Err(error_msg.into());
     }
 
@@ -327,52 +1793,483 @@ async fn retry_post_escalation_impl(
     write_audit_log(id, "retry_posted", &serde_json::json!({
         "ticket_id": escalation.ticket_id,
         "files_attached": file_paths.len(),
+        "remote_links_added": follow_ups.remote_links_added,
+        "watching": follow_ups.watching,
+        "priority_set": follow_ups.priority_set,
+        "markdown_source": markdown_source,
+        "components_added": follow_ups.components_added,
+        "assigned_to": follow_ups.assigned_to,
     }))?;
 
     Ok(())
 }
 
+/// What the best-effort steps after the comment managed to do to the ticket
+struct FollowUps {
+    watching: bool,
+    priority_set: Option<String>,
+    remote_links_added: usize,
+    components_added: bool,
+    assigned_to: Option<String>,
+}
+
+/// Watch, prioritise, link and route the ticket once the comment is on it, then upload the files
+///
+/// The follow-ups come first so a failed upload never leaves the ticket unrouted; returns them
+/// with the `(file_path, error)` of each failed upload.
+async fn deliver(
+    client: &JiraClient,
+    escalation: &Escalation,
+    preferences: &Preferences,
+    capabilities: &BackendCapabilities,
+    watch_ticket: Option<bool>,
+    priority: Option<&str>,
+    file_paths: &[String],
+) -> (FollowUps, Vec<(String, String)>) {
+    // Watch the ticket so Jira notifies the agent about L2 activity
+    let watching = watch(client, escalation, preferences, capabilities, watch_ticket).await;
+    let priority_set = apply_priority(client, escalation, capabilities, priority).await;
+
+    // Link referenced dashboards/docs
+    let remote_links_added = if preferences.auto_add_remote_links && capabilities.remote_links {
+        add_reference_links(client, escalation).await
+    } else {
+        0
+    };
+
+    // Route the ticket to the selected L2 teams
+    let (components_added, assigned_to) = apply_routing(client, escalation, preferences, capabilities).await;
+
+    let failed_files = upload_files(client, escalation, file_paths).await;
+    let follow_ups = FollowUps {
+        watching,
+        priority_set,
+        remote_links_added,
+        components_added,
+        assigned_to,
+    };
+    (follow_ups, failed_files)
+}
+
+/// Refuse files Jira will reject before anything is uploaded; the budget only warns, and a failed
+/// lookup shouldn't stop the post
+async fn ensure_quota_accepts(client: &JiraClient, escalation: &Escalation, file_paths: &[String]) -> AppResult<()> {
+    if file_paths.is_empty() {
+        return Ok(());
+    }
+    match attachment_quota_check(client, &escalation.ticket_id, file_paths).await {
+        Ok(check) if !check.rejections.is_empty() => {
+            return Err(AppError::Validation(format!(
+                "Jira would reject these attachments: {}",
+                check.rejections.join("; ")
+            )));
+        }
+        Ok(check) if !check.warnings.is_empty() => {
+            log::warn!(
+                "Attachments for escalation {} are over budget: {}",
+                escalation.id,
+                check.warnings.join("; ")
+            );
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Skipping attachment quota check for escalation {}: {}", escalation.id, e),
+    }
+    Ok(())
+}
+
+/// Set the priority the agent accepted, returning it when Jira took it
+async fn apply_priority(
+    client: &JiraClient,
+    escalation: &Escalation,
+    capabilities: &BackendCapabilities,
+    priority: Option<&str>,
+) -> Option<String> {
+    let priority = priority.map(str::trim).filter(|p| !p.is_empty() && capabilities.priority)?;
+    match client.set_priority(&escalation.ticket_id, priority).await {
+        Ok(_) => Some(priority.to_string()),
+        Err(e) => {
+            log::warn!("Failed to set priority on {}: {}", escalation.ticket_id, e);
+            None
+        }
+    }
+}
+
+/// Refuse escalations below the configured minimum quality score
+fn ensure_min_quality(escalation: &Escalation) -> AppResult<()> {
+    if let Some(min_score) = db::get_preferences()?.min_quality_score {
+        let score = quality::score(escalation);
+        if score.score < min_score {
+            return Err(AppError::Validation(format!(
+                "Escalation quality score {} is below the required {}. Add more detail before posting.",
+                score.score, min_score
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Watch the ticket as the posting agent when asked to (the preference when `watch_ticket` is omitted)
+async fn watch(
+    client: &JiraClient,
+    escalation: &Escalation,
+    preferences: &Preferences,
+    capabilities: &BackendCapabilities,
+    watch_ticket: Option<bool>,
+) -> bool {
+    if !capabilities.watchers || !watch_ticket.unwrap_or(preferences.watch_posted_tickets) {
+        return false;
+    }
+    match client.watch_issue(&escalation.ticket_id).await {
+        Ok(_) => true,
+        Err(e) => {
+            log::warn!("Failed to watch {}: {}", escalation.ticket_id, e);
+            false
+        }
+    }
+}
+
+/// Add the selected teams' components and assign the ticket; returns whether components
+/// were added and who it was assigned to
+async fn apply_routing(
+    client: &JiraClient,
+    escalation: &Escalation,
+    preferences: &Preferences,
+    capabilities: &BackendCapabilities,
+) -> (bool, Option<String>) {
+    let routing = team_routing::routing(&escalation.l2_teams, &preferences.l2_teams);
+    let components_added = capabilities.components
+        && !routing.components.is_empty()
+        && match client.add_components(&escalation.ticket_id, &routing.components).await {
+            Ok(_) => true,
+            Err(e) => {
+                log::warn!("Failed to add components to {}: {}", escalation.ticket_id, e);
+                false
+            }
+        };
+    let assigned_to = match routing.assignee_account_id.filter(|_| capabilities.assign) {
+        Some(account_id) => match client.assign_issue(&escalation.ticket_id, &account_id).await {
+            Ok(_) => Some(account_id),
+            Err(e) => {
+                log::warn!("Failed to assign {}: {}", escalation.ticket_id, e);
+                None
+            }
+        },
+        None => None,
+    };
+    (components_added, assigned_to)
+}
+
+/// Refuse up front rather than posting a comment whose attachments can never follow
+fn ensure_backend_accepts(capabilities: &BackendCapabilities, file_paths: &[String]) -> AppResult<()> {
+    if !file_paths.is_empty() && !capabilities.attachments {
+        return Err(AppError::Validation(format!(
+            "The {} backend does not support attachments. Remove the files before posting.",
+            capabilities.backend
+        )));
+    }
+    Ok(())
+}
+
+/// Upload each file, returning `(file_path, error)` for the ones that failed
+///
+/// Screenshots with redactions are uploaded from a redacted copy; if the copy
+/// can't be made the file fails rather than going up unredacted.
+async fn upload_files(
+    client: &JiraClient,
+    escalation: &Escalation,
+    file_paths: &[String],
+) -> Vec<(String, String)> {
+    let mut failed = Vec::new();
+    for file_path in file_paths {
+        if let Err(e) = attach_file_redacted(client, &escalation.ticket_id, &escalation.attachments, file_path).await {
+            failed.push((file_path.clone(), e.to_string()));
+        }
+    }
+    failed
+}
+
+fn upload_failure_message(failed: &[(String, String)]) -> String {
+    let lines: Vec<String> = failed.iter().map(|(path, error)| format!("{}: {}", path, error)).collect();
+    format!("Failed to attach {} file(s):\n{}", failed.len(), lines.join("\n"))
+}
+
+/// Replace the stored upload failures for an escalation
+fn save_upload_failures(escalation_id: i64, failed: &[(String, String)]) -> AppResult<()> {
+    let mut conn = db::get_connection()?;
+    let tx = conn.transaction()?;
+
+    tx.execute("DELETE FROM attachment_upload_failures WHERE escalation_id = ?", [escalation_id])?;
+    for (file_path, error) in failed {
+        tx.execute(
+            "INSERT INTO attachment_upload_failures (escalation_id, file_path, error) VALUES (?, ?, ?)",
+            rusqlite::params![escalation_id, file_path, error],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(())
+}
+
+fn load_upload_failures(escalation_id: i64) -> AppResult<Vec<String>> {
+    let conn = db::get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT file_path FROM attachment_upload_failures WHERE escalation_id = ? ORDER BY id",
+    )?;
+
+    let paths = stmt
+        .query_map([escalation_id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(paths)
+}
+
+/// Add every resource referenced by the escalation as a remote link, returning how many succeeded
+async fn add_reference_links(client: &JiraClient, escalation: &Escalation) -> usize {
+    let mut added = 0;
+
+    for link in remote_links::collect_links(escalation) {
+        match client.add_remote_link(&escalation.ticket_id, &link.url, &link.title).await {
+            Ok(_) => added += 1,
+            Err(e) => log::warn!("Failed to add remote link {} to {}: {}", link.url, escalation.ticket_id, e),
+        }
+    }
+
+    added
+}
+
+/// Fix a draft's layout to the current version; already posted escalations keep theirs
+fn pin_render_version(id: i64) -> AppResult<u32> {
+    let conn = db::get_connection()?;
+    conn.execute(
+        "UPDATE escalations SET render_version = ? WHERE id = ? AND status = 'draft'",
+        rusqlite::params![template_engine::RENDER_VERSION, id],
+    )?;
+    Ok(conn.query_row("SELECT render_version FROM escalations WHERE id = ?", [id], |row| row.get(0))?)
+}
+
 fn update_escalation_status(
     id: i64,
     status: &str,
     markdown_output: Option<&str>,
     error_details: Option<&str>,
 ) -> AppResult<()> {
-    let conn = db::get_connection()?;
+    store_status(&*db::get_connection()?, id, status, markdown_output)?;
+
+    // Posts are counted where the comment is created; an attachment retry also lands here
+    if status == "post_failed" {
+        METRICS.record_post_failure();
+    }
 
-    let posted_at = if status == "posted" {
+    // Write audit log for status change
+    if let Some(error) = error_details {
+        write_audit_log(id, status, &serde_json::json!({
+            "error": error,
+        }))?;
+    }
+
+    Ok(())
+}
+
+/// Record a post confirmed inside a change-freeze window, so overrides can be reviewed
+///
+/// Only recorded once the comment is on the ticket; a post that never landed overrode nothing.
+fn audit_freeze_override(id: i64, freeze: Option<FreezeWindow>) -> AppResult<()> {
+    let Some(window) = freeze else {
+        return Ok(());
+    };
+    if !matches!(
+        get_escalation_impl(id)?.status,
+        EscalationStatus::Posted | EscalationStatus::PostedWithErrors
+    ) {
+        return Ok(());
+    }
+    log::warn!("Escalation {} posted during freeze window \"{}\"", id, window.label);
+    write_audit_log(id, "freeze_override", &serde_json::json!({
+        "window": window.label,
+        "start": window.start,
+        "end": window.end,
+    }))
+}
+
+/// Write the status and output with their field history; `posted_at` keeps the first time the
+/// comment landed, so attachment retries and resumes don't move it
+fn store_status(conn: &rusqlite::Connection, id: i64, status: &str, markdown_output: Option<&str>) -> AppResult<()> {
+    let posted_at = if status == "posted" || status == "posted_with_errors" {
         Some(chrono::Utc::now().to_rfc3339())
     } else {
         None
     };
 
+    let (old_status, old_markdown, old_markdown_zstd, old_posted_at): (String, Option<String>, Option<Vec<u8>>, Option<String>) =
+        conn.query_row(
+            "SELECT status, markdown_output, markdown_output_zstd, posted_at FROM escalations WHERE id = ?",
+            [id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+    let old_markdown = cold_storage::text_column(old_markdown, old_markdown_zstd)?;
+
     conn.execute(
-        "UPDATE escalations SET status = ?, markdown_output = ?, posted_at = ?, updated_at = datetime('now') WHERE id = ?",
+        "UPDATE escalations SET status = ?, markdown_output = ?, markdown_output_zstd = NULL,
+        posted_at = COALESCE(posted_at, ?), updated_at = datetime('now') WHERE id = ?",
         rusqlite::params![status, markdown_output, posted_at, id],
     )?;
+    field_history::record(conn, id, "status", Some(&old_status), Some(status))?;
+    field_history::record(conn, id, "markdown_output", old_markdown.as_deref(), markdown_output)?;
 
-    // Write audit log for status change
-    if let Some(error) = error_details {
-        write_audit_log(id, status, &serde_json::json!({
-            "error": error,
-        }))?;
+    if posted_at.is_some() && old_posted_at.is_none() {
+        log_activity(conn, activity::KIND_ESCALATION_POSTED, id);
     }
 
     Ok(())
 }
 
-fn write_audit_log(escalation_id: i64, action: &str, details: &serde_json::Value) -> AppResult<()> {
-    let conn = db::get_connection()?;
+/// Activity feeds the daily summary only, so a failure to record it never fails the command
+fn log_activity(conn: &rusqlite::Connection, kind: &str, escalation_id: i64) {
+    if let Err(e) = activity::record_for_escalation(conn, kind, escalation_id) {
+        log::warn!("Failed to record {} activity for escalation {}: {}", kind, escalation_id, e);
+    }
+}
+
+pub fn write_audit_log(escalation_id: i64, action: &str, details: &serde_json::Value) -> AppResult<()> {
+    insert_audit_log(&*db::get_connection()?, escalation_id, action, details)
+}
+
+/// Store an audit entry with details redacted to the configured verbosity
+fn insert_audit_log(
+    conn: &rusqlite::Connection,
+    escalation_id: i64,
+    action: &str,
+    details: &serde_json::Value,
+) -> AppResult<()> {
+    let details = audit::redact_details(details, db::get_preferences()?.audit_verbosity);
 
     conn.execute(
-        "INSERT INTO audit_log (escalation_id, action, details) VALUES (?, ?, ?)",
+        &format!(
+            "INSERT INTO audit_log (escalation_id, action, details, actor_account_id, actor) VALUES (?, ?, ?, {})",
+            db::AUDIT_ACTOR_SQL
+        ),
         rusqlite::params![
             escalation_id,
             action,
-            serde_json::to_string(details)
+            serde_json::to_string(&details)
                 .map_err(|e| AppError::Validation(format!("Failed to serialize audit log: {}", e)))?,
         ],
     )?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::L2Team;
+    use rusqlite::Connection;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/017_jira_identity.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/021_cold_storage.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/022_activity_log.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/031_field_history.sql")).unwrap();
+        conn.execute(
+            "INSERT INTO escalations (ticket_id, problem_summary, checklist, current_status, next_steps, status)
+            VALUES ('IT-1', 'VPN drops', '[]', '', '', 'draft')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn posted_at(conn: &Connection) -> Option<String> {
+        conn.query_row("SELECT posted_at FROM escalations WHERE id = 1", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_attachment_retry_keeps_first_post_time() {
+        let conn = setup();
+        store_status(&conn, 1, "post_failed", Some("body")).unwrap();
+        assert_eq!(posted_at(&conn), None);
+
+        store_status(&conn, 1, "posted_with_errors", Some("body")).unwrap();
+        let first = posted_at(&conn).expect("stamped when the comment lands");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        store_status(&conn, 1, "posted", Some("body")).unwrap();
+        assert_eq!(posted_at(&conn), Some(first));
+
+        let posts: i64 = conn
+            .query_row("SELECT COUNT(*) FROM activity_log WHERE kind = ?", [activity::KIND_ESCALATION_POSTED], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(posts, 1);
+    }
+
+    /// A Jira that accepts every request, reporting each as "METHOD path"
+    fn fake_jira() -> (String, std::sync::mpsc::Receiver<String>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let _ = tx.send(format!("{} {}", request.method(), request.url()));
+                let _ = request.respond(tiny_http::Response::empty(204));
+            }
+        });
+        (base_url, rx)
+    }
+
+    #[test]
+    fn test_routes_the_ticket_when_an_upload_fails() {
+        let (base_url, requests) = fake_jira();
+        let client = JiraClient::new(base_url, "agent@example.com".into(), "token".into()).unwrap();
+        let escalation = Escalation {
+            l2_teams: vec![TeamAsk {
+                team: "Network".into(),
+                asks: "Check the VPN concentrator".into(),
+                mentions: Vec::new(),
+            }],
+            ..Escalation::fixture()
+        };
+        let preferences = Preferences {
+            l2_teams: vec![L2Team {
+                name: "Network".into(),
+                component: Some("VPN".into()),
+                assignee_account_id: Some("acc-net".into()),
+                mention_account_ids: Vec::new(),
+            }],
+            auto_add_remote_links: false,
+            ..Preferences::default()
+        };
+        let capabilities = BackendCapabilities {
+            backend: "jira".into(),
+            attachments: true,
+            internal_comments: true,
+            transitions: true,
+            watchers: false,
+            remote_links: false,
+            priority: false,
+            components: true,
+            assign: true,
+        };
+        let files = vec!["/nonexistent/vpn.log".to_string()];
+
+        let (follow_ups, failed_files) = tauri::async_runtime::block_on(deliver(
+            &client,
+            &escalation,
+            &preferences,
+            &capabilities,
+            None,
+            None,
+            &files,
+        ));
+
+        assert_eq!(failed_files.len(), 1);
+        assert!(follow_ups.components_added);
+        assert_eq!(follow_ups.assigned_to.as_deref(), Some("acc-net"));
+        let seen: Vec<String> = requests.try_iter().collect();
+        assert!(seen.contains(&"PUT /rest/api/3/issue/TEST-1".to_string()), "{:?}", seen);
+        assert!(seen.contains(&"PUT /rest/api/3/issue/TEST-1/assignee".to_string()), "{:?}", seen);
+    }
+}
@@ -1,13 +1,18 @@
 use crate::db;
-use crate::models::{ChecklistItem, LLMSummaryResult};
+use crate::error::{AppError, AppResult};
+use crate::models::{ChecklistItem, JiraLinkedIssue, LLMSummaryResult, LlmUsageReport, TranslationResult};
+use crate::services::{language, llm_usage};
+use crate::services::metrics::METRICS;
 use crate::services::ollama::OllamaClient;
+use std::time::Instant;
 
 #[tauri::command]
 pub async fn summarize_with_llm(
     checklist: Vec<ChecklistItem>,
     problem_summary: String,
+    linked_issues: Option<Vec<JiraLinkedIssue>>,
 ) -> Result<LLMSummaryResult, String> {
-    summarize_with_llm_impl(checklist, problem_summary)
+    summarize_with_llm_impl(checklist, problem_summary, linked_issues.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -15,13 +20,15 @@ pub async fn summarize_with_llm(
 async fn summarize_with_llm_impl(
     checklist: Vec<ChecklistItem>,
     problem_summary: String,
+    linked_issues: Vec<JiraLinkedIssue>,
 ) -> Result<LLMSummaryResult, Box<dyn std::error::Error>> {
     // Get Ollama config from database
     let config = db::get_api_config()?
         .ok_or("No API config found. Please configure Ollama in Settings.")?;
 
     // Create Ollama client
-    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?;
+    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
 
     // Check if Ollama is available
     if !client.is_available().await? {
@@ -29,7 +36,75 @@ async fn summarize_with_llm_impl(
     }
 
     // Generate summary
-    let result = client.summarize(&checklist, &problem_summary).await?;
+    let started = Instant::now();
+    let result = client.summarize(&checklist, &problem_summary, &linked_issues).await?;
+    METRICS.record_llm_latency(started.elapsed());
 
     Ok(result)
 }
+
+/// Detect the summary's language and translate it to English when it isn't English
+#[tauri::command]
+pub async fn translate_problem_summary(problem_summary: String) -> Result<TranslationResult, String> {
+    translate_problem_summary_impl(problem_summary)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn translate_problem_summary_impl(
+    problem_summary: String,
+) -> Result<TranslationResult, Box<dyn std::error::Error>> {
+    let code = language::detect(&problem_summary);
+    let source_language = match code.filter(|code| *code != "en").and_then(language::name) {
+        Some(name) => name,
+        None => {
+            return Ok(TranslationResult {
+                language: code.map(str::to_string),
+                translation: None,
+            })
+        }
+    };
+
+    let config = db::get_api_config()?
+        .ok_or("No API config found. Please configure Ollama in Settings.")?;
+    let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?
+        .with_timeout(db::get_preferences()?.ollama_timeout_secs);
+
+    if !client.is_available().await? {
+        return Err("Ollama is not running. Start it with `ollama serve` or skip the translation.".into());
+    }
+
+    let translation = client.translate_to_english(&problem_summary, source_language).await?;
+
+    Ok(TranslationResult {
+        language: code.map(str::to_string),
+        translation: Some(translation),
+    })
+}
+
+/// LLM calls between two UTC dates (YYYY-MM-DD, inclusive); the last 30 days when omitted
+#[tauri::command]
+pub fn get_llm_usage_report(from: Option<String>, to: Option<String>) -> Result<LlmUsageReport, String> {
+    get_llm_usage_report_impl(from, to).map_err(|e| e.to_string())
+}
+
+fn get_llm_usage_report_impl(from: Option<String>, to: Option<String>) -> AppResult<LlmUsageReport> {
+    let to = match to {
+        Some(to) => parse_date(&to)?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let from = match from {
+        Some(from) => parse_date(&from)?,
+        None => to - chrono::Duration::days(29),
+    };
+    if from > to {
+        return Err(AppError::Validation(format!("Start date {} is after end date {}", from, to)));
+    }
+
+    llm_usage::report(&*db::get_connection()?, &from.to_string(), &to.to_string())
+}
+
+fn parse_date(date: &str) -> AppResult<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| AppError::Validation(format!("Invalid date '{}', expected YYYY-MM-DD", date)))
+}
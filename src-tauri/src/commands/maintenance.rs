@@ -0,0 +1,166 @@
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::{AttachmentCleanupReport, DataIntegrityReport, DatabaseRecoveryReport, MaintenanceReport, StartupStatus};
+use crate::services::{attachment_storage, cold_storage, data_integrity, db_recovery, maintenance};
+use std::path::Path;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub async fn run_db_maintenance() -> Result<MaintenanceReport, String> {
+    run_db_maintenance_impl().await.map_err(|e| e.to_string())
+}
+
+/// VACUUM can take a while on a large database, so it runs off the async runtime
+async fn run_db_maintenance_impl() -> AppResult<MaintenanceReport> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut conn = db::get_connection()?;
+        run_with_cold_storage(&mut conn)
+    })
+    .await
+    .map_err(|e| AppError::Db(format!("Maintenance task failed: {}", e)))?
+}
+
+/// Remove orphaned rows and compress old escalations first so the VACUUM in `maintenance::run` reclaims the space
+fn run_with_cold_storage(conn: &mut rusqlite::Connection) -> AppResult<MaintenanceReport> {
+    let orphans = data_integrity::repair(conn)?;
+    if orphans.total > 0 {
+        log::warn!("Removed {} row(s) referring to deleted escalations", orphans.total);
+    }
+    let compressed = cold_storage::archive(conn, db::get_preferences()?.cold_storage_after_days)?;
+    let mut report = maintenance::run(conn)?;
+    report.compressed_escalations = compressed;
+    report.orphans_removed = orphans.total;
+    Ok(report)
+}
+
+/// Count rows referring to deleted escalations; with `repair`, remove them
+#[tauri::command]
+pub fn check_data_integrity(repair: bool) -> Result<DataIntegrityReport, String> {
+    check_data_integrity_impl(repair).map_err(|e| e.to_string())
+}
+
+fn check_data_integrity_impl(repair: bool) -> AppResult<DataIntegrityReport> {
+    let mut conn = db::get_connection()?;
+    if repair {
+        data_integrity::repair(&mut conn)
+    } else {
+        data_integrity::check(&conn)
+    }
+}
+
+#[tauri::command]
+pub fn clean_attachment_storage(
+    retention_days: Option<u32>,
+    dry_run: bool,
+) -> Result<AttachmentCleanupReport, String> {
+    clean_attachment_storage_impl(retention_days, dry_run).map_err(|e| e.to_string())
+}
+
+fn clean_attachment_storage_impl(
+    retention_days: Option<u32>,
+    dry_run: bool,
+) -> AppResult<AttachmentCleanupReport> {
+    let retention_days = match retention_days {
+        Some(days) => days,
+        None => db::get_preferences()?.attachment_retention_days,
+    };
+
+    let conn = db::get_connection()?;
+    let Some(dir) = attachment_storage::storage_dir(&conn)? else {
+        return Err(AppError::File("Attachment storage is unavailable for this database".to_string()));
+    };
+
+    attachment_storage::cleanup(&conn, &dir, retention_days, dry_run)
+}
+
+/// Whether the app started in settings-only mode because the database failed to open
+#[tauri::command]
+pub fn get_startup_status() -> StartupStatus {
+    db::startup_status()
+}
+
+/// Reopen, salvage or (with `reset`) replace the database after a failed startup.
+/// The previous file is always renamed aside, never deleted.
+#[tauri::command]
+pub fn repair_or_reset_database(app: AppHandle, reset: bool) -> Result<DatabaseRecoveryReport, String> {
+    let report = repair_or_reset_database_impl(reset).map_err(|e| e.to_string())?;
+    // The boot that failed never started these
+    crate::start_database_services(&app);
+    Ok(report)
+}
+
+fn repair_or_reset_database_impl(reset: bool) -> AppResult<DatabaseRecoveryReport> {
+    let status = db::startup_status();
+    if !status.degraded {
+        return Err(AppError::Validation(
+            "The database opened normally; repair and reset are only available after a failed startup".to_string(),
+        ));
+    }
+
+    let Some(db_path) = status.db_path else {
+        return Err(AppError::File(
+            "The app data directory could not be resolved. Check folder permissions and restart the app."
+                .to_string(),
+        ));
+    };
+    let path = Path::new(&db_path);
+
+    db::close()?;
+
+    if reset {
+        let backup = if path.exists() {
+            Some(db_recovery::move_aside(path, "reset")?)
+        } else {
+            None
+        };
+        reopen(&db_path)?;
+        return Ok(recovery_report("reset", backup.as_deref()));
+    }
+
+    // A lock held by another process may have cleared since launch
+    if !path.exists() || db_recovery::integrity_ok(path) {
+        reopen(&db_path)?;
+        return Ok(recovery_report("reopened", None));
+    }
+
+    let salvaged = db_recovery::salvage(path)?;
+    let backup = db_recovery::move_aside(path, "corrupt")?;
+    std::fs::rename(&salvaged, path)?;
+    reopen(&db_path)?;
+
+    log::warn!("Database salvaged; damaged copy kept at {}", backup.display());
+    Ok(recovery_report("salvaged", Some(&backup)))
+}
+
+fn reopen(db_path: &str) -> AppResult<()> {
+    db::init_db(db_path).inspect_err(|e| db::mark_degraded(None, &e.to_string()))
+}
+
+fn recovery_report(action: &str, backup: Option<&Path>) -> DatabaseRecoveryReport {
+    DatabaseRecoveryReport {
+        action: action.to_string(),
+        backup_path: backup.map(|p| p.to_string_lossy().into_owned()),
+    }
+}
+
+/// Run maintenance if the last run is older than the maintenance interval.
+/// Called from a background thread at startup.
+pub fn run_scheduled_maintenance() -> AppResult<Option<MaintenanceReport>> {
+    let mut conn = db::get_connection()?;
+
+    if !maintenance::is_due(&conn)? {
+        return Ok(None);
+    }
+
+    let report = run_with_cold_storage(&mut conn)?;
+    log::info!(
+        "Scheduled maintenance finished in {}ms ({} -> {} bytes, {} escalations compressed, integrity {})",
+        report.duration_ms,
+        report.size_before_bytes,
+        report.size_after_bytes,
+        report.compressed_escalations,
+        if report.integrity_ok { "ok" } else { "FAILED" }
+    );
+
+    Ok(Some(report))
+}
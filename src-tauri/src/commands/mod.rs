@@ -3,3 +3,8 @@ pub mod escalations;
 pub mod tickets;
 pub mod llm;
 pub mod settings;
+pub mod maintenance;
+pub mod drafts;
+pub mod activity;
+pub mod acknowledgment;
+pub mod onboarding;
@@ -0,0 +1,69 @@
+use crate::commands::settings::get_jira_client;
+use crate::commands::templates;
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::{OnboardingState, OnboardingStep};
+use crate::services::ollama::OllamaClient;
+use crate::services::onboarding;
+use tauri::AppHandle;
+
+#[tauri::command]
+pub fn get_onboarding_state() -> Result<OnboardingState, String> {
+    onboarding::state(&*db::get_connection().map_err(|e| e.to_string())?).map_err(|e| e.to_string())
+}
+
+/// Mark a step done after checking it actually is; `skip` is only accepted for optional steps
+#[tauri::command]
+pub async fn complete_onboarding_step(
+    app: AppHandle,
+    step: OnboardingStep,
+    skip: Option<bool>,
+) -> Result<OnboardingState, String> {
+    complete_onboarding_step_impl(app, step, skip.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn complete_onboarding_step_impl(
+    app: AppHandle,
+    step: OnboardingStep,
+    skip: bool,
+) -> Result<OnboardingState, Box<dyn std::error::Error>> {
+    if !skip {
+        verify_step(app, step).await?;
+    }
+    Ok(onboarding::complete(&*db::get_connection()?, step, skip)?)
+}
+
+async fn verify_step(app: AppHandle, step: OnboardingStep) -> Result<(), Box<dyn std::error::Error>> {
+    match step {
+        OnboardingStep::JiraConfig => {
+            get_jira_client(app).await?;
+        }
+        OnboardingStep::TestConnection => {
+            // Cached by a successful connection test
+            if db::get_jira_identity()?.is_none() {
+                return Err(AppError::Validation("Test the Jira connection in Settings first".to_string()).into());
+            }
+        }
+        OnboardingStep::Ollama => {
+            let config = db::get_api_config()?.ok_or("No API config found. Please configure Ollama in Settings.")?;
+            let client = OllamaClient::new(config.ollama_endpoint, config.ollama_model)?;
+            if !client.is_available().await? {
+                return Err(AppError::Ollama("Ollama isn't reachable. Start it, or skip this step.".to_string()).into());
+            }
+        }
+        OnboardingStep::FirstTemplate => {
+            let template_id = db::get_preferences()?
+                .default_template_id
+                .ok_or_else(|| AppError::Validation("Pick a default template first".to_string()))?;
+            templates::get_template_impl(template_id)?;
+        }
+    }
+    Ok(())
+}
+
+/// Refuse to post until the required onboarding steps are done; checked by every posting command
+pub fn ensure_onboarded() -> AppResult<()> {
+    onboarding::ensure_posting_enabled(&*db::get_connection()?)
+}
@@ -1,7 +1,11 @@
 use crate::db;
 use crate::keychain;
-use crate::models::ApiConfig;
+use crate::models::{ApiConfig, BackendCapabilities, FreezeWindow, JiraIdentity, Preferences, TicketKeyPreset};
+use crate::error::{AppError, AppResult};
+use crate::services::freeze_window;
 use crate::services::jira::JiraClient;
+use crate::services::ticket_keys::{self, TicketKeyRules};
+use crate::services::ticket_system::TicketSystemClient;
 use tauri::AppHandle;
 
 #[tauri::command]
@@ -23,10 +27,68 @@ pub async fn test_jira_connection(_app: AppHandle) -> Result<String, String> {
         .map_err(|e| e.to_string())
 }
 
+/// Optional actions the configured ticket system supports, so the UI can hide the rest
+#[tauri::command]
+pub async fn get_backend_capabilities(app: AppHandle) -> Result<BackendCapabilities, String> {
+    let client = get_jira_client(app).await.map_err(|e| e.to_string())?;
+    client.capabilities().await.map_err(|e| e.to_string())
+}
+
+/// The Jira account cached by the last successful connection test
+#[tauri::command]
+pub fn get_current_identity() -> Result<Option<JiraIdentity>, String> {
+    db::get_jira_identity().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_preferences() -> Result<Preferences, String> {
+    db::get_preferences().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn save_preferences(preferences: Preferences) -> Result<(), String> {
+    save_preferences_impl(preferences).map_err(|e| e.to_string())
+}
+
+/// Store the Slack app signing secret used by the local API's /slack/handoff endpoint
+#[tauri::command]
+pub fn save_slack_signing_secret(secret: String) -> Result<(), String> {
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err("Slack signing secret cannot be empty".to_string());
+    }
+    keychain::save_slack_signing_secret(secret).map_err(|e| e.to_string())
+}
+
+/// Store the shared key template packs are signed with on export and verified with on import
+#[tauri::command]
+pub fn save_template_pack_key(key: String) -> Result<(), String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Template pack key cannot be empty".to_string());
+    }
+    keychain::save_template_pack_key(key).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_ticket_key_presets() -> Vec<TicketKeyPreset> {
+    ticket_keys::presets()
+}
+
+fn save_preferences_impl(preferences: Preferences) -> AppResult<()> {
+    // Reject patterns that would make every ticket key invalid
+    TicketKeyRules::new(&preferences.ticket_key_pattern)?;
+    freeze_window::validate(&preferences.posting_freeze_windows)?;
+
+    db::save_preferences(&preferences)
+}
+
 fn save_api_config_impl(config: ApiConfig) -> Result<(), Box<dyn std::error::Error>> {
     // Save Jira credentials to keychain
     if !config.jira_base_url.is_empty() && !config.jira_email.is_empty() && !config.jira_api_token.is_empty() {
         keychain::save_jira_credentials(&config.jira_base_url, &config.jira_email, &config.jira_api_token)?;
+        // New credentials may belong to someone else; re-cached on the next connection test
+        db::clear_jira_identity()?;
     }
 
     // Save Ollama config to database
@@ -89,8 +151,25 @@ async fn test_jira_connection_impl() -> Result<String, Box<dyn std::error::Error
         config.jira_api_token,
     )?;
 
-    let display_name = client.test_connection().await?;
-    Ok(format!("Connected as {}", display_name))
+    let identity = client.fetch_identity().await?;
+    db::save_jira_identity(&identity)?;
+    Ok(format!("Connected as {}", identity.display_name))
+}
+
+/// Refuse Jira changes while read-only mode is on; checked by every command that writes to Jira
+pub fn ensure_jira_writable() -> AppResult<()> {
+    if db::get_preferences()?.read_only_mode {
+        return Err(AppError::Validation(
+            "Read-only mode is on, so nothing is changed in Jira. Turn it off in Settings to post.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Refuse to post inside a change-freeze window unless `confirmed`; returns the window a confirmed post overrides
+pub fn ensure_posting_window(confirmed: bool) -> AppResult<Option<FreezeWindow>> {
+    let windows = db::get_preferences()?.posting_freeze_windows;
+    freeze_window::check(&windows, chrono::Local::now().naive_local(), confirmed)
 }
 
 // Helper function used by ticket commands
@@ -98,9 +177,12 @@ pub async fn get_jira_client(_app: AppHandle) -> Result<JiraClient, Box<dyn std:
     let config = get_api_config_for_use()?
         .ok_or("No API config found. Please configure Jira credentials in Settings.")?;
 
+    let comment_format = db::get_preferences()?.output_format;
+
     Ok(JiraClient::new(
         config.jira_base_url,
         config.jira_email,
         config.jira_api_token,
-    )?)
+    )?
+    .with_comment_format(comment_format))
 }
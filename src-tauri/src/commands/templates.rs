@@ -1,6 +1,13 @@
+use crate::commands::settings::get_jira_client;
+use crate::commands::tickets::normalize_ticket_key;
 use crate::db;
+use crate::keychain;
 use crate::error::AppResult;
-use crate::models::{ChecklistItem, Template};
+use crate::models::{
+    ChecklistItem, ChecklistItemStat, EscalationInput, NextStepBlock, NextStepBlockInput, Template, TemplateImportResult, TemplatePackManifest, TemplateRenderResult,
+};
+use crate::services::{checklist_stats, next_step_blocks, template_engine, template_files, template_filter, template_pack};
+use tauri::AppHandle;
 
 #[tauri::command]
 pub fn list_templates() -> Result<Vec<Template>, String> {
@@ -12,60 +19,224 @@ pub fn get_template(id: i64) -> Result<Template, String> {
     get_template_impl(id).map_err(|e| e.to_string())
 }
 
-fn list_templates_impl() -> AppResult<Vec<Template>> {
+#[tauri::command]
+pub fn test_render_template(
+    template_id: i64,
+    sample_input: Option<EscalationInput>,
+) -> Result<TemplateRenderResult, String> {
+    test_render_template_impl(template_id, sample_input).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_checklist_item_stats(template_id: i64) -> Result<Vec<ChecklistItemStat>, String> {
+    get_checklist_item_stats_impl(template_id).map_err(|e| e.to_string())
+}
+
+/// Templates whose category fits the ticket's Jira components and labels
+#[tauri::command]
+pub async fn get_templates_for_ticket(app: AppHandle, key: String) -> Result<Vec<Template>, String> {
+    get_templates_for_ticket_impl(app, key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Validate and import every template JSON file in a folder, reporting per file
+#[tauri::command]
+pub fn import_templates_from_directory(path: String) -> Result<Vec<TemplateImportResult>, String> {
+    import_templates_from_directory_impl(path).map_err(|e| e.to_string())
+}
+
+/// Export every template as a signed `.thpack`, signed with the template pack key from Settings
+#[tauri::command]
+pub fn export_template_pack(path: String) -> Result<TemplatePackManifest, String> {
+    export_template_pack_impl(path).map_err(|e| e.to_string())
+}
+
+/// Verify a signed `.thpack` and import its templates, reporting per file; a pack that fails verification imports nothing
+#[tauri::command]
+pub fn import_template_pack(path: String) -> Result<Vec<TemplateImportResult>, String> {
+    import_template_pack_impl(path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_next_step_blocks() -> Result<Vec<NextStepBlock>, String> {
+    db::get_connection()
+        .and_then(|conn| next_step_blocks::list(&conn))
+        .map_err(|e| e.to_string())
+}
+
+/// Create a reusable next-steps block, or update it when `block.id` is set
+#[tauri::command]
+pub fn save_next_step_block(block: NextStepBlockInput) -> Result<NextStepBlock, String> {
+    db::get_connection()
+        .and_then(|conn| next_step_blocks::save(&conn, &block))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_next_step_block(id: i64) -> Result<(), String> {
+    db::get_connection()
+        .and_then(|conn| next_step_blocks::delete(&conn, id))
+        .map_err(|e| e.to_string())
+}
+
+fn template_from_row(row: &rusqlite::Row) -> rusqlite::Result<Template> {
+    let template_id: i64 = row.get(0)?;
+    let checklist_json: String = row.get(4)?;
+    let checklist_items: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
+        .map_err(|e| {
+            log::error!("Corrupted checklist data for template {}: {}", template_id, e);
+            rusqlite::Error::InvalidQuery
+        })?;
+
+    // NULL for templates that predate expected artifacts
+    let expected_artifacts = row
+        .get::<_, Option<String>>(7)?
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    Ok(Template {
+        id: template_id,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        category: row.get(3)?,
+        checklist_items,
+        l2_team: row.get(5)?,
+        body: row.get(6)?,
+        expected_artifacts,
+        pinned: false,
+    })
+}
+
+pub fn list_templates_impl() -> AppResult<Vec<Template>> {
     let conn = db::get_connection()?;
 
     let mut stmt = conn.prepare(
-        "SELECT id, name, description, category, checklist_items, l2_team FROM templates ORDER BY category, name"
+        "SELECT id, name, description, category, checklist_items, l2_team, body, expected_artifacts
+        FROM templates ORDER BY category, name"
     )?;
 
-    let templates = stmt.query_map([], |row| {
-        let template_id: i64 = row.get(0)?;
-        let checklist_json: String = row.get(4)?;
-        let checklist_items: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
-            .map_err(|e| {
-                log::error!("Corrupted checklist data for template {}: {}", template_id, e);
-                rusqlite::Error::InvalidQuery
-            })?;
-
-        Ok(Template {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            category: row.get(3)?,
-            checklist_items,
-            l2_team: row.get(5)?,
+    let templates = stmt
+        .query_map([], template_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let preferences = db::get_preferences()?;
+    Ok(template_filter::pin_first(
+        templates,
+        preferences.default_template_id,
+        &preferences.pinned_template_ids,
+    ))
+}
+
+pub fn get_template_impl(id: i64) -> AppResult<Template> {
+    let conn = db::get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, name, description, category, checklist_items, l2_team, body, expected_artifacts
+        FROM templates WHERE id = ?"
+    )?;
+
+    let template = stmt.query_row([id], template_from_row)?;
+
+    Ok(template)
+}
+
+async fn get_templates_for_ticket_impl(
+    app: AppHandle,
+    key: String,
+) -> Result<Vec<Template>, Box<dyn std::error::Error>> {
+    // Opening the picker shouldn't cost a full ticket fetch (or log one as activity)
+    let key = normalize_ticket_key(&key)?;
+    let client = get_jira_client(app).await?;
+    let ticket = client.fetch_issue_classification(&key).await?;
+    let templates = list_templates_impl()?;
+    let preferences = db::get_preferences()?;
+
+    Ok(template_filter::relevant(templates, &ticket, &preferences.template_category_map))
+}
+
+fn import_templates_from_directory_impl(path: String) -> AppResult<Vec<TemplateImportResult>> {
+    let mut conn = db::get_connection()?;
+    let results = template_files::import_directory(&mut conn, std::path::Path::new(&path))?;
+
+    let imported = results.iter().filter(|r| r.template_id.is_some()).count();
+    log::info!("Imported {} of {} template files from {}", imported, results.len(), path);
+    Ok(results)
+}
+
+fn export_template_pack_impl(path: String) -> AppResult<TemplatePackManifest> {
+    let key = keychain::get_template_pack_key()?;
+    let mut path = std::path::PathBuf::from(path);
+    if path.extension().is_none() {
+        path.set_extension(template_pack::PACK_EXTENSION);
+    }
+
+    let templates: Vec<template_files::TemplateFile> = list_templates_impl()?
+        .into_iter()
+        .map(|template| template_files::TemplateFile {
+            name: template.name,
+            description: template.description,
+            category: template.category,
+            checklist_items: template.checklist_items,
+            l2_team: template.l2_team,
+            body: template.body,
+            expected_artifacts: template.expected_artifacts,
         })
-    })?
-    .collect::<Result<Vec<_>, _>>()?;
+        .collect();
+
+    let manifest = template_pack::write_pack(&templates, &key, &path)?;
+    log::info!("Exported {} templates to {}", manifest.files.len(), path.display());
+    Ok(manifest)
+}
+
+fn import_template_pack_impl(path: String) -> AppResult<Vec<TemplateImportResult>> {
+    let key = keychain::get_template_pack_key()?;
+    let files = template_pack::read_pack(std::path::Path::new(&path), &key)?
+        .into_iter()
+        .map(|(file_name, json)| (file_name, Ok(json)))
+        .collect();
 
-    Ok(templates)
+    let mut conn = db::get_connection()?;
+    let results = template_files::import_files(&mut conn, files)?;
+    let imported = results.iter().filter(|r| r.template_id.is_some()).count();
+    log::info!("Imported {} of {} templates from pack {}", imported, results.len(), path);
+    Ok(results)
 }
 
-fn get_template_impl(id: i64) -> AppResult<Template> {
+fn test_render_template_impl(
+    template_id: i64,
+    sample_input: Option<EscalationInput>,
+) -> AppResult<TemplateRenderResult> {
+    let template = get_template_impl(template_id)?;
+    let input = sample_input.unwrap_or_else(|| template_engine::sample_input(&template));
+
+    let blocks = next_step_blocks::list(&*db::get_connection()?)?;
+
+    Ok(template_engine::test_render(&template, &input, &blocks))
+}
+
+fn get_checklist_item_stats_impl(template_id: i64) -> AppResult<Vec<ChecklistItemStat>> {
+    let template = get_template_impl(template_id)?;
     let conn = db::get_connection()?;
 
+    // Drafts are still being filled in, so only count submitted escalations
     let mut stmt = conn.prepare(
-        "SELECT id, name, description, category, checklist_items, l2_team FROM templates WHERE id = ?"
+        "SELECT id, checklist FROM escalations WHERE template_id = ? AND status != 'draft'"
     )?;
+    let rows = stmt
+        .query_map([template_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let template = stmt.query_row([id], |row| {
-        let checklist_json: String = row.get(4)?;
-        let checklist_items: Vec<ChecklistItem> = serde_json::from_str(&checklist_json)
-            .map_err(|e| {
-                log::error!("Corrupted checklist data for template {}: {}", id, e);
-                rusqlite::Error::InvalidQuery
-            })?;
-
-        Ok(Template {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            category: row.get(3)?,
-            checklist_items,
-            l2_team: row.get(5)?,
+    let checklists: Vec<Vec<ChecklistItem>> = rows
+        .into_iter()
+        .filter_map(|(id, json)| match serde_json::from_str(&json) {
+            Ok(checklist) => Some(checklist),
+            Err(e) => {
+                log::warn!("Skipping corrupted checklist for escalation {}: {}", id, e);
+                None
+            }
         })
-    })?;
+        .collect();
 
-    Ok(template)
+    Ok(checklist_stats::aggregate(&template.checklist_items, &checklists))
 }
@@ -1,5 +1,20 @@
-use crate::commands::settings::get_jira_client;
-use crate::models::JiraTicket;
+use crate::commands::onboarding::ensure_onboarded;
+use crate::commands::escalations::load_ticket_attachments;
+use crate::commands::settings::{ensure_jira_writable, ensure_posting_window, get_jira_client};
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    Attachment, AttachmentQuotaCheck, CandidateAttachment, EscalationCommentRef, EscalationQueue, JiraAccount, JiraTicket, MyOpenTickets, ScreeningReport, ScreeningSeverity,
+};
+use crate::services::{activity, attachment_quota, chunked_upload, correlation, escalation_queue, redaction, ticket_cache};
+use crate::services::attachment_quota::QuotaLimits;
+use crate::services::jira::JiraClient;
+use crate::services::screening::{self, ScreeningConfig};
+use crate::services::ticket_keys::TicketKeyRules;
+use crate::services::watch_folders;
+use chrono::Utc;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
 use tauri::AppHandle;
 
 #[tauri::command]
@@ -10,7 +25,18 @@ pub async fn fetch_jira_ticket(app: AppHandle, ticket_id: String) -> Result<Jira
 }
 
 #[tauri::command]
-pub async fn post_to_jira(app: AppHandle, ticket_id: String, comment: String) -> Result<(), String> {
+pub async fn post_to_jira(
+    app: AppHandle,
+    ticket_id: String,
+    comment: String,
+    confirm_freeze: Option<bool>,
+) -> Result<(), String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    // Not tied to an escalation, so the override goes to the log rather than an audit trail
+    if let Some(window) = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())? {
+        log::warn!("Comment posted to {} during freeze window \"{}\"", ticket_id, window.label);
+    }
     post_to_jira_impl(app, ticket_id, comment)
         .await
         .map_err(|e| e.to_string())
@@ -21,24 +47,297 @@ pub async fn attach_files_to_jira(
     app: AppHandle,
     ticket_id: String,
     file_paths: Vec<String>,
+    confirm_freeze: Option<bool>,
 ) -> Result<(), String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    if let Some(window) = ensure_posting_window(confirm_freeze.unwrap_or(false)).map_err(|e| e.to_string())? {
+        log::warn!("Files attached to {} during freeze window \"{}\"", ticket_id, window.label);
+    }
     attach_files_to_jira_impl(app, ticket_id, file_paths)
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn find_escalation_comments(
+    app: AppHandle,
+    key: String,
+) -> Result<Vec<EscalationCommentRef>, String> {
+    find_escalation_comments_impl(app, key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn add_remote_link(
+    app: AppHandle,
+    key: String,
+    url: String,
+    title: String,
+) -> Result<(), String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    add_remote_link_impl(app, key, url, title)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn create_jira_ticket(
+    app: AppHandle,
+    project: String,
+    summary: String,
+    description: String,
+    issue_type: Option<String>,
+) -> Result<String, String> {
+    ensure_jira_writable().map_err(|e| e.to_string())?;
+    ensure_onboarded().map_err(|e| e.to_string())?;
+    create_jira_ticket_impl(app, project, summary, description, issue_type)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn search_jira_users(app: AppHandle, query: String) -> Result<Vec<JiraAccount>, String> {
+    search_jira_users_impl(app, query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The agent's open tickets from the background prefetch cache
+///
+/// With `refresh`, or when nothing has been cached yet, the list is fetched
+/// from Jira first (at most once a minute).
+#[tauri::command]
+pub async fn list_my_open_tickets(app: AppHandle, refresh: Option<bool>) -> Result<MyOpenTickets, String> {
+    list_my_open_tickets_impl(app, refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tickets flagged as needing an L2 handoff, from the cache unless `refresh` is set
+#[tauri::command]
+pub async fn list_escalation_queue(app: AppHandle, refresh: Option<bool>) -> Result<EscalationQueue, String> {
+    list_escalation_queue_impl(app, refresh.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Keep the open-ticket cache and the escalation queue fresh while they are enabled in preferences
+///
+/// Preferences are re-read every poll, so turning either on or off takes
+/// effect without a restart.
+pub fn spawn_ticket_prefetch(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // A failed refresh counts as an attempt, so an unreachable Jira is retried on the interval, not every poll
+        let mut prefetch_failed_at: Option<String> = None;
+        let mut queue_failed_at: Option<String> = None;
+        loop {
+            if let Err(e) = prefetch_if_due(app.clone(), prefetch_failed_at.as_deref()).await {
+                log::warn!("Background ticket prefetch failed: {}", e);
+                prefetch_failed_at = Some(Utc::now().to_rfc3339());
+            }
+            if let Err(e) = poll_queue_if_due(app.clone(), queue_failed_at.as_deref()).await {
+                log::warn!("Background escalation queue poll failed: {}", e);
+                queue_failed_at = Some(Utc::now().to_rfc3339());
+            }
+            tokio::time::sleep(PREFETCH_POLL).await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn parse_ticket_reference(input: String) -> Result<String, String> {
+    normalize_ticket_key(&input).map_err(|e| e.to_string())
+}
+
+/// Validate a ticket key (or extract it from a pasted URL) using the configured format
+pub fn normalize_ticket_key(input: &str) -> AppResult<String> {
+    let preferences = db::get_preferences()?;
+    TicketKeyRules::new(&preferences.ticket_key_pattern)?.parse(input)
+}
+
+/// Recent files from the configured watch folders that look like they belong to the ticket
+#[tauri::command]
+pub fn list_candidate_attachments(ticket_key: String) -> Result<Vec<CandidateAttachment>, String> {
+    list_candidate_attachments_impl(&ticket_key).map_err(|e| e.to_string())
+}
+
+fn list_candidate_attachments_impl(ticket_key: &str) -> AppResult<Vec<CandidateAttachment>> {
+    let ticket_key = normalize_ticket_key(ticket_key)?;
+    let preferences = db::get_preferences()?;
+    let window = Duration::from_secs(u64::from(preferences.watch_window_hours) * 3600);
+
+    Ok(watch_folders::candidates(&preferences.watch_folders, &ticket_key, window, SystemTime::now()))
+}
+
+/// Check files against the instance's upload limit and the ticket's attachment budget before uploading
+#[tauri::command]
+pub async fn check_attachment_quota(
+    app: AppHandle,
+    ticket_id: String,
+    file_paths: Vec<String>,
+) -> Result<AttachmentQuotaCheck, String> {
+    check_attachment_quota_impl(app, ticket_id, file_paths)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+async fn check_attachment_quota_impl(
+    app: AppHandle,
+    ticket_id: String,
+    file_paths: Vec<String>,
+) -> Result<AttachmentQuotaCheck, Box<dyn std::error::Error>> {
+    let ticket_id = normalize_ticket_key(&ticket_id)?;
+    let client = get_jira_client(app).await?;
+    Ok(attachment_quota_check(&client, &ticket_id, &file_paths).await?)
+}
+
+pub async fn attachment_quota_check(
+    client: &JiraClient,
+    ticket_id: &str,
+    file_paths: &[String],
+) -> AppResult<AttachmentQuotaCheck> {
+    let files = attachment_quota::file_sizes(file_paths);
+    let settings = client.attachment_settings().await?;
+    let existing_bytes = client.attachment_bytes(ticket_id).await?;
+    let preferences = db::get_preferences()?;
+
+    let limits = QuotaLimits {
+        attachments_enabled: settings.enabled,
+        upload_limit: settings.upload_limit,
+        chunk_bytes: preferences.upload_chunk_size_mb.filter(|mb| *mb > 0).map(|mb| u64::from(mb) * 1024 * 1024),
+        ticket_budget: preferences.ticket_attachment_budget_mb.map(|mb| u64::from(mb) * 1024 * 1024),
+    };
+
+    Ok(AttachmentQuotaCheck {
+        upload_limit_bytes: settings.upload_limit,
+        existing_bytes,
+        new_bytes: files.iter().map(|(_, size)| size).sum(),
+        warnings: attachment_quota::check(&files, existing_bytes, &limits),
+        rejections: attachment_quota::rejections(&files, &limits),
+    })
+}
+
+#[tauri::command]
+pub async fn screen_attachments(file_paths: Vec<String>) -> Result<ScreeningReport, String> {
+    screen_attachments_impl(&file_paths).await.map_err(|e| e.to_string())
+}
+
+/// The ClamAV scan streams whole files over a blocking socket, so screening runs off the async runtime
+pub async fn screen_attachments_impl(file_paths: &[String]) -> AppResult<ScreeningReport> {
+    let preferences = db::get_preferences()?;
+    let file_paths = file_paths.to_vec();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = ScreeningConfig {
+            blocked_extensions: &preferences.blocked_extensions,
+            clamav_socket: preferences.clamav_socket.as_deref(),
+        };
+        screening::screen_files(&file_paths, &config)
+    })
+    .await
+    .map_err(|e| AppError::File(format!("Attachment screening failed: {}", e)))
+}
+
+/// Fail with a validation error if any file is blocked by screening
+pub async fn ensure_attachments_allowed(file_paths: &[String]) -> AppResult<()> {
+    let report = screen_attachments_impl(file_paths).await?;
+    if report.blocked_count == 0 {
+        return Ok(());
+    }
+
+    let reasons: Vec<String> = report
+        .results
+        .iter()
+        .filter(|r| !r.allowed)
+        .map(|r| {
+            let messages: Vec<&str> = r
+                .issues
+                .iter()
+                .filter(|i| i.severity == ScreeningSeverity::Block)
+                .map(|i| i.message.as_str())
+                .collect();
+            format!("{}: {}", r.file_path, messages.join("; "))
+        })
+        .collect();
+
+    Err(AppError::Validation(format!(
+        "{} attachment(s) blocked by screening:\n{}",
+        report.blocked_count,
+        reasons.join("\n")
+    )))
+}
+
+/// Attach a file, splitting large ones into parts and skipping parts Jira already has
+pub async fn attach_file_resumable(client: &JiraClient, ticket_id: &str, path: &Path) -> AppResult<()> {
+    let Some(chunk_mb) = db::get_preferences()?.upload_chunk_size_mb.filter(|mb| *mb > 0) else {
+        return client.attach_file(ticket_id, path).await;
+    };
+
+    // Hashing, zipping and splitting read the whole file, so keep them off the async runtime
+    let work_dir = chunked_upload::work_dir();
+    let plan = {
+        let (path, work_dir) = (path.to_path_buf(), work_dir.clone());
+        tauri::async_runtime::spawn_blocking(move || {
+            chunked_upload::prepare(&path, chunk_mb as u64 * 1024 * 1024, &work_dir)
+        })
+        .await
+        .map_err(|e| AppError::File(format!("Preparing upload parts failed: {}", e)))??
+    };
+    if plan.parts.len() == 1 && plan.parts[0] == path {
+        return client.attach_file(ticket_id, path).await;
+    }
+
+    let done = chunked_upload::uploaded_parts(&*db::get_connection()?, ticket_id, &plan.file_sha256)?;
+    for part in &plan.parts {
+        let name = chunked_upload::part_name(part);
+        if done.contains(&name) {
+            continue;
+        }
+
+        client.attach_file(ticket_id, part).await?;
+        chunked_upload::mark_uploaded(&*db::get_connection()?, ticket_id, &plan.file_sha256, &name)?;
+    }
+
+    chunked_upload::finish(&*db::get_connection()?, ticket_id, &plan.file_sha256, &work_dir)
+}
+
+/// Attach a file, uploading a redacted copy instead when its attachment has redactions
+pub async fn attach_file_redacted(
+    client: &JiraClient,
+    ticket_id: &str,
+    attachments: &[Attachment],
+    file_path: &str,
+) -> AppResult<()> {
+    let path = Path::new(file_path);
+    let redactions = redaction::redactions_for(file_path, attachments)?;
+    if redactions.is_empty() {
+        return attach_file_resumable(client, ticket_id, path).await;
+    }
+
+    let copy = redaction::redacted_copy(path, redactions)?;
+    let result = attach_file_resumable(client, ticket_id, &copy).await;
+    redaction::remove_copy(&copy);
+    result
+}
+
 async fn attach_files_to_jira_impl(
     app: AppHandle,
     ticket_id: String,
     file_paths: Vec<String>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let ticket_id = normalize_ticket_key(&ticket_id)?;
+    ensure_attachments_allowed(&file_paths).await?;
+
     let client = get_jira_client(app).await?;
+    let attachments = load_ticket_attachments(&ticket_id)?;
 
     let mut failed_files = Vec::new();
 
     for file_path in &file_paths {
-        let path = std::path::Path::new(file_path);
-        match client.attach_file(&ticket_id, path).await {
+        match attach_file_redacted(&client, &ticket_id, &attachments, file_path).await {
             Ok(_) => {},
             Err(e) => {
                 failed_files.push(format!("{}: {}", file_path, e));
@@ -57,21 +356,196 @@ async fn attach_files_to_jira_impl(
     Ok(())
 }
 
-async fn fetch_jira_ticket_impl(
+pub async fn fetch_jira_ticket_impl(
     app: AppHandle,
     ticket_id: String,
 ) -> Result<JiraTicket, Box<dyn std::error::Error>> {
+    let ticket_id = normalize_ticket_key(&ticket_id)?;
     let client = get_jira_client(app).await?;
     let ticket = client.fetch_issue(&ticket_id).await?;
+
+    let recorded = db::get_connection()
+        .and_then(|conn| activity::record(&conn, activity::KIND_TICKET_FETCHED, Some(&ticket.key), None));
+    if let Err(e) = recorded {
+        log::warn!("Failed to record ticket fetch for {}: {}", ticket.key, e);
+    }
+
     Ok(ticket)
 }
 
+const PREFETCH_POLL: Duration = Duration::from_secs(60);
+
+async fn list_my_open_tickets_impl(app: AppHandle, refresh: bool) -> Result<MyOpenTickets, Box<dyn std::error::Error>> {
+    let cached = ticket_cache::load(&*db::get_connection()?)?;
+    let wanted = refresh || cached.fetched_at.is_none();
+    if !wanted || !ticket_cache::is_due(cached.fetched_at.as_deref(), Utc::now(), ticket_cache::MIN_MANUAL_INTERVAL) {
+        return Ok(cached);
+    }
+
+    let jql = db::get_preferences()?.ticket_prefetch_jql;
+    refresh_open_tickets(app, &jql).await?;
+    Ok(ticket_cache::load(&*db::get_connection()?)?)
+}
+
+async fn prefetch_if_due(app: AppHandle, failed_at: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let preferences = db::get_preferences()?;
+    let Some(minutes) = preferences.ticket_prefetch_minutes else {
+        return Ok(());
+    };
+
+    let interval = Duration::from_secs(u64::from(minutes) * 60).max(ticket_cache::MIN_PREFETCH_INTERVAL);
+    let cached = ticket_cache::load(&*db::get_connection()?)?;
+    let now = Utc::now();
+    if !ticket_cache::is_due(cached.fetched_at.as_deref(), now, interval) || !ticket_cache::is_due(failed_at, now, interval) {
+        return Ok(());
+    }
+
+    refresh_open_tickets(app, &preferences.ticket_prefetch_jql).await
+}
+
+async fn refresh_open_tickets(app: AppHandle, jql: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let client = get_jira_client(app).await?;
+    let tickets = client.search_issues(jql, ticket_cache::MAX_TICKETS).await?;
+    ticket_cache::save(&*db::get_connection()?, &tickets, Utc::now())?;
+    Ok(())
+}
+
+async fn list_escalation_queue_impl(app: AppHandle, refresh: bool) -> Result<EscalationQueue, Box<dyn std::error::Error>> {
+    let preferences = db::get_preferences()?;
+    let Some(jql) = escalation_queue::configured_jql(preferences.escalation_queue_jql.as_deref()) else {
+        return Ok(escalation_queue::load(&*db::get_connection()?, false)?);
+    };
+
+    let cached = escalation_queue::load(&*db::get_connection()?, true)?;
+    let wanted = refresh || cached.fetched_at.is_none();
+    if !wanted || !ticket_cache::is_due(cached.fetched_at.as_deref(), Utc::now(), ticket_cache::MIN_MANUAL_INTERVAL) {
+        return Ok(cached);
+    }
+
+    refresh_escalation_queue(app, jql).await?;
+    Ok(escalation_queue::load(&*db::get_connection()?, true)?)
+}
+
+async fn poll_queue_if_due(app: AppHandle, failed_at: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let preferences = db::get_preferences()?;
+    let Some(jql) = escalation_queue::configured_jql(preferences.escalation_queue_jql.as_deref()) else {
+        return Ok(());
+    };
+
+    let interval = Duration::from_secs(u64::from(preferences.escalation_queue_minutes) * 60)
+        .max(ticket_cache::MIN_PREFETCH_INTERVAL);
+    let cached = escalation_queue::load(&*db::get_connection()?, true)?;
+    let now = Utc::now();
+    if !ticket_cache::is_due(cached.fetched_at.as_deref(), now, interval) || !ticket_cache::is_due(failed_at, now, interval) {
+        return Ok(());
+    }
+
+    refresh_escalation_queue(app, jql).await
+}
+
+async fn refresh_escalation_queue(app: AppHandle, jql: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let client = get_jira_client(app).await?;
+    let tickets = client.search_issues(jql, ticket_cache::MAX_TICKETS).await?;
+    escalation_queue::save(&*db::get_connection()?, &tickets, Utc::now())?;
+    Ok(())
+}
+
 async fn post_to_jira_impl(
     app: AppHandle,
     ticket_id: String,
     comment: String,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let ticket_id = normalize_ticket_key(&ticket_id)?;
     let client = get_jira_client(app).await?;
     client.post_comment(&ticket_id, &comment).await?;
     Ok(())
 }
+
+async fn find_escalation_comments_impl(
+    app: AppHandle,
+    key: String,
+) -> Result<Vec<EscalationCommentRef>, Box<dyn std::error::Error>> {
+    let key = normalize_ticket_key(&key)?;
+    let marker = db::get_preferences()?.correlation_marker;
+    let client = get_jira_client(app).await?;
+
+    let matches = client
+        .list_comments(&key)
+        .await?
+        .into_iter()
+        .filter_map(|comment| {
+            correlation::extract_escalation_id(&comment.body_text, &marker).map(|escalation_id| {
+                EscalationCommentRef {
+                    comment_id: comment.id,
+                    escalation_id,
+                    author: comment.author,
+                    created: comment.created,
+                }
+            })
+        })
+        .collect();
+
+    Ok(matches)
+}
+
+async fn search_jira_users_impl(
+    app: AppHandle,
+    query: String,
+) -> Result<Vec<JiraAccount>, Box<dyn std::error::Error>> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Err(AppError::Validation("Enter a name or email to search for".to_string()).into());
+    }
+
+    let client = get_jira_client(app).await?;
+    Ok(client.search_users(query).await?)
+}
+
+async fn add_remote_link_impl(
+    app: AppHandle,
+    key: String,
+    url: String,
+    title: String,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let key = normalize_ticket_key(&key)?;
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid link URL: {}", url).into());
+    }
+
+    let title = if title.trim().is_empty() { url.clone() } else { title };
+
+    let client = get_jira_client(app).await?;
+    client.add_remote_link(&key, &url, &title).await?;
+    Ok(())
+}
+
+async fn create_jira_ticket_impl(
+    app: AppHandle,
+    project: String,
+    summary: String,
+    description: String,
+    issue_type: Option<String>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let project = project.trim().to_uppercase();
+    if project.is_empty() {
+        return Err("Project key is required".into());
+    }
+
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err("Ticket summary is required".into());
+    }
+    if summary.chars().count() > 255 {
+        return Err("Ticket summary must be 255 characters or fewer".into());
+    }
+
+    let issue_type = issue_type
+        .filter(|t| !t.trim().is_empty())
+        .unwrap_or_else(|| "Task".to_string());
+
+    let client = get_jira_client(app).await?;
+    let key = client
+        .create_issue(&project, &issue_type, summary, &description)
+        .await?;
+    Ok(key)
+}
@@ -1,5 +1,6 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{ApiConfig, ChecklistItem};
+use crate::models::{ApiConfig, JiraIdentity, Preferences, StartupStatus};
+use crate::services::template_files;
 use once_cell::sync::Lazy;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::params;
@@ -10,7 +11,14 @@ type PooledConnection = r2d2::PooledConnection<SqliteConnectionManager>;
 
 static DB_POOL: Lazy<Mutex<Option<DbPool>>> = Lazy::new(|| Mutex::new(None));
 
+/// Where the database lives and why it failed to open, if it did
+static STARTUP: Lazy<Mutex<StartupStatus>> = Lazy::new(|| Mutex::new(StartupStatus::default()));
+
 pub fn init_db(db_path: &str) -> AppResult<()> {
+    if let Ok(mut startup) = STARTUP.lock() {
+        startup.db_path = Some(db_path.to_string());
+    }
+
     // Create connection pool
     let manager = SqliteConnectionManager::file(db_path);
     let pool = r2d2::Pool::builder()
@@ -26,6 +34,9 @@ pub fn init_db(db_path: &str) -> AppResult<()> {
     // Run migrations
     run_migrations(&conn)?;
 
+    // Seed templates if empty (on this connection: each ":memory:" connection is its own database)
+    seed_templates(&conn)?;
+
     // Release connection
     drop(conn);
 
@@ -35,12 +46,48 @@ pub fn init_db(db_path: &str) -> AppResult<()> {
         .map_err(|_| AppError::Db("Pool lock poisoned".into()))?;
     *pool_guard = Some(pool);
 
-    // Seed templates if empty
-    seed_templates()?;
+    if let Ok(mut startup) = STARTUP.lock() {
+        startup.initializing = false;
+        startup.degraded = false;
+        startup.error = None;
+    }
 
     Ok(())
 }
 
+/// Record that the database is being opened in the background, so commands report that instead of a failure
+pub fn mark_initializing(db_path: &str) {
+    if let Ok(mut startup) = STARTUP.lock() {
+        startup.initializing = true;
+        startup.db_path = Some(db_path.to_string());
+    }
+}
+
+/// Record a startup failure so the UI can boot into settings-only mode
+pub fn mark_degraded(db_path: Option<&str>, error: &str) {
+    if let Ok(mut startup) = STARTUP.lock() {
+        startup.initializing = false;
+        startup.degraded = true;
+        startup.error = Some(error.to_string());
+        if let Some(path) = db_path {
+            startup.db_path = Some(path.to_string());
+        }
+    }
+}
+
+pub fn startup_status() -> StartupStatus {
+    STARTUP.lock().map(|s| s.clone()).unwrap_or_default()
+}
+
+/// Drop the pool so the database file can be moved or replaced
+pub fn close() -> AppResult<()> {
+    let mut pool_guard = DB_POOL
+        .lock()
+        .map_err(|_| AppError::Db("Pool lock poisoned".into()))?;
+    *pool_guard = None;
+    Ok(())
+}
+
 fn run_migrations(conn: &rusqlite::Connection) -> AppResult<()> {
     // Create schema_migrations table if it doesn't exist
     conn.execute(
@@ -74,12 +121,218 @@ fn run_migrations(conn: &rusqlite::Connection) -> AppResult<()> {
         // Note: 002_security.sql inserts its own version record
     }
 
+    // Apply migration 003 if needed
+    if applied_version < 3 {
+        let migration_003 = include_str!("../migrations/003_preferences.sql");
+        conn.execute_batch(migration_003)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (3)", [])?;
+    }
+
+    // Apply migration 004 if needed
+    if applied_version < 4 {
+        let migration_004 = include_str!("../migrations/004_attachments.sql");
+        conn.execute_batch(migration_004)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (4)", [])?;
+    }
+
+    // Apply migration 005 if needed
+    if applied_version < 5 {
+        let migration_005 = include_str!("../migrations/005_template_body.sql");
+        conn.execute_batch(migration_005)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (5)", [])?;
+    }
+
+    // Apply migration 006 if needed
+    if applied_version < 6 {
+        let migration_006 = include_str!("../migrations/006_maintenance.sql");
+        conn.execute_batch(migration_006)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (6)", [])?;
+    }
+
+    // Apply migration 007 if needed
+    if applied_version < 7 {
+        let migration_007 = include_str!("../migrations/007_bounces.sql");
+        conn.execute_batch(migration_007)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (7)", [])?;
+    }
+
+    // Apply migration 008 if needed
+    if applied_version < 8 {
+        let migration_008 = include_str!("../migrations/008_attachment_retention.sql");
+        conn.execute_batch(migration_008)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (8)", [])?;
+    }
+
+    // Apply migration 009 if needed
+    if applied_version < 9 {
+        let migration_009 = include_str!("../migrations/009_private_notes.sql");
+        conn.execute_batch(migration_009)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (9)", [])?;
+    }
+
+    // Apply migration 010 if needed
+    if applied_version < 10 {
+        let migration_010 = include_str!("../migrations/010_escalation_locks.sql");
+        conn.execute_batch(migration_010)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (10)", [])?;
+    }
+
+    // Apply migration 011 if needed
+    if applied_version < 11 {
+        let migration_011 = include_str!("../migrations/011_escalation_embeddings.sql");
+        conn.execute_batch(migration_011)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (11)", [])?;
+    }
+
+    // Apply migration 012 if needed
+    if applied_version < 12 {
+        let migration_012 = include_str!("../migrations/012_ticket_snapshot.sql");
+        conn.execute_batch(migration_012)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (12)", [])?;
+    }
+
+    // Apply migration 013 if needed
+    if applied_version < 13 {
+        let migration_013 = include_str!("../migrations/013_upload_failures.sql");
+        conn.execute_batch(migration_013)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (13)", [])?;
+    }
+
+    // Apply migration 014 if needed
+    if applied_version < 14 {
+        let migration_014 = include_str!("../migrations/014_upload_parts.sql");
+        conn.execute_batch(migration_014)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (14)", [])?;
+    }
+
+    // Apply migration 015 if needed
+    if applied_version < 15 {
+        let migration_015 = include_str!("../migrations/015_summary_translation.sql");
+        conn.execute_batch(migration_015)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (15)", [])?;
+    }
+
+    // Apply migration 016 if needed
+    if applied_version < 16 {
+        let migration_016 = include_str!("../migrations/016_expected_artifacts.sql");
+        conn.execute_batch(migration_016)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (16)", [])?;
+    }
+
+    // Apply migration 017 if needed
+    if applied_version < 17 {
+        let migration_017 = include_str!("../migrations/017_jira_identity.sql");
+        conn.execute_batch(migration_017)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (17)", [])?;
+    }
+
+    // Apply migration 018 if needed
+    if applied_version < 18 {
+        let migration_018 = include_str!("../migrations/018_jobs.sql");
+        conn.execute_batch(migration_018)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (18)", [])?;
+    }
+
+    if applied_version < 19 {
+        let migration_019 = include_str!("../migrations/019_kb_drafts.sql");
+        conn.execute_batch(migration_019)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (19)", [])?;
+    }
+
+    if applied_version < 20 {
+        let migration_020 = include_str!("../migrations/020_attachment_annotations.sql");
+        conn.execute_batch(migration_020)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (20)", [])?;
+    }
+
+    if applied_version < 21 {
+        let migration_021 = include_str!("../migrations/021_cold_storage.sql");
+        conn.execute_batch(migration_021)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (21)", [])?;
+    }
+
+    if applied_version < 22 {
+        let migration_022 = include_str!("../migrations/022_activity_log.sql");
+        conn.execute_batch(migration_022)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (22)", [])?;
+    }
+
+    if applied_version < 23 {
+        let migration_023 = include_str!("../migrations/023_reescalation.sql");
+        conn.execute_batch(migration_023)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (23)", [])?;
+    }
+
+    if applied_version < 24 {
+        let migration_024 = include_str!("../migrations/024_open_ticket_cache.sql");
+        conn.execute_batch(migration_024)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (24)", [])?;
+    }
+
+    if applied_version < 25 {
+        let migration_025 = include_str!("../migrations/025_acknowledgments.sql");
+        conn.execute_batch(migration_025)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (25)", [])?;
+    }
+
+    if applied_version < 26 {
+        let migration_026 = include_str!("../migrations/026_next_step_blocks.sql");
+        conn.execute_batch(migration_026)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (26)", [])?;
+    }
+
+    if applied_version < 27 {
+        let migration_027 = include_str!("../migrations/027_render_version.sql");
+        conn.execute_batch(migration_027)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (27)", [])?;
+    }
+
+    if applied_version < 28 {
+        let migration_028 = include_str!("../migrations/028_escalation_teams.sql");
+        conn.execute_batch(migration_028)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (28)", [])?;
+    }
+
+    if applied_version < 29 {
+        let migration_029 = include_str!("../migrations/029_onboarding.sql");
+        conn.execute_batch(migration_029)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (29)", [])?;
+    }
+
+    if applied_version < 30 {
+        let migration_030 = include_str!("../migrations/030_escalation_queue_cache.sql");
+        conn.execute_batch(migration_030)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (30)", [])?;
+    }
+
+    if applied_version < 31 {
+        let migration_031 = include_str!("../migrations/031_field_history.sql");
+        conn.execute_batch(migration_031)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (31)", [])?;
+    }
+
+    if applied_version < 32 {
+        let migration_032 = include_str!("../migrations/032_llm_usage.sql");
+        conn.execute_batch(migration_032)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (32)", [])?;
+    }
+
+    if applied_version < 33 {
+        let migration_033 = include_str!("../migrations/033_environment_info.sql");
+        conn.execute_batch(migration_033)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (33)", [])?;
+    }
+
+    if applied_version < 34 {
+        let migration_034 = include_str!("../migrations/034_incident_id.sql");
+        conn.execute_batch(migration_034)?;
+        conn.execute("INSERT INTO schema_migrations (version) VALUES (34)", [])?;
+    }
+
     Ok(())
 }
 
-pub fn seed_templates() -> AppResult<()> {
-    let conn = get_connection()?;
-
+fn seed_templates(conn: &rusqlite::Connection) -> AppResult<()> {
     // Check if templates already exist
     let count: i64 = conn.query_row("SELECT COUNT(*) FROM templates", [], |row| row.get(0))?;
     if count > 0 {
@@ -94,31 +347,8 @@ pub fn seed_templates() -> AppResult<()> {
     ];
 
     for template_json in templates_json {
-        #[derive(serde::Deserialize)]
-        struct TemplateJson {
-            name: String,
-            description: String,
-            category: String,
-            checklist_items: Vec<ChecklistItem>,
-            l2_team: Option<String>,
-        }
-
-        let template: TemplateJson = serde_json::from_str(template_json)
-            .map_err(|e| AppError::Validation(format!("Failed to parse template: {}", e)))?;
-
-        let checklist_json = serde_json::to_string(&template.checklist_items)
-            .map_err(|e| AppError::Validation(format!("Failed to serialize checklist: {}", e)))?;
-
-        conn.execute(
-            "INSERT INTO templates (name, description, category, checklist_items, l2_team) VALUES (?, ?, ?, ?, ?)",
-            params![
-                template.name,
-                template.description,
-                template.category,
-                checklist_json,
-                template.l2_team,
-            ],
-        )?;
+        let template = template_files::parse(template_json)?;
+        template_files::insert(conn, &template)?;
     }
 
     Ok(())
@@ -129,9 +359,18 @@ pub fn get_connection() -> AppResult<PooledConnection> {
         .lock()
         .map_err(|_| AppError::Db("Pool lock poisoned".into()))?;
 
-    pool_guard
-        .as_ref()
-        .ok_or(AppError::Db("Database not initialized".into()))?
+    let Some(pool) = pool_guard.as_ref() else {
+        if startup_status().initializing {
+            return Err(AppError::Db(
+                "The database is still initializing. Try again in a moment.".into(),
+            ));
+        }
+        return Err(AppError::Db(
+            "Database not available. Open Settings to repair or reset it.".into(),
+        ));
+    };
+
+    pool
         .get()
         .map_err(|e| AppError::Db(e.to_string().into()))
 }
@@ -174,6 +413,81 @@ pub fn get_api_config() -> AppResult<Option<ApiConfig>> {
     }
 }
 
+/// Audit log columns filled from the cached Jira identity; NULL until a connection test succeeds
+pub const AUDIT_ACTOR_SQL: &str = "(SELECT account_id FROM jira_identity WHERE id = 1), \
+     (SELECT display_name FROM jira_identity WHERE id = 1)";
+
+pub fn save_jira_identity(identity: &JiraIdentity) -> AppResult<()> {
+    let conn = get_connection()?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO jira_identity (id, account_id, display_name, email, avatar_url, fetched_at)
+         VALUES (1, ?, ?, ?, ?, datetime('now'))",
+        params![identity.account_id, identity.display_name, identity.email, identity.avatar_url],
+    )?;
+
+    Ok(())
+}
+
+pub fn get_jira_identity() -> AppResult<Option<JiraIdentity>> {
+    let conn = get_connection()?;
+
+    let result = conn.query_row(
+        "SELECT account_id, display_name, email, avatar_url, fetched_at FROM jira_identity WHERE id = 1",
+        [],
+        |row| {
+            Ok(JiraIdentity {
+                account_id: row.get(0)?,
+                display_name: row.get(1)?,
+                email: row.get(2)?,
+                avatar_url: row.get(3)?,
+                fetched_at: row.get(4)?,
+            })
+        },
+    );
+
+    match result {
+        Ok(identity) => Ok(Some(identity)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(AppError::DbSql(e)),
+    }
+}
+
+pub fn clear_jira_identity() -> AppResult<()> {
+    let conn = get_connection()?;
+    conn.execute("DELETE FROM jira_identity", [])?;
+    Ok(())
+}
+
+pub fn get_preferences() -> AppResult<Preferences> {
+    let conn = get_connection()?;
+
+    let result = conn.query_row("SELECT data FROM preferences WHERE id = 1", [], |row| {
+        row.get::<_, String>(0)
+    });
+
+    match result {
+        Ok(data) => serde_json::from_str(&data)
+            .map_err(|e| AppError::Validation(format!("Failed to parse preferences: {}", e))),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(Preferences::default()),
+        Err(e) => Err(AppError::DbSql(e)),
+    }
+}
+
+pub fn save_preferences(preferences: &Preferences) -> AppResult<()> {
+    let conn = get_connection()?;
+
+    let data = serde_json::to_string(preferences)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize preferences: {}", e)))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO preferences (id, data, updated_at) VALUES (1, ?, datetime('now'))",
+        params![data],
+    )?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,5 +496,6 @@ mod tests {
     fn test_init_db() {
         let result = init_db(":memory:");
         assert!(result.is_ok());
+        assert!(!startup_status().degraded);
     }
 }
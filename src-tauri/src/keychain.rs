@@ -44,3 +44,43 @@ pub fn delete_jira_credentials(email: &str) -> AppResult<()> {
 pub fn credentials_exist(email: &str) -> bool {
     get_generic_password(SERVICE_NAME, email).is_ok()
 }
+
+const SLACK_SERVICE_NAME: &str = "com.tickethandoff.slack";
+const SLACK_ACCOUNT: &str = "signing-secret";
+
+/// Save the Slack app signing secret used to verify slash-command requests
+pub fn save_slack_signing_secret(secret: &str) -> AppResult<()> {
+    set_generic_password(SLACK_SERVICE_NAME, SLACK_ACCOUNT, secret.as_bytes())
+        .map_err(|e| AppError::Keychain(format!("Failed to save Slack signing secret: {}", e)))?;
+
+    Ok(())
+}
+
+/// Retrieve the Slack signing secret from macOS Keychain
+pub fn get_slack_signing_secret() -> AppResult<String> {
+    let secret_bytes = get_generic_password(SLACK_SERVICE_NAME, SLACK_ACCOUNT)
+        .map_err(|e| AppError::Keychain(format!("Failed to retrieve Slack signing secret: {}", e)))?;
+
+    String::from_utf8(secret_bytes)
+        .map_err(|e| AppError::Keychain(format!("Invalid Slack signing secret: {}", e)))
+}
+
+const TEMPLATE_PACK_SERVICE_NAME: &str = "com.tickethandoff.templatepack";
+const TEMPLATE_PACK_ACCOUNT: &str = "signing-key";
+
+/// Save the shared key template packs are signed and verified with
+pub fn save_template_pack_key(key: &str) -> AppResult<()> {
+    set_generic_password(TEMPLATE_PACK_SERVICE_NAME, TEMPLATE_PACK_ACCOUNT, key.as_bytes())
+        .map_err(|e| AppError::Keychain(format!("Failed to save template pack key: {}", e)))?;
+
+    Ok(())
+}
+
+/// Retrieve the template pack signing key from macOS Keychain
+pub fn get_template_pack_key() -> AppResult<String> {
+    let key_bytes = get_generic_password(TEMPLATE_PACK_SERVICE_NAME, TEMPLATE_PACK_ACCOUNT)
+        .map_err(|e| AppError::Keychain(format!("Failed to retrieve template pack key: {}", e)))?;
+
+    String::from_utf8(key_bytes)
+        .map_err(|e| AppError::Keychain(format!("Invalid template pack key: {}", e)))
+}
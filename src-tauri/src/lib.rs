@@ -4,9 +4,14 @@ mod error;
 mod keychain;
 mod models;
 mod services;
+#[cfg(test)]
+mod test_support;
 
-use commands::{escalations, llm, settings, templates, tickets};
-use tauri::Manager;
+use commands::{acknowledgment, activity, drafts, escalations, llm, maintenance, onboarding, settings, templates, tickets};
+use tauri::{Emitter, Manager, RunEvent};
+
+/// Emitted with the `StartupStatus` once the database has opened (or failed to)
+pub const DB_READY_EVENT: &str = "db-ready";
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -14,42 +19,189 @@ pub fn run() {
         .plugin(tauri_plugin_sql::Builder::new().build())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_opener::init())
         .setup(|app| {
-            // Initialize database with proper error handling
-            let app_data_dir = app.handle().path().app_data_dir()
-                .map_err(|e| format!("Cannot access app data directory: {}", e))?;
-
-            std::fs::create_dir_all(&app_data_dir)
-                .map_err(|e| format!("Cannot create app directory: {}. Check disk permissions.", e))?;
+            // A database that can't be opened boots the app into settings-only mode
+            // (see get_startup_status / repair_or_reset_database) instead of aborting
+            let db_path = match database_path(app.handle()) {
+                Ok(path) => path,
+                Err(e) => {
+                    log::error!("{}", e);
+                    db::mark_degraded(None, &e);
+                    return Ok(());
+                }
+            };
 
-            let db_path = app_data_dir.join("tickets.db");
-            let db_path_str = db_path.to_str()
-                .ok_or("Invalid database path with non-UTF8 characters")?;
-
-            db::init_db(db_path_str)
-                .map_err(|e| format!("Database initialization failed: {}\n\nPlease restart the app or check permissions.", e))?;
+            // Migrations and seeding run off the main thread so a slow disk doesn't hold
+            // the window; commands report "initializing" until `db-ready` is emitted
+            db::mark_initializing(&db_path);
+            let handle = app.handle().clone();
+            std::thread::spawn(move || {
+                initialize_database(&handle, &db_path);
+                if let Err(e) = handle.emit(DB_READY_EVENT, db::startup_status()) {
+                    log::error!("Failed to emit {}: {}", DB_READY_EVENT, e);
+                }
+            });
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             templates::list_templates,
             templates::get_template,
+            templates::get_templates_for_ticket,
+            templates::import_templates_from_directory,
+            templates::export_template_pack,
+            templates::import_template_pack,
+            templates::test_render_template,
+            templates::get_checklist_item_stats,
+            templates::list_next_step_blocks,
+            templates::save_next_step_block,
+            templates::delete_next_step_block,
             escalations::save_escalation,
             escalations::get_escalation,
             escalations::list_escalations,
+            escalations::link_incident,
             escalations::delete_escalation,
             escalations::render_markdown,
+            escalations::render_output,
+            escalations::render_with_version,
+            escalations::preview_template_impact,
+            escalations::score_escalation,
+            escalations::lint_escalation,
+            escalations::suggest_priority,
+            escalations::reescalate,
+            escalations::mark_bounced,
+            escalations::get_bounce_report,
+            escalations::get_field_history,
+            escalations::export_handoff_bundle,
+            escalations::save_clipboard_image_as_attachment,
+            escalations::capture_environment_info,
+            escalations::generate_print_view,
+            escalations::create_share_link,
+            escalations::draft_kb_article,
+            escalations::list_kb_drafts,
+            escalations::export_kb_draft,
+            escalations::export_anonymized_dataset,
+            escalations::check_expected_artifacts,
+            escalations::check_output_size,
             escalations::post_escalation,
             escalations::retry_post_escalation,
+            escalations::check_ticket_status,
+            escalations::dry_run_post,
+            escalations::retry_failed_attachments,
+            escalations::list_interrupted_jobs,
+            escalations::resume_interrupted_job,
+            escalations::dismiss_interrupted_job,
+            escalations::lock_escalation,
+            escalations::unlock_escalation,
+            escalations::force_unlock,
             tickets::fetch_jira_ticket,
             tickets::post_to_jira,
             tickets::attach_files_to_jira,
+            tickets::find_escalation_comments,
+            tickets::add_remote_link,
+            tickets::create_jira_ticket,
+            tickets::screen_attachments,
+            tickets::list_candidate_attachments,
+            tickets::list_escalation_queue,
+            tickets::check_attachment_quota,
+            tickets::list_my_open_tickets,
+            tickets::parse_ticket_reference,
+            tickets::search_jira_users,
             llm::summarize_with_llm,
+            llm::translate_problem_summary,
+            llm::get_llm_usage_report,
             settings::save_api_config,
             settings::get_api_config,
             settings::test_jira_connection,
+            settings::get_backend_capabilities,
+            settings::get_current_identity,
+            settings::get_preferences,
+            settings::save_preferences,
+            settings::get_ticket_key_presets,
+            settings::save_slack_signing_secret,
+            settings::save_template_pack_key,
+            maintenance::run_db_maintenance,
+            maintenance::check_data_integrity,
+            maintenance::clean_attachment_storage,
+            maintenance::get_startup_status,
+            maintenance::repair_or_reset_database,
+            drafts::load_draft_context,
+            activity::get_daily_summary,
+            acknowledgment::sync_acknowledgments,
+            acknowledgment::list_unacknowledged,
+            onboarding::get_onboarding_state,
+            onboarding::complete_onboarding_step,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application")
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, event| {
+            // Anything still posting or uploading gets resumed (or surfaced) on next launch
+            if let RunEvent::ExitRequested { .. } = event {
+                let interrupted = db::get_connection()
+                    .and_then(|conn| services::jobs::interrupt_instance(&conn, &services::locks::INSTANCE_ID));
+                match interrupted {
+                    Ok(0) => {}
+                    Ok(count) => log::warn!("Quitting with {} post/upload job(s) in flight", count),
+                    Err(e) => log::error!("Failed to record in-flight jobs on exit: {}", e),
+                }
+            }
+        });
+}
+
+/// Open the database and start everything that needs it
+fn initialize_database(app: &tauri::AppHandle, db_path: &str) {
+    if let Err(e) = db::init_db(db_path) {
+        log::error!("Database initialization failed: {}", e);
+        db::mark_degraded(Some(db_path), &format!("Database initialization failed: {}", e));
+        return;
+    }
+
+    start_database_services(app);
+}
+
+/// Background work that needs an open database; also started once a degraded boot is repaired
+pub(crate) fn start_database_services(app: &tauri::AppHandle) {
+    // Jobs a crashed instance left running will never finish; offer them for resumption
+    match db::get_connection().and_then(|conn| services::jobs::interrupt_stale(&conn)) {
+        Ok(0) => {}
+        Ok(count) => log::warn!("{} post/upload job(s) were interrupted by a previous exit", count),
+        Err(e) => log::error!("Failed to check for interrupted jobs: {}", e),
+    }
+
+    // Opt-in local API; a failure to bind shouldn't stop the app from starting
+    match db::get_preferences() {
+        Ok(preferences) if preferences.local_api_enabled => {
+            if let Err(e) = services::local_api::spawn(&preferences.local_api_bind) {
+                log::error!("{}", e);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => log::error!("Failed to read preferences: {}", e),
+    }
+
+    // Monthly VACUUM/ANALYZE/integrity check, off the main thread
+    std::thread::spawn(|| {
+        if let Err(e) = maintenance::run_scheduled_maintenance() {
+            log::error!("Scheduled database maintenance failed: {}", e);
+        }
+    });
+
+    // Opt-in prefetch of the agent's open tickets for the ticket picker
+    tickets::spawn_ticket_prefetch(app.clone());
+}
+
+fn database_path(app: &tauri::AppHandle) -> Result<String, String> {
+    let app_data_dir = app.path().app_data_dir()
+        .map_err(|e| format!("Cannot access app data directory: {}", e))?;
+
+    std::fs::create_dir_all(&app_data_dir)
+        .map_err(|e| format!("Cannot create app directory: {}. Check disk permissions.", e))?;
+
+    app_data_dir
+        .join("tickets.db")
+        .to_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Invalid database path with non-UTF8 characters".to_string())
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // === Templates ===
 
@@ -10,12 +11,84 @@ pub struct Template {
     pub category: String,
     pub checklist_items: Vec<ChecklistItem>,
     pub l2_team: Option<String>,
+    /// Custom handlebars body; the built-in layout is used when absent
+    #[serde(default)]
+    pub body: Option<String>,
+    /// Artifacts L2 expects attached, e.g. "Client log" or "Network trace"
+    #[serde(default)]
+    pub expected_artifacts: Vec<String>,
+    /// The agent's default or a pinned template; set when listing
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// Outcome of importing one template file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImportResult {
+    pub file_name: String,
+    pub template_name: Option<String>,
+    /// Set when the template was imported
+    pub template_id: Option<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateRenderResult {
+    pub markdown: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// How a proposed template change would alter the rendered output of drafts using it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateImpact {
+    pub template_id: i64,
+    pub drafts: Vec<DraftImpact>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftImpact {
+    pub escalation_id: i64,
+    pub ticket_id: String,
+    pub changed: bool,
+    /// Line diff from the current render to the proposed one; empty when unchanged
+    pub diff: Vec<DiffLine>,
+    /// Set when either version fails to render for this draft
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffOp {
+    Same,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChecklistItem {
     pub text: String,
     pub checked: bool,
+    /// Runbook or KB article the step follows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItemStat {
+    pub text: String,
+    /// False for wordings no longer in the template
+    pub in_template: bool,
+    /// Submitted escalations that included this item
+    pub times_used: i64,
+    pub times_checked: i64,
+    /// Share of uses left unchecked (0.0-1.0)
+    pub skip_rate: f64,
 }
 
 // === Escalations ===
@@ -36,6 +109,28 @@ pub struct Escalation {
     pub posted_at: Option<String>,
     pub created_at: String,
     pub updated_at: String,
+    pub attachments: Vec<Attachment>,
+    /// Internal observations; never rendered or posted to the ticket
+    pub private_notes: Option<String>,
+    pub ticket_snapshot: Option<TicketSnapshot>,
+    pub summary_language: Option<String>,
+    pub summary_translation: Option<String>,
+    /// Set when another app instance has this escalation open
+    pub locked_by: Option<String>,
+    pub locked_at: Option<String>,
+    /// The escalation this one re-escalates, when the issue came back after L2 closed it
+    pub parent_escalation_id: Option<i64>,
+    /// When and by whom L2 acknowledged the posted escalation
+    pub acknowledged_at: Option<String>,
+    pub acknowledged_by: Option<String>,
+    /// Built-in layout version the escalation was written with; re-renders use it
+    pub render_version: u32,
+    /// L2 teams the escalation is handed to, with what each is asked to do
+    pub l2_teams: Vec<TeamAsk>,
+    /// Machine details captured for L2, rendered in the Environment section
+    pub environment_info: Option<EnvironmentInfo>,
+    /// Major incident this handoff belongs to (PagerDuty, Statuspage or Jira incident ID)
+    pub incident_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +140,7 @@ pub enum EscalationStatus {
     Posted,
     PostedWithErrors,
     PostFailed,
+    Bounced,
 }
 
 impl EscalationStatus {
@@ -53,9 +149,20 @@ impl EscalationStatus {
             "posted" => EscalationStatus::Posted,
             "posted_with_errors" => EscalationStatus::PostedWithErrors,
             "post_failed" => EscalationStatus::PostFailed,
+            "bounced" => EscalationStatus::Bounced,
             _ => EscalationStatus::Draft,
         }
     }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EscalationStatus::Draft => "draft",
+            EscalationStatus::Posted => "posted",
+            EscalationStatus::PostedWithErrors => "posted_with_errors",
+            EscalationStatus::PostFailed => "post_failed",
+            EscalationStatus::Bounced => "bounced",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,6 +172,7 @@ pub struct EscalationSummary {
     pub problem_summary: String,
     pub status: EscalationStatus,
     pub created_at: String,
+    pub incident_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,6 +185,382 @@ pub struct EscalationInput {
     pub next_steps: String,
     pub llm_summary: Option<String>,
     pub llm_confidence: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<AttachmentInput>,
+    #[serde(default)]
+    pub private_notes: Option<String>,
+    #[serde(default)]
+    pub ticket_snapshot: Option<TicketSnapshot>,
+    /// ISO 639-1 code of the problem summary's language, when detected
+    #[serde(default)]
+    pub summary_language: Option<String>,
+    /// English translation of a non-English problem summary
+    #[serde(default)]
+    pub summary_translation: Option<String>,
+    #[serde(default)]
+    pub parent_escalation_id: Option<i64>,
+    /// Filled in from the parent escalation at render time
+    #[serde(default)]
+    pub previous_escalation: Option<PreviousEscalation>,
+    /// Built-in layout to render with; the current one when unset
+    #[serde(default)]
+    pub render_version: Option<u32>,
+    #[serde(default)]
+    pub l2_teams: Vec<TeamAsk>,
+    #[serde(default)]
+    pub environment_info: Option<EnvironmentInfo>,
+    #[serde(default)]
+    pub incident_id: Option<String>,
+}
+
+/// Troubleshooting-relevant details of the machine the escalation was written on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentInfo {
+    pub captured_at: String,
+    pub items: Vec<EnvironmentItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentItem {
+    pub label: String,
+    /// First line of the command's output; None when it failed
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// One change to a tracked escalation field; values are SHA-256 hashes, None when unset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_hash: Option<String>,
+    pub new_hash: Option<String>,
+    /// Display name of the Jira identity at the time, when known
+    pub actor: Option<String>,
+    pub changed_at: String,
+}
+
+/// One L2 team an escalation is handed to and what it's asked to do
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamAsk {
+    /// Name of a team configured in Settings
+    pub team: String,
+    pub asks: String,
+    /// Account ids to @mention; filled in from the team's settings at render time
+    #[serde(default)]
+    pub mentions: Vec<String>,
+}
+
+/// What the rendered "Previous escalation" section shows about the parent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviousEscalation {
+    pub id: i64,
+    pub ticket_id: String,
+    pub problem_summary: String,
+    pub current_status: String,
+    pub next_steps: String,
+    pub posted_at: Option<String>,
+}
+
+/// A posted escalation L2 hasn't acknowledged yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnacknowledgedEscalation {
+    pub id: i64,
+    pub ticket_id: String,
+    pub problem_summary: String,
+    pub posted_at: String,
+    pub hours_waiting: i64,
+}
+
+/// A file from a watch folder offered as an attachment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateAttachment {
+    pub file_path: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub modified_at: String,
+    /// The file or its folder is named after the ticket key
+    pub matches_ticket: bool,
+}
+
+/// Attachment sizes checked against Jira's limits before uploading
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentQuotaCheck {
+    pub upload_limit_bytes: u64,
+    pub existing_bytes: u64,
+    pub new_bytes: u64,
+    pub warnings: Vec<String>,
+    /// The warnings Jira would reject the upload for; posting refuses to start while there are any
+    pub rejections: Vec<String>,
+}
+
+/// How the Jira ticket looked when the escalation was created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketSnapshot {
+    pub summary: String,
+    pub status: String,
+    pub assignee: Option<String>,
+    /// Filled in on save when the caller doesn't provide it
+    #[serde(default)]
+    pub captured_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i64,
+    pub escalation_id: i64,
+    pub file_path: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    /// When the staged file was removed by the retention policy
+    pub purged_at: Option<String>,
+    /// Expected artifact (from the template) this file provides
+    pub artifact: Option<String>,
+    pub annotations: Option<ImageAnnotations>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentInput {
+    pub file_path: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub artifact: Option<String>,
+    #[serde(default)]
+    pub annotations: Option<ImageAnnotations>,
+}
+
+/// Annotations drawn on a screenshot in the frontend
+///
+/// Only redactions change the uploaded bytes; crop and arrows are kept so the
+/// editor can restore them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImageAnnotations {
+    #[serde(default)]
+    pub crop: Option<ImageRect>,
+    #[serde(default)]
+    pub arrows: Vec<ImageArrow>,
+    #[serde(default)]
+    pub redactions: Vec<ImageRect>,
+}
+
+/// Rectangle in image pixels, origin top-left
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImageRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImageArrow {
+    pub from_x: u32,
+    pub from_y: u32,
+    pub to_x: u32,
+    pub to_y: u32,
+}
+
+impl AttachmentInput {
+    pub fn file_name(&self) -> String {
+        std::path::Path::new(&self.file_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&self.file_path)
+            .to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    Spelling,
+    Terminology,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LintWarning {
+    /// Escalation field the text came from (e.g. "next_steps")
+    pub field: String,
+    pub kind: LintKind,
+    pub found: String,
+    pub suggestion: String,
+    /// Byte offset of `found` within the field
+    pub offset: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrioritySuggestion {
+    /// Jira priority name (Highest, High, Medium, Low)
+    pub priority: String,
+    pub reasons: Vec<String>,
+    /// "heuristic" or "llm"
+    pub source: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StartupStatus {
+    /// True while migrations and seeding run at launch; `db-ready` is emitted when they finish
+    pub initializing: bool,
+    /// True when the database failed to open; only settings and recovery work
+    pub degraded: bool,
+    pub error: Option<String>,
+    pub db_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseRecoveryReport {
+    /// "reopened", "salvaged" or "reset"
+    pub action: String,
+    /// Where the previous database file was moved, if it was
+    pub backup_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputSizeReport {
+    pub total_chars: usize,
+    pub total_words: usize,
+    pub max_chars: usize,
+    /// True when posting would trim the text and attach the full version
+    pub over_budget: bool,
+    pub sections: Vec<SectionSize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionSize {
+    pub name: String,
+    pub chars: usize,
+    pub words: usize,
+    pub limit: Option<usize>,
+    pub over_budget: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityScore {
+    pub score: u32,
+    pub factors: Vec<QualityFactor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QualityFactor {
+    pub name: String,
+    pub points: u32,
+    pub max_points: u32,
+    pub note: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BounceReport {
+    pub posted_count: i64,
+    pub bounced_count: i64,
+    pub bounce_rate: f64,
+    /// Average quality score (at bounce time) of escalations L2 rejected
+    pub avg_score_bounced: Option<f64>,
+    /// Average current quality score of posted escalations L2 accepted
+    pub avg_score_accepted: Option<f64>,
+    pub top_reasons: Vec<BounceReasonCount>,
+}
+
+/// LLM calls over a date range, grouped by provider, model, operation and agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsageReport {
+    /// Inclusive UTC dates, YYYY-MM-DD
+    pub from: String,
+    pub to: String,
+    pub total_calls: i64,
+    pub failed_calls: i64,
+    pub timed_out_calls: i64,
+    pub rows: Vec<LlmUsageRow>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmUsageRow {
+    pub provider: String,
+    pub model: String,
+    /// e.g. "summarize", "translate" or "embed"
+    pub operation: String,
+    /// Display name of the Jira identity that made the calls, when known
+    pub agent: Option<String>,
+    pub calls: i64,
+    /// Calls that failed, timeouts included
+    pub failures: i64,
+    pub timeouts: i64,
+    pub prompt_chars: i64,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BounceReasonCount {
+    pub reason: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub escalation_id: i64,
+    pub ticket_id: String,
+    pub status: EscalationStatus,
+    pub created_at: String,
+    pub exported_at: String,
+    pub files: Vec<BundleFile>,
+    pub missing_attachments: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    pub path: String,
+    pub size_bytes: u64,
+    pub sha256: String,
+}
+
+/// Contents of a template pack; the pack's signature covers this manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplatePackManifest {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub files: Vec<BundleFile>,
+}
+
+/// Escalations with personal details removed, for process research
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedDataset {
+    pub format_version: u32,
+    pub exported_at: String,
+    pub escalations: Vec<AnonymizedEscalation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedEscalation {
+    /// Position in this export; not the escalation's id
+    pub ref_id: usize,
+    /// `ref_id` of the escalation this one re-escalates
+    pub parent_ref: Option<usize>,
+    pub template_name: Option<String>,
+    pub status: EscalationStatus,
+    pub created_at: String,
+    pub minutes_to_post: Option<i64>,
+    pub minutes_to_acknowledge: Option<i64>,
+    pub checklist: Vec<AnonymizedChecklistItem>,
+    pub checklist_checked: usize,
+    /// Lengths of the agent's free text, which isn't exported
+    pub problem_summary_chars: usize,
+    pub current_status_chars: usize,
+    pub next_steps_chars: usize,
+    pub summary_language: Option<String>,
+    pub llm_confidence: Option<String>,
+    pub attachment_count: usize,
+    pub bounce_count: i64,
+    pub l2_teams: Vec<String>,
+    pub incident_linked: bool,
+    pub render_version: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnonymizedChecklistItem {
+    /// The template's wording; None for items the agent added or reworded
+    pub text: Option<String>,
+    pub checked: bool,
 }
 
 // === Jira ===
@@ -87,15 +571,66 @@ pub struct JiraTicket {
     pub summary: String,
     pub description: Option<String>,
     pub status: String,
+    /// Which of Jira's fixed groups the status belongs to, whatever the workflow calls it
+    #[serde(default)]
+    pub status_category: Option<StatusCategory>,
+    /// e.g. "Incident", "Service Request"
+    #[serde(default)]
+    pub issue_type: Option<String>,
     pub reporter: Option<JiraUser>,
     pub assignee: Option<JiraUser>,
     pub comments: Vec<JiraComment>,
+    #[serde(default)]
+    pub linked_issues: Vec<JiraLinkedIssue>,
+    #[serde(default)]
+    pub subtasks: Vec<JiraLinkedIssue>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// True when Jira refused some sections and only the basics were fetched
+    #[serde(default)]
+    pub partial: bool,
+    /// Sections left empty because Jira didn't return them, e.g. "comments"
+    #[serde(default)]
+    pub missing_sections: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatusCategory {
+    ToDo,
+    InProgress,
+    Done,
+}
+
+impl StatusCategory {
+    /// From the category key Jira sends with every status ("new", "indeterminate", "done")
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "new" => Some(StatusCategory::ToDo),
+            "indeterminate" => Some(StatusCategory::InProgress),
+            "done" => Some(StatusCategory::Done),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraLinkedIssue {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    /// Link phrased from this ticket's side, e.g. "is caused by" or "sub-task"
+    pub relation: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraUser {
     pub display_name: String,
     pub email: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,6 +640,190 @@ pub struct JiraComment {
     pub created: String,
 }
 
+/// A command whose output is captured as environment info, e.g. a VPN client's version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentCommand {
+    pub label: String,
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Local-time window (e.g. nightly maintenance) when posting needs an explicit confirmation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreezeWindow {
+    pub label: String,
+    /// "HH:MM"; a window whose end is before its start runs past midnight
+    pub start: String,
+    pub end: String,
+    /// Days the window starts on ("mon".."sun"); every day when empty
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+/// How escalations handed to an L2 team are routed in Jira
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct L2Team {
+    pub name: String,
+    /// Component added to the ticket
+    #[serde(default)]
+    pub component: Option<String>,
+    /// Account the ticket is assigned to; the first selected team with one wins
+    #[serde(default)]
+    pub assignee_account_id: Option<String>,
+    /// Accounts @mentioned in the team's section of the comment
+    #[serde(default)]
+    pub mention_account_ids: Vec<String>,
+}
+
+/// A Jira user as needed for @mentions and assignment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraAccount {
+    pub account_id: String,
+    pub display_name: String,
+    /// Only present when the user's privacy settings expose it
+    pub email: Option<String>,
+    pub active: bool,
+}
+
+/// The Jira account the configured credentials belong to, cached from /myself
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JiraIdentity {
+    pub account_id: String,
+    pub display_name: String,
+    pub email: Option<String>,
+    pub avatar_url: Option<String>,
+    /// Filled in when read back from the cache
+    pub fetched_at: Option<String>,
+}
+
+/// An issue from the prefetched "assigned to me" list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenTicket {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+    pub priority: Option<String>,
+    pub updated: Option<String>,
+}
+
+/// The cached ticket picker list and when it was last refreshed from Jira
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MyOpenTickets {
+    pub tickets: Vec<OpenTicket>,
+    /// None until the first successful fetch
+    pub fetched_at: Option<String>,
+}
+
+/// Tickets other tools have flagged as needing an L2 handoff, from the last queue poll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationQueue {
+    /// False when no queue JQL is configured
+    pub enabled: bool,
+    pub tickets: Vec<OpenTicket>,
+    /// None until the first successful fetch
+    pub fetched_at: Option<String>,
+}
+
+/// Which optional actions a ticket system backend supports, so the UI can hide them up front
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackendCapabilities {
+    pub backend: String,
+    pub attachments: bool,
+    pub internal_comments: bool,
+    pub transitions: bool,
+    pub watchers: bool,
+    pub remote_links: bool,
+    pub priority: bool,
+    /// Adding the L2 teams' components to the ticket
+    pub components: bool,
+    /// Assigning the ticket to an L2 team
+    pub assign: bool,
+}
+
+/// A post or upload that was still running when the app quit or crashed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterruptedJob {
+    pub id: i64,
+    /// "post", "retry_post" or "retry_attachments"
+    pub kind: String,
+    pub escalation_id: i64,
+    pub file_paths: Vec<String>,
+    pub started_at: String,
+    pub interrupted_at: Option<String>,
+}
+
+/// One day's activity, for pasting into shift handover notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailySummary {
+    /// Local date, YYYY-MM-DD
+    pub date: String,
+    /// Distinct ticket keys per activity, in the order they were first touched
+    pub tickets_fetched: Vec<String>,
+    pub drafts_created: Vec<String>,
+    pub escalations_posted: Vec<String>,
+    pub escalations_bounced: Vec<String>,
+    pub markdown: String,
+    /// LLM-written prose version, when requested and Ollama is available
+    pub narrative: Option<String>,
+}
+
+/// Reusable L2 instructions for the next steps field
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextStepBlock {
+    pub id: i64,
+    /// Letters, digits, '-' and '_'; referenced as `{{> name}}`
+    pub name: String,
+    pub body: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A next-steps block to create, or to update when `id` is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NextStepBlockInput {
+    #[serde(default)]
+    pub id: Option<i64>,
+    pub name: String,
+    pub body: String,
+}
+
+/// Knowledge-base article drafted from a resolved escalation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KbDraft {
+    pub id: i64,
+    pub escalation_id: i64,
+    pub title: String,
+    pub markdown: String,
+    pub created_at: String,
+}
+
+/// A ticket comment carrying a TicketHandoff correlation footer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationCommentRef {
+    pub comment_id: String,
+    pub escalation_id: i64,
+    pub author: String,
+    pub created: String,
+}
+
+// === Draft context ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimilarEscalation {
+    pub escalation: EscalationSummary,
+    /// Word-overlap score (0.0-1.0); 1.0 for escalations on the same ticket
+    pub score: f64,
+}
+
+/// Everything the draft screen needs, loaded in one invocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DraftContext {
+    pub ticket: JiraTicket,
+    pub templates: Vec<Template>,
+    pub similar_escalations: Vec<SimilarEscalation>,
+}
+
 // === LLM ===
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,6 +831,144 @@ pub struct LLMSummaryResult {
     pub summary: String,
     pub confidence: String,
     pub confidence_reason: String,
+    /// Steps the model listed that weren't on the checklist and were removed
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationResult {
+    /// ISO 639-1 code, None when the text is too short or ambiguous
+    pub language: Option<String>,
+    /// Only set for non-English text
+    pub translation: Option<String>,
+}
+
+// === Attachment screening ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningReport {
+    pub results: Vec<ScreeningResult>,
+    pub blocked_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningResult {
+    pub file_path: String,
+    pub allowed: bool,
+    pub issues: Vec<ScreeningIssue>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreeningIssue {
+    pub severity: ScreeningSeverity,
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreeningSeverity {
+    Warning,
+    Block,
+}
+
+// === Maintenance ===
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentCleanupReport {
+    pub dry_run: bool,
+    pub retention_days: u32,
+    /// Staged files removed (or that would be removed in a dry run)
+    pub file_count: usize,
+    pub reclaimable_bytes: u64,
+    /// Old attachments referencing files outside app storage, which are left alone
+    pub skipped_unstaged: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub integrity_ok: bool,
+    pub integrity_messages: Vec<String>,
+    pub size_before_bytes: i64,
+    pub size_after_bytes: i64,
+    pub duration_ms: u64,
+    pub ran_at: String,
+    /// Old escalations whose large text was moved to compressed storage this run
+    #[serde(default)]
+    pub compressed_escalations: usize,
+    /// Rows pointing at deleted escalations that were removed this run
+    #[serde(default)]
+    pub orphans_removed: i64,
+}
+
+/// Rows referring to escalations that no longer exist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataIntegrityReport {
+    pub orphans: Vec<OrphanCount>,
+    /// Re-escalations whose parent escalation was deleted
+    pub dangling_parents: i64,
+    pub total: i64,
+    /// True when the rows were removed rather than only counted
+    pub repaired: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanCount {
+    pub table: String,
+    pub count: i64,
+}
+
+// === Onboarding ===
+
+/// First-run setup steps, in the order they're done
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    JiraConfig,
+    TestConnection,
+    Ollama,
+    FirstTemplate,
+}
+
+impl OnboardingStep {
+    pub const ALL: [OnboardingStep; 4] = [
+        OnboardingStep::JiraConfig,
+        OnboardingStep::TestConnection,
+        OnboardingStep::Ollama,
+        OnboardingStep::FirstTemplate,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OnboardingStep::JiraConfig => "jira_config",
+            OnboardingStep::TestConnection => "test_connection",
+            OnboardingStep::Ollama => "ollama",
+            OnboardingStep::FirstTemplate => "first_template",
+        }
+    }
+
+    /// Ollama only powers the AI summary, so it can be skipped
+    pub fn is_required(&self) -> bool {
+        !matches!(self, OnboardingStep::Ollama)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingStepStatus {
+    pub step: OnboardingStep,
+    pub required: bool,
+    pub completed: bool,
+    pub skipped: bool,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnboardingState {
+    pub steps: Vec<OnboardingStepStatus>,
+    /// The first step neither completed nor skipped
+    pub current_step: Option<OnboardingStep>,
+    /// Every required step is done
+    pub posting_enabled: bool,
 }
 
 // === Settings ===
@@ -124,3 +981,192 @@ pub struct ApiConfig {
     pub ollama_endpoint: String,
     pub ollama_model: String,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketKeyPreset {
+    pub name: String,
+    pub pattern: String,
+}
+
+/// Executable and script types blocked from upload unless preferences say otherwise
+pub const DEFAULT_BLOCKED_EXTENSIONS: &[&str] = &[
+    "exe", "bat", "cmd", "com", "scr", "pif", "msi", "dll", "ps1", "vbs", "vbe", "js", "jse",
+    "wsf", "hta", "jar", "lnk", "reg", "app", "command",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// Footer text appended to posted comments as `<marker> #<escalation id>`.
+    /// An empty marker disables the footer.
+    pub correlation_marker: String,
+    /// Add dashboards/docs referenced in the escalation as Jira remote links when posting
+    pub auto_add_remote_links: bool,
+    /// Add the posting agent as a watcher so Jira notifies them about L2 activity
+    pub watch_posted_tickets: bool,
+    /// Refuse to post escalations scoring below this quality score (0-100)
+    pub min_quality_score: Option<u32>,
+    /// Extensions (without dot, lowercase) that may never be uploaded
+    pub blocked_extensions: Vec<String>,
+    /// Path to a local clamd socket; files are virus-scanned when set
+    pub clamav_socket: Option<String>,
+    /// Regex for valid ticket keys (Jira Cloud by default; see services::ticket_keys for presets)
+    pub ticket_key_pattern: String,
+    /// Ticket used by dry_run_post so template authors can check rendering safely
+    pub sandbox_ticket_key: Option<String>,
+    /// Staged attachment files are removed this many days after posting
+    pub attachment_retention_days: u32,
+    /// Ollama embedding model (e.g. nomic-embed-text) for similar-escalation lookup; word overlap when unset
+    pub embedding_model: Option<String>,
+    /// Serve the local HTTP API (metrics and integrations); applied on restart
+    pub local_api_enabled: bool,
+    /// Address the local API binds to; use 0.0.0.0:<port> to allow fleet scraping
+    pub local_api_bind: String,
+    /// Files larger than this (MB) are zipped and split into resumable parts; None (the default) uploads whole files
+    pub upload_chunk_size_mb: Option<u32>,
+    /// Offer an English translation of non-English problem summaries in the rendered output
+    pub bilingual_output: bool,
+    /// Canonical spellings of product names and terms; lint flags other casings
+    pub terminology: Vec<String>,
+    /// Pre-post ticket status check: off, warn or block
+    pub ticket_status_guard: TicketGuardMode,
+    /// Jira statuses treated as closed by the ticket status guard
+    pub closed_statuses: Vec<String>,
+    /// L2 assignees (display names or emails) in addition to the template's L2 team name
+    pub l2_assignees: Vec<String>,
+    /// Output flavor for previews and posted comments
+    pub output_format: OutputFormat,
+    /// Longest comment posted before the full text moves to an attachment (Jira allows 32,767)
+    pub max_comment_chars: usize,
+    /// Per-section character limits, keyed by section heading (e.g. "Problem Summary")
+    pub section_char_limits: HashMap<String, usize>,
+    /// Seconds to wait for each Ollama request; raise for large models on modest hardware
+    pub ollama_timeout_secs: u64,
+    /// Template categories to offer for tickets with a Jira component or label, keyed by its name
+    /// (e.g. "VPN" -> ["Network"]); names matching a category directly need no entry
+    pub template_category_map: HashMap<String, Vec<String>>,
+    /// Posted escalations older than this many days have their rendered output and AI summary compressed
+    pub cold_storage_after_days: u32,
+    /// Detail kept in audit entries: minimal, standard (masks emails, truncates long text) or full
+    pub audit_verbosity: AuditVerbosity,
+    /// Folders searched for candidate attachments (e.g. a diagnostics tool's output directory);
+    /// relative attachment paths also resolve against them
+    pub watch_folders: Vec<String>,
+    /// Files in watch folders modified within this many hours are offered even without the ticket key
+    pub watch_window_hours: u32,
+    /// Template preselected for new escalations; listed first
+    pub default_template_id: Option<i64>,
+    /// Templates listed right after the default, in this order
+    pub pinned_template_ids: Vec<i64>,
+    /// Minutes between background refreshes of the agent's open tickets; off when unset
+    /// (never more often than every 5 minutes)
+    pub ticket_prefetch_minutes: Option<u32>,
+    /// JQL for the prefetched ticket picker list
+    pub ticket_prefetch_jql: String,
+    /// Phrases in an L2 reply that acknowledge a posted escalation; a leading "^" only matches
+    /// at the start of the comment
+    pub ack_phrases: Vec<String>,
+    /// Ticket labels that acknowledge a posted escalation
+    pub ack_labels: Vec<String>,
+    /// Hours after posting before an unacknowledged escalation is listed as waiting
+    pub unacknowledged_after_hours: u32,
+    /// Days after posting that the acknowledgment sync keeps checking a ticket
+    pub ack_sync_days: u32,
+    /// Block every Jira change (posting, attaching, links, new tickets) while fetching and rendering
+    /// keep working; for practicing against production Jira
+    pub read_only_mode: bool,
+    /// Warn before uploading when a ticket's attachments would exceed this many MB in total (None disables)
+    pub ticket_attachment_budget_mb: Option<u32>,
+    /// L2 teams escalations can be handed to, with their Jira routing
+    pub l2_teams: Vec<L2Team>,
+    /// Retries re-render the comment from the escalation's current fields instead of reusing the stored output
+    pub rerender_on_retry: bool,
+    /// JQL for the shared queue of tickets flagged for escalation, e.g. "project = SUP AND labels = needs-escalation AND assignee IS EMPTY"; off when unset
+    pub escalation_queue_jql: Option<String>,
+    /// Minutes between background polls of the escalation queue (never more often than every 5 minutes)
+    pub escalation_queue_minutes: u32,
+    /// Commands run to capture environment info alongside the OS version, e.g. a VPN client's version
+    pub environment_commands: Vec<EnvironmentCommand>,
+    /// Change-freeze windows during which posting requires an explicit confirmation and is audited
+    pub posting_freeze_windows: Vec<FreezeWindow>,
+}
+
+/// Flavor of the rendered escalation; also decides how comments are posted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    Markdown,
+    /// Atlassian Document Format (Jira Cloud, REST v3)
+    Adf,
+    /// Wiki markup (Jira Server/Data Center, REST v2)
+    JiraWiki,
+    PlainText,
+}
+
+/// What to do when the ticket no longer needs an escalation (closed, or already with L2)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TicketGuardMode {
+    Off,
+    Warn,
+    Block,
+}
+
+/// How much detail audit entries keep; credentials are always removed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditVerbosity {
+    Minimal,
+    Standard,
+    Full,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            correlation_marker: "TicketHandoff escalation".to_string(),
+            auto_add_remote_links: false,
+            watch_posted_tickets: false,
+            min_quality_score: None,
+            blocked_extensions: DEFAULT_BLOCKED_EXTENSIONS.iter().map(|e| e.to_string()).collect(),
+            clamav_socket: None,
+            ticket_key_pattern: crate::services::ticket_keys::JIRA_CLOUD_PATTERN.to_string(),
+            sandbox_ticket_key: None,
+            attachment_retention_days: 90,
+            embedding_model: None,
+            local_api_enabled: false,
+            local_api_bind: "127.0.0.1:8787".to_string(),
+            upload_chunk_size_mb: None,
+            bilingual_output: false,
+            terminology: Vec::new(),
+            ticket_status_guard: TicketGuardMode::Warn,
+            closed_statuses: ["Closed", "Resolved", "Done"].iter().map(|s| s.to_string()).collect(),
+            l2_assignees: Vec::new(),
+            output_format: OutputFormat::Adf,
+            max_comment_chars: 32_000,
+            section_char_limits: HashMap::new(),
+            ollama_timeout_secs: 30,
+            template_category_map: HashMap::new(),
+            cold_storage_after_days: 365,
+            audit_verbosity: AuditVerbosity::Standard,
+            watch_folders: Vec::new(),
+            watch_window_hours: 24,
+            default_template_id: None,
+            pinned_template_ids: Vec::new(),
+            ticket_prefetch_minutes: None,
+            ticket_prefetch_jql: crate::services::ticket_cache::DEFAULT_JQL.to_string(),
+            ack_phrases: vec!["ack".to_string(), "acknowledged".to_string(), "taking this".to_string(), "^on it".to_string()],
+            ack_labels: Vec::new(),
+            unacknowledged_after_hours: 4,
+            ack_sync_days: 14,
+            read_only_mode: false,
+            ticket_attachment_budget_mb: Some(1024),
+            l2_teams: Vec::new(),
+            rerender_on_retry: false,
+            escalation_queue_jql: None,
+            escalation_queue_minutes: 10,
+            environment_commands: Vec::new(),
+            posting_freeze_windows: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+/// Tracks whether L2 picked up a posted escalation
+///
+/// The acknowledgment sync reads each posted, unacknowledged escalation's
+/// ticket: an L2 comment after posting that contains one of the configured
+/// phrases, or one of the configured labels on the ticket, marks the
+/// escalation acknowledged. Jira's REST API doesn't expose comment reactions,
+/// so replies and labels are the signals.
+use crate::error::AppResult;
+use crate::models::UnacknowledgedEscalation;
+use crate::services::correlation;
+use crate::services::jira::IssueComment;
+use chrono::{DateTime, FixedOffset, Utc};
+use rusqlite::{params, Connection};
+
+/// A posted escalation still waiting for L2
+pub struct Pending {
+    pub id: i64,
+    pub ticket_id: String,
+    pub posted_at: String,
+}
+
+/// What counts as an acknowledgment
+pub struct AckRules<'a> {
+    /// Phrases in an L2 reply, matched case-insensitively on word boundaries; a leading "^"
+    /// anchors the phrase to the start of the reply, for phrases like "on it" that are common mid-sentence
+    pub phrases: &'a [String],
+    /// Ticket labels L2 sets on pickup
+    pub labels: &'a [String],
+    /// Correlation marker; comments carrying it are escalations themselves
+    pub marker: &'a str,
+    /// The agent's own display name, whose comments don't count
+    pub own_name: Option<&'a str>,
+}
+
+/// Who acknowledged and when
+#[derive(Debug, PartialEq)]
+pub struct Acknowledgment {
+    pub by: String,
+    pub at: String,
+}
+
+/// Unacknowledged escalations posted within the last `within_days`; older ones are left alone
+/// so the sync doesn't keep fetching tickets L2 has long since dealt with
+pub fn pending(conn: &Connection, within_days: u32, now: DateTime<Utc>) -> AppResult<Vec<Pending>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, ticket_id, posted_at FROM escalations
+        WHERE status IN ('posted', 'posted_with_errors') AND posted_at IS NOT NULL AND acknowledged_at IS NULL
+        ORDER BY posted_at",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(Pending {
+                id: row.get(0)?,
+                ticket_id: row.get(1)?,
+                posted_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows
+        .into_iter()
+        .filter(|pending| {
+            parse_time(&pending.posted_at)
+                .is_some_and(|posted| now.signed_duration_since(posted).num_days() < i64::from(within_days))
+        })
+        .collect())
+}
+
+/// The first acknowledging comment after posting, or a matching label
+///
+/// A label has no author or time, so it is credited to the label and stamped with `now`.
+pub fn detect(
+    comments: &[IssueComment],
+    labels: &[String],
+    posted_at: &str,
+    rules: &AckRules,
+    now: DateTime<Utc>,
+) -> Option<Acknowledgment> {
+    let posted = parse_time(posted_at);
+
+    let reply = comments.iter().find(|comment| {
+        let after_post = match (posted, parse_time(&comment.created)) {
+            (Some(posted), Some(created)) => created > posted,
+            _ => false,
+        };
+        after_post
+            && correlation::extract_escalation_id(&comment.body_text, rules.marker).is_none()
+            && rules.own_name != Some(comment.author.as_str())
+            && rules.phrases.iter().any(|phrase| contains_phrase(&comment.body_text, phrase))
+    });
+    if let Some(comment) = reply {
+        return Some(Acknowledgment {
+            by: comment.author.clone(),
+            at: comment.created.clone(),
+        });
+    }
+
+    labels
+        .iter()
+        .find(|label| rules.labels.iter().any(|ack| ack.eq_ignore_ascii_case(label)))
+        .map(|label| Acknowledgment {
+            by: format!("label {}", label),
+            at: now.to_rfc3339(),
+        })
+}
+
+pub fn mark(conn: &Connection, escalation_id: i64, acknowledgment: &Acknowledgment) -> AppResult<()> {
+    conn.execute(
+        "UPDATE escalations SET acknowledged_at = ?, acknowledged_by = ?, updated_at = datetime('now')
+        WHERE id = ?",
+        params![acknowledgment.at, acknowledgment.by, escalation_id],
+    )?;
+    Ok(())
+}
+
+/// Posted escalations nobody has acknowledged for at least `older_than_hours`, longest waiting first
+pub fn unacknowledged(conn: &Connection, older_than_hours: u32, now: DateTime<Utc>) -> AppResult<Vec<UnacknowledgedEscalation>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, ticket_id, problem_summary, posted_at FROM escalations
+        WHERE status IN ('posted', 'posted_with_errors') AND posted_at IS NOT NULL AND acknowledged_at IS NULL",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut waiting: Vec<UnacknowledgedEscalation> = rows
+        .into_iter()
+        .filter_map(|(id, ticket_id, problem_summary, posted_at)| {
+            let hours = now.signed_duration_since(parse_time(&posted_at)?).num_hours();
+            (hours >= i64::from(older_than_hours)).then_some(UnacknowledgedEscalation {
+                id,
+                ticket_id,
+                problem_summary,
+                posted_at,
+                hours_waiting: hours,
+            })
+        })
+        .collect();
+    waiting.sort_by_key(|w| std::cmp::Reverse(w.hours_waiting));
+    Ok(waiting)
+}
+
+/// Our RFC 3339 timestamps, or Jira's `2024-01-15T10:30:00.000+0000`
+fn parse_time(value: &str) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+}
+
+/// Case-insensitive match on word boundaries, so "ack" doesn't match "back"; a leading "^"
+/// only matches at the start of the text
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    let phrase = phrase.trim().to_lowercase();
+    let (phrase, anchored) = match phrase.strip_prefix('^') {
+        Some(rest) => (rest.trim_start(), true),
+        None => (phrase.as_str(), false),
+    };
+    if phrase.is_empty() {
+        return false;
+    }
+
+    let text = text.trim_start().to_lowercase();
+    text.match_indices(phrase).any(|(start, _)| {
+        if anchored && start > 0 {
+            return false;
+        }
+        let end = start + phrase.len();
+        let before = text[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let after = text[end..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        before && after
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(author: &str, body: &str, created: &str) -> IssueComment {
+        IssueComment {
+            id: "1".to_string(),
+            author: author.to_string(),
+            body_text: body.to_string(),
+            created: created.to_string(),
+        }
+    }
+
+    fn phrases() -> Vec<String> {
+        vec!["ack".to_string(), "taking this".to_string()]
+    }
+
+    fn rules<'a>(phrases: &'a [String], labels: &'a [String], own_name: Option<&'a str>) -> AckRules<'a> {
+        AckRules { phrases, labels, marker: MARKER, own_name }
+    }
+
+    const POSTED: &str = "2024-01-15T10:00:00+00:00";
+    const MARKER: &str = "TicketHandoff escalation";
+
+    #[test]
+    fn test_detect_reply_after_posting() {
+        let comments = vec![
+            comment("Dana L2", "ack, looks like the earlier outage", "2024-01-15T09:00:00.000+0000"),
+            comment("Agent", "ack: escalation details\n\nTicketHandoff escalation #7", "2024-01-15T10:00:00.000+0000"),
+            comment("Dana L2", "Sending it back to you", "2024-01-15T10:30:00.000+0000"),
+            comment("Dana L2", "Taking this one.", "2024-01-15T11:00:00.000+0000"),
+        ];
+
+        let ack = detect(&comments, &[], POSTED, &rules(&phrases(), &[], Some("Agent")), Utc::now()).unwrap();
+        assert_eq!(ack.by, "Dana L2");
+        assert_eq!(ack.at, "2024-01-15T11:00:00.000+0000");
+    }
+
+    #[test]
+    fn test_own_comments_and_no_match() {
+        let comments = vec![comment("Agent", "ack", "2024-01-15T11:00:00.000+0000")];
+        assert!(detect(&comments, &[], POSTED, &rules(&phrases(), &[], Some("Agent")), Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_detect_label() {
+        let labels = vec!["vpn".to_string(), "L2-ACK".to_string()];
+        let ack_labels = vec!["l2-ack".to_string()];
+        let ack = detect(&[], &labels, POSTED, &rules(&phrases(), &ack_labels, None), Utc::now()).unwrap();
+        assert_eq!(ack.by, "label L2-ACK");
+    }
+
+    #[test]
+    fn test_unacknowledged_waiting_time() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/025_acknowledgments.sql")).unwrap();
+        for (ticket, posted_at, acknowledged_at) in [
+            ("IT-1", "2024-01-15T00:00:00+00:00", None),
+            ("IT-2", "2024-01-15T10:00:00+00:00", None),
+            ("IT-3", "2024-01-14T00:00:00+00:00", Some("2024-01-14T01:00:00+00:00")),
+        ] {
+            conn.execute(
+                "INSERT INTO escalations (ticket_id, problem_summary, checklist, current_status, next_steps, status, posted_at, acknowledged_at)
+                VALUES (?, 'VPN drops', '[]', '', '', 'posted', ?, ?)",
+                params![ticket, posted_at, acknowledged_at],
+            )
+            .unwrap();
+        }
+
+        let now = DateTime::parse_from_rfc3339("2024-01-15T12:00:00+00:00").unwrap().with_timezone(&Utc);
+        let waiting = unacknowledged(&conn, 4, now).unwrap();
+        assert_eq!(waiting.len(), 1);
+        assert_eq!(waiting[0].ticket_id, "IT-1");
+        assert_eq!(waiting[0].hours_waiting, 12);
+        assert_eq!(pending(&conn, 14, now).unwrap().len(), 2);
+        assert!(pending(&conn, 14, now + chrono::Duration::days(14)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_anchored_phrase_only_at_start() {
+        let phrases = vec!["^on it".to_string()];
+        let rules = rules(&phrases, &[], None);
+        let at = "2024-01-15T11:00:00.000+0000";
+
+        for body in ["On it", "  on it, checking the VPN logs now"] {
+            assert!(detect(&[comment("Dana L2", body, at)], &[], POSTED, &rules, Utc::now()).is_some(), "{}", body);
+        }
+        for body in ["The agent was not on it yet", "Online it says"] {
+            assert!(detect(&[comment("Dana L2", body, at)], &[], POSTED, &rules, Utc::now()).is_none(), "{}", body);
+        }
+    }
+}
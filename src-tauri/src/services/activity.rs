@@ -0,0 +1,123 @@
+/// Per-day record of what the agent did, summarized for shift handover
+///
+/// Days are local dates so a late shift isn't split at UTC midnight.
+use crate::error::AppResult;
+use crate::models::DailySummary;
+use rusqlite::{params, Connection};
+
+pub const KIND_TICKET_FETCHED: &str = "ticket_fetched";
+pub const KIND_DRAFT_CREATED: &str = "draft_created";
+pub const KIND_ESCALATION_POSTED: &str = "escalation_posted";
+pub const KIND_ESCALATION_BOUNCED: &str = "escalation_bounced";
+
+pub fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+pub fn record(conn: &Connection, kind: &str, ticket_key: Option<&str>, escalation_id: Option<i64>) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO activity_log (day, kind, ticket_key, escalation_id) VALUES (?, ?, ?, ?)",
+        params![today(), kind, ticket_key, escalation_id],
+    )?;
+    Ok(())
+}
+
+/// Record an activity on an escalation, taking the ticket key from the escalation
+pub fn record_for_escalation(conn: &Connection, kind: &str, escalation_id: i64) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO activity_log (day, kind, ticket_key, escalation_id)
+        VALUES (?1, ?2, (SELECT ticket_id FROM escalations WHERE id = ?3), ?3)",
+        params![today(), kind, escalation_id],
+    )?;
+    Ok(())
+}
+
+pub fn daily_summary(conn: &Connection, date: &str) -> AppResult<DailySummary> {
+    let mut stmt = conn.prepare(
+        "SELECT kind, ticket_key FROM activity_log
+        WHERE day = ? AND ticket_key IS NOT NULL ORDER BY id",
+    )?;
+    let rows = stmt
+        .query_map([date], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let keys = |kind: &str| {
+        let mut keys: Vec<String> = Vec::new();
+        for (_, key) in rows.iter().filter(|(k, _)| k == kind) {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+        keys
+    };
+
+    let mut summary = DailySummary {
+        date: date.to_string(),
+        tickets_fetched: keys(KIND_TICKET_FETCHED),
+        drafts_created: keys(KIND_DRAFT_CREATED),
+        escalations_posted: keys(KIND_ESCALATION_POSTED),
+        escalations_bounced: keys(KIND_ESCALATION_BOUNCED),
+        markdown: String::new(),
+        narrative: None,
+    };
+    summary.markdown = render_markdown(&summary);
+    Ok(summary)
+}
+
+fn render_markdown(summary: &DailySummary) -> String {
+    let mut out = format!("### My day: {}\n", summary.date);
+    for (label, keys) in [
+        ("Tickets reviewed", &summary.tickets_fetched),
+        ("Escalation drafts started", &summary.drafts_created),
+        ("Escalations posted to L2", &summary.escalations_posted),
+        ("Escalations bounced back", &summary.escalations_bounced),
+    ] {
+        if !keys.is_empty() {
+            out.push_str(&format!("- {}: {} ({})\n", label, keys.len(), keys.join(", ")));
+        }
+    }
+    if out.lines().count() == 1 {
+        out.push_str("- No recorded activity\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/022_activity_log.sql")).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_daily_summary_dedupes_tickets() {
+        let conn = setup();
+        record(&conn, KIND_TICKET_FETCHED, Some("IT-1"), None).unwrap();
+        record(&conn, KIND_TICKET_FETCHED, Some("IT-2"), None).unwrap();
+        record(&conn, KIND_TICKET_FETCHED, Some("IT-1"), None).unwrap();
+        record(&conn, KIND_DRAFT_CREATED, Some("IT-1"), Some(5)).unwrap();
+        record(&conn, KIND_ESCALATION_POSTED, Some("IT-1"), Some(5)).unwrap();
+        conn.execute(
+            "INSERT INTO activity_log (day, kind, ticket_key) VALUES ('2020-01-01', ?, 'OLD-1')",
+            [KIND_TICKET_FETCHED],
+        )
+        .unwrap();
+
+        let summary = daily_summary(&conn, &today()).unwrap();
+        assert_eq!(summary.tickets_fetched, vec!["IT-1", "IT-2"]);
+        assert_eq!(summary.drafts_created, vec!["IT-1"]);
+        assert!(summary.escalations_bounced.is_empty());
+        assert!(summary.markdown.contains("- Tickets reviewed: 2 (IT-1, IT-2)\n"));
+        assert!(summary.markdown.contains("- Escalations posted to L2: 1 (IT-1)\n"));
+        assert!(!summary.markdown.contains("bounced"));
+    }
+
+    #[test]
+    fn test_empty_day() {
+        let summary = daily_summary(&setup(), "2020-01-02").unwrap();
+        assert_eq!(summary.markdown, "### My day: 2020-01-02\n- No recorded activity\n");
+    }
+}
@@ -7,13 +7,29 @@
 /// - Code blocks (```)
 /// - Bullet lists (-)
 /// - Numbered lists (1.)
+/// - Task lists (- [x]) as ADF taskList/taskItem nodes
+/// - Links ([text](url))
+/// - The AI Summary's "(Confidence: High|Medium|Low)" line as a status lozenge
+/// - `[~accountid:ID]` as a user mention
+use once_cell::sync::Lazy;
 use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use regex::Regex;
 use serde_json::{json, Value};
 
+static CONFIDENCE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\(Confidence: (High|Medium|Low)\)$").expect("valid confidence regex")
+});
+
+const AI_SUMMARY_HEADING: &str = "AI Summary";
+
+static MENTION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[~accountid:([^\]\s]+)\]").expect("valid mention regex"));
+
 pub fn markdown_to_adf(markdown: &str) -> Value {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
 
     let parser = Parser::new_ext(markdown, options);
     let mut content: Vec<Value> = Vec::new();
@@ -23,15 +39,22 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
     let mut list_items: Vec<Value> = Vec::new();
     let mut in_list = false;
     let mut list_type = String::new();
+    // State of the current item's task marker, and how many items in the list had one
+    let mut task_state: Option<bool> = None;
+    let mut task_items = 0;
+    let mut local_ids = 0;
+    // Only the AI Summary section carries a confidence line
+    let mut in_ai_summary = false;
 
     for event in parser {
         match event {
             Event::Start(Tag::Heading { .. }) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 flush_paragraph(&mut current_paragraph, &mut content);
             }
             Event::End(TagEnd::Heading(level)) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                in_ai_summary = current_text.trim() == AI_SUMMARY_HEADING;
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, false);
                 if !current_paragraph.is_empty() {
                     content.push(json!({
                         "type": "heading",
@@ -44,23 +67,23 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
                 }
             }
             Event::Start(Tag::Strong) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 current_marks.push(json!({"type": "strong"}));
             }
             Event::End(TagEnd::Strong) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 current_marks.retain(|m| m["type"] != "strong");
             }
             Event::Start(Tag::Emphasis) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 current_marks.push(json!({"type": "em"}));
             }
             Event::End(TagEnd::Emphasis) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 current_marks.retain(|m| m["type"] != "em");
             }
             Event::Start(Tag::CodeBlock(_)) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 flush_paragraph(&mut current_paragraph, &mut content);
             }
             Event::End(TagEnd::CodeBlock) => {
@@ -76,33 +99,61 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
                 }
             }
             Event::Start(Tag::List(None)) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 flush_paragraph(&mut current_paragraph, &mut content);
                 in_list = true;
                 list_type = "bulletList".to_string();
             }
             Event::Start(Tag::List(Some(_))) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 flush_paragraph(&mut current_paragraph, &mut content);
                 in_list = true;
                 list_type = "orderedList".to_string();
             }
             Event::End(TagEnd::List(_)) => {
                 if !list_items.is_empty() {
-                    content.push(json!({
-                        "type": list_type.clone(),
-                        "content": list_items.clone()
-                    }));
+                    if task_items == list_items.len() {
+                        local_ids += 1;
+                        content.push(json!({
+                            "type": "taskList",
+                            "attrs": { "localId": format!("tasklist-{}", local_ids) },
+                            "content": list_items.clone()
+                        }));
+                    } else {
+                        // Mixed list: fall back to plain list items
+                        let items: Vec<Value> = list_items.iter().map(task_item_to_list_item).collect();
+                        content.push(json!({
+                            "type": list_type.clone(),
+                            "content": items
+                        }));
+                    }
                     list_items.clear();
                 }
+                task_items = 0;
                 in_list = false;
             }
             Event::Start(Tag::Item) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
+                task_state = None;
+            }
+            Event::TaskListMarker(checked) => {
+                task_state = Some(checked);
             }
             Event::End(TagEnd::Item) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
-                if !current_paragraph.is_empty() {
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
+                if let Some(checked) = task_state.take() {
+                    local_ids += 1;
+                    task_items += 1;
+                    list_items.push(json!({
+                        "type": "taskItem",
+                        "attrs": {
+                            "localId": format!("task-{}", local_ids),
+                            "state": if checked { "DONE" } else { "TODO" }
+                        },
+                        "content": current_paragraph.clone()
+                    }));
+                    current_paragraph.clear();
+                } else if !current_paragraph.is_empty() {
                     list_items.push(json!({
                         "type": "listItem",
                         "content": [{
@@ -113,11 +164,19 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
                     current_paragraph.clear();
                 }
             }
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
+                current_marks.push(json!({"type": "link", "attrs": {"href": dest_url.to_string()}}));
+            }
+            Event::End(TagEnd::Link) => {
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
+                current_marks.retain(|m| m["type"] != "link");
+            }
             Event::Text(text) => {
                 current_text.push_str(&text);
             }
             Event::Code(code) => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 current_paragraph.push(json!({
                     "type": "text",
                     "text": code.to_string(),
@@ -128,7 +187,7 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
                 current_text.push('\n');
             }
             Event::End(TagEnd::Paragraph) if !in_list => {
-                flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+                flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
                 flush_paragraph(&mut current_paragraph, &mut content);
             }
             _ => {}
@@ -136,7 +195,7 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
     }
 
     // Flush any remaining content
-    flush_text(&mut current_text, &mut current_paragraph, &current_marks);
+    flush_text(&mut current_text, &mut current_paragraph, &current_marks, in_ai_summary);
     flush_paragraph(&mut current_paragraph, &mut content);
 
     // Ensure at least one paragraph exists
@@ -154,14 +213,52 @@ pub fn markdown_to_adf(markdown: &str) -> Value {
     })
 }
 
-fn flush_text(text: &mut String, paragraph: &mut Vec<Value>, marks: &[Value]) {
+fn flush_text(text: &mut String, paragraph: &mut Vec<Value>, marks: &[Value], lozenge: bool) {
+    if text.is_empty() {
+        return;
+    }
+
+    // Split out mentions so they notify the people named
+    let mut last = 0;
+    for caps in MENTION_RE.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        let account_id = caps.get(1).expect("account id group");
+
+        push_lozenged_text(&text[last..whole.start()], paragraph, marks, lozenge);
+        paragraph.push(json!({ "type": "mention", "attrs": { "id": account_id.as_str() } }));
+        last = whole.end();
+    }
+    push_lozenged_text(&text[last..], paragraph, marks, lozenge);
+
+    text.clear();
+}
+
+fn push_lozenged_text(text: &str, paragraph: &mut Vec<Value>, marks: &[Value], lozenge: bool) {
+    if !lozenge {
+        push_text(text, paragraph, marks);
+        return;
+    }
+
+    // Split out the confidence level so it renders as a status lozenge
+    let mut last = 0;
+    for caps in CONFIDENCE_RE.captures_iter(text) {
+        let level = caps.get(1).expect("level group");
+
+        push_text(&text[last..level.start()], paragraph, marks);
+        paragraph.push(status_node(level.as_str()));
+        last = level.end();
+    }
+    push_text(&text[last..], paragraph, marks);
+}
+
+fn push_text(text: &str, paragraph: &mut Vec<Value>, marks: &[Value]) {
     if text.is_empty() {
         return;
     }
 
     let mut node = json!({
         "type": "text",
-        "text": text.clone()
+        "text": text
     });
 
     if !marks.is_empty() {
@@ -169,7 +266,43 @@ fn flush_text(text: &mut String, paragraph: &mut Vec<Value>, marks: &[Value]) {
     }
 
     paragraph.push(node);
-    text.clear();
+}
+
+fn status_node(level: &str) -> Value {
+    let color = match level {
+        "High" => "green",
+        "Medium" => "yellow",
+        _ => "red",
+    };
+
+    json!({
+        "type": "status",
+        "attrs": {
+            "text": level.to_uppercase(),
+            "color": color
+        }
+    })
+}
+
+/// Convert a taskItem back into a plain listItem (for lists mixing tasks and bullets)
+fn task_item_to_list_item(item: &Value) -> Value {
+    if item["type"] != "taskItem" {
+        return item.clone();
+    }
+
+    let prefix = if item["attrs"]["state"] == "DONE" { "[x] " } else { "[ ] " };
+    let mut inline = vec![json!({"type": "text", "text": prefix})];
+    if let Some(children) = item["content"].as_array() {
+        inline.extend(children.iter().cloned());
+    }
+
+    json!({
+        "type": "listItem",
+        "content": [{
+            "type": "paragraph",
+            "content": inline
+        }]
+    })
 }
 
 fn flush_paragraph(paragraph: &mut Vec<Value>, content: &mut Vec<Value>) {
@@ -184,6 +317,44 @@ fn flush_paragraph(paragraph: &mut Vec<Value>, content: &mut Vec<Value>) {
     paragraph.clear();
 }
 
+/// Flatten an ADF document back to plain text (one line per block node)
+pub fn adf_to_plain_text(node: &Value) -> String {
+    let mut out = String::new();
+    collect_text(node, &mut out);
+    out.trim_end().to_string()
+}
+
+fn collect_text(node: &Value, out: &mut String) {
+    match node["type"].as_str() {
+        Some("text") => {
+            if let Some(text) = node["text"].as_str() {
+                out.push_str(text);
+            }
+        }
+        Some("hardBreak") => out.push('\n'),
+        Some("status") => {
+            if let Some(text) = node["attrs"]["text"].as_str() {
+                out.push_str(text);
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(children) = node["content"].as_array() {
+        for child in children {
+            collect_text(child, out);
+        }
+    }
+
+    if matches!(
+        node["type"].as_str(),
+        Some("paragraph") | Some("heading") | Some("codeBlock") | Some("taskItem")
+    ) && !out.ends_with('\n')
+    {
+        out.push('\n');
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -246,4 +417,75 @@ mod tests {
         assert_eq!(content[2]["marks"][0]["type"], "em");
         assert_eq!(content[4]["marks"][0]["type"], "code");
     }
+
+    #[test]
+    fn test_adf_to_plain_text_roundtrip() {
+        let adf = markdown_to_adf("## Title\n\nFirst line\n\n*TicketHandoff escalation #3*");
+        let text = adf_to_plain_text(&adf);
+
+        assert_eq!(text, "Title\nFirst line\nTicketHandoff escalation #3");
+    }
+
+    #[test]
+    fn test_task_list() {
+        let md = "- [x] Restarted VPN client\n- [ ] Verified DNS settings";
+        let adf = markdown_to_adf(md);
+
+        let list = &adf["content"][0];
+        assert_eq!(list["type"], "taskList");
+        assert_eq!(list["content"][0]["type"], "taskItem");
+        assert_eq!(list["content"][0]["attrs"]["state"], "DONE");
+        assert_eq!(list["content"][0]["content"][0]["text"], "Restarted VPN client");
+        assert_eq!(list["content"][1]["attrs"]["state"], "TODO");
+        assert_ne!(list["content"][0]["attrs"]["localId"], list["content"][1]["attrs"]["localId"]);
+    }
+
+    #[test]
+    fn test_task_item_link() {
+        let md = "- [x] [Reset stack](https://kb.example.com/reset)";
+        let adf = markdown_to_adf(md);
+
+        let text = &adf["content"][0]["content"][0]["content"][0];
+        assert_eq!(text["text"], "Reset stack");
+        assert_eq!(text["marks"][0]["type"], "link");
+        assert_eq!(text["marks"][0]["attrs"]["href"], "https://kb.example.com/reset");
+    }
+
+    #[test]
+    fn test_confidence_lozenge() {
+        let md = "### AI Summary\nSummary text\n(Confidence: High)";
+        let adf = markdown_to_adf(md);
+
+        let content = &adf["content"][1]["content"];
+        assert_eq!(content[0]["text"], "Summary text\n(Confidence: ");
+        assert_eq!(content[1]["type"], "status");
+        assert_eq!(content[1]["attrs"]["text"], "HIGH");
+        assert_eq!(content[1]["attrs"]["color"], "green");
+        assert_eq!(content[2]["text"], ")");
+    }
+
+    #[test]
+    fn test_confidence_outside_ai_summary_is_text() {
+        let md = "### Problem\nUser said Confidence: Low\n(Confidence: Low)\n\n### AI Summary\nSummary mentions Confidence: Low\n(Confidence: Medium)";
+        let adf = markdown_to_adf(md);
+
+        let problem = &adf["content"][1]["content"];
+        assert_eq!(problem.as_array().unwrap().len(), 1);
+        assert_eq!(problem[0]["type"], "text");
+
+        let summary = &adf["content"][3]["content"];
+        assert_eq!(summary[0]["text"], "Summary mentions Confidence: Low\n(Confidence: ");
+        assert_eq!(summary[1]["attrs"]["text"], "MEDIUM");
+        assert_eq!(summary[2]["text"], ")");
+    }
+
+    #[test]
+    fn test_mention() {
+        let adf = markdown_to_adf("[~accountid:5b10a2844c20165700ede21g] please check the tunnel");
+
+        let content = &adf["content"][0]["content"];
+        assert_eq!(content[0]["type"], "mention");
+        assert_eq!(content[0]["attrs"]["id"], "5b10a2844c20165700ede21g");
+        assert_eq!(content[1]["text"], " please check the tunnel");
+    }
 }
@@ -0,0 +1,186 @@
+/// Escalations exported for process research without customer or agent details
+///
+/// Structure is kept as-is: checklist completion, template, status, timings,
+/// bounces, teams. The agent's own free text (problem summary, status, next
+/// steps, added checklist items) can name customers the app has no record of,
+/// so only its length is exported; checklist items worded as in the template
+/// keep their text, through `redaction::TextRedactor`. Ticket keys, private
+/// notes, LLM summaries, rendered output, ticket snapshots, environment
+/// details, attachment names and incident IDs are left out, and escalations are
+/// numbered by their position in the export instead of their id.
+use crate::models::{AnonymizedChecklistItem, AnonymizedDataset, AnonymizedEscalation, Escalation, Template};
+use crate::services::redaction::TextRedactor;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::HashMap;
+
+pub const DATASET_FORMAT_VERSION: u32 = 2;
+
+/// Escalations in export order, with per-escalation bounce and attachment counts and templates by id
+pub fn build(
+    escalations: &[Escalation],
+    bounce_counts: &HashMap<i64, i64>,
+    attachment_counts: &HashMap<i64, usize>,
+    templates: &HashMap<i64, Template>,
+    redactor: &TextRedactor,
+) -> AnonymizedDataset {
+    let refs: HashMap<i64, usize> = escalations.iter().enumerate().map(|(i, e)| (e.id, i + 1)).collect();
+
+    let escalations = escalations
+        .iter()
+        .enumerate()
+        .map(|(i, escalation)| {
+            let template = escalation.template_id.and_then(|id| templates.get(&id));
+            AnonymizedEscalation {
+                ref_id: i + 1,
+                parent_ref: escalation.parent_escalation_id.and_then(|id| refs.get(&id).copied()),
+                template_name: template.map(|t| t.name.clone()),
+                status: escalation.status.clone(),
+                created_at: escalation.created_at.clone(),
+                minutes_to_post: escalation
+                    .posted_at
+                    .as_deref()
+                    .and_then(|posted| minutes_between(&escalation.created_at, posted)),
+                minutes_to_acknowledge: escalation
+                    .posted_at
+                    .as_deref()
+                    .zip(escalation.acknowledged_at.as_deref())
+                    .and_then(|(posted, acknowledged)| minutes_between(posted, acknowledged)),
+                checklist: escalation
+                    .checklist
+                    .iter()
+                    .map(|item| AnonymizedChecklistItem {
+                        text: template
+                            .filter(|t| t.checklist_items.iter().any(|from| from.text.trim() == item.text.trim()))
+                            .map(|_| redactor.redact(&item.text)),
+                        checked: item.checked,
+                    })
+                    .collect(),
+                checklist_checked: escalation.checklist.iter().filter(|item| item.checked).count(),
+                problem_summary_chars: escalation.problem_summary.trim().chars().count(),
+                current_status_chars: escalation.current_status.trim().chars().count(),
+                next_steps_chars: escalation.next_steps.trim().chars().count(),
+                summary_language: escalation.summary_language.clone(),
+                llm_confidence: escalation.llm_confidence.clone(),
+                attachment_count: attachment_counts.get(&escalation.id).copied().unwrap_or(0),
+                bounce_count: bounce_counts.get(&escalation.id).copied().unwrap_or(0),
+                l2_teams: escalation.l2_teams.iter().map(|ask| ask.team.clone()).collect(),
+                incident_linked: escalation.incident_id.is_some(),
+                render_version: escalation.render_version,
+            }
+        })
+        .collect();
+
+    AnonymizedDataset {
+        format_version: DATASET_FORMAT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        escalations,
+    }
+}
+
+/// Whole minutes from `from` to `to`; timestamps are RFC 3339 or SQLite's UTC `YYYY-MM-DD HH:MM:SS`
+fn minutes_between(from: &str, to: &str) -> Option<i64> {
+    Some((parse_time(to)? - parse_time(from)?).num_minutes())
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").map(|t| t.and_utc()))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ChecklistItem, EscalationStatus, TeamAsk, TicketSnapshot};
+
+    fn escalation(id: i64, parent: Option<i64>) -> Escalation {
+        Escalation {
+            id,
+            ticket_id: "IT-4521".to_string(),
+            template_id: Some(3),
+            problem_summary: "Dana Agent can't reach IT-4521's VPN (dana@example.com)".to_string(),
+            checklist: vec![
+                ChecklistItem { text: "Restarted the VPN client".to_string(), checked: true, link: None },
+                ChecklistItem { text: "Collected logs".to_string(), checked: false, link: None },
+            ],
+            current_status: "Waiting on Sam Reporter".to_string(),
+            next_steps: "Check the concentrator".to_string(),
+            llm_summary: Some("Dana's VPN drops".to_string()),
+            llm_confidence: Some("high".to_string()),
+            markdown_output: Some("## Escalation: IT-4521".to_string()),
+            status: EscalationStatus::Posted,
+            posted_at: Some("2024-01-15T10:30:00+00:00".to_string()),
+            created_at: "2024-01-15 10:00:00".to_string(),
+            updated_at: "2024-01-15 10:30:00".to_string(),
+            private_notes: Some("Customer is the CFO".to_string()),
+            ticket_snapshot: Some(TicketSnapshot {
+                summary: "VPN drops".to_string(),
+                status: "Open".to_string(),
+                assignee: Some("Sam Reporter".to_string()),
+                captured_at: None,
+            }),
+            parent_escalation_id: parent,
+            acknowledged_at: Some("2024-01-15T12:00:00+00:00".to_string()),
+            acknowledged_by: Some("Lee Network".to_string()),
+            render_version: 4,
+            l2_teams: vec![TeamAsk { team: "Network".to_string(), asks: String::new(), mentions: vec!["5b10a".to_string()] }],
+            incident_id: Some("INC-2041".to_string()),
+            ..Escalation::fixture()
+        }
+    }
+
+    fn template() -> Template {
+        Template {
+            id: 3,
+            name: "VPN".to_string(),
+            description: String::new(),
+            category: String::new(),
+            checklist_items: vec![ChecklistItem { text: "Restarted the VPN client".to_string(), checked: false, link: None }],
+            l2_team: None,
+            body: None,
+            expected_artifacts: Vec::new(),
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn test_build_strips_personal_details() {
+        let escalations = vec![escalation(10, None), escalation(12, Some(10)), escalation(13, Some(99))];
+        let redactor = TextRedactor::new(crate::services::ticket_keys::JIRA_CLOUD_PATTERN, &[]).unwrap();
+        let dataset = build(
+            &escalations,
+            &HashMap::from([(12, 2)]),
+            &HashMap::from([(10, 1)]),
+            &HashMap::from([(3, template())]),
+            &redactor,
+        );
+
+        let first = &dataset.escalations[0];
+        assert_eq!(first.problem_summary_chars, escalations[0].problem_summary.chars().count());
+        assert_eq!(first.template_name.as_deref(), Some("VPN"));
+        assert_eq!((first.minutes_to_post, first.minutes_to_acknowledge), (Some(30), Some(90)));
+        assert_eq!((first.checklist.len(), first.checklist_checked), (2, 1));
+        assert_eq!(first.checklist[0].text.as_deref(), Some("Restarted the VPN client"));
+        assert_eq!(first.checklist[1].text, None);
+        assert_eq!(first.attachment_count, 1);
+        assert_eq!(first.l2_teams, vec!["Network"]);
+        assert!(first.incident_linked);
+
+        assert_eq!((dataset.escalations[1].ref_id, dataset.escalations[1].parent_ref), (2, Some(1)));
+        assert_eq!(dataset.escalations[1].bounce_count, 2);
+        assert_eq!(dataset.escalations[2].parent_ref, None);
+
+        // Names nothing in the app knows about, like the customer on the ticket, must not get through either
+        let json = serde_json::to_string(&dataset).unwrap();
+        for leaked in ["IT-4521", "Dana", "CFO", "Sam", "Lee", "5b10a", "INC-2041", "dana@example.com", "Collected logs"] {
+            assert!(!json.contains(leaked), "{} leaked", leaked);
+        }
+    }
+
+    #[test]
+    fn test_minutes_between() {
+        assert_eq!(minutes_between("2024-01-15 10:00:00", "2024-01-15T11:15:30+01:00"), Some(15));
+        assert_eq!(minutes_between("not a time", "2024-01-15 10:00:00"), None);
+    }
+}
@@ -0,0 +1,130 @@
+/// Pre-upload check against Jira's attachment limits
+///
+/// Jira rejects an oversized file with a 413 only after the whole upload has
+/// been sent. Sizes are checked up front against the instance's per-file limit
+/// (or the part size, when large files are split) and against a budget for
+/// everything attached to the ticket.
+use std::fs;
+use std::path::Path;
+
+const MB: u64 = 1024 * 1024;
+
+/// What the uploads will be checked against
+pub struct QuotaLimits {
+    pub attachments_enabled: bool,
+    /// Largest single file the instance accepts, in bytes
+    pub upload_limit: u64,
+    /// Part size when large files are split before uploading
+    pub chunk_bytes: Option<u64>,
+    /// Practical ceiling for a ticket's attachments in total
+    pub ticket_budget: Option<u64>,
+}
+
+/// Size of each file that exists; missing files are left for screening to report
+pub fn file_sizes(file_paths: &[String]) -> Vec<(String, u64)> {
+    file_paths
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok().map(|m| (path.clone(), m.len())))
+        .collect()
+}
+
+/// One warning per problem the upload would run into
+pub fn check(files: &[(String, u64)], existing_bytes: u64, limits: &QuotaLimits) -> Vec<String> {
+    let mut warnings = rejections(files, limits);
+    if files.is_empty() || !limits.attachments_enabled {
+        return warnings;
+    }
+
+    let new_bytes: u64 = files.iter().map(|(_, size)| size).sum();
+    if let Some(budget) = limits.ticket_budget {
+        if existing_bytes + new_bytes > budget {
+            warnings.push(format!(
+                "The ticket already has {} attached; adding {} would exceed the {} budget",
+                format_mb(existing_bytes),
+                format_mb(new_bytes),
+                format_mb(budget)
+            ));
+        }
+    }
+    warnings
+}
+
+/// The problems Jira would certainly reject the upload for; the ticket budget is advisory and not among them
+pub fn rejections(files: &[(String, u64)], limits: &QuotaLimits) -> Vec<String> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    if !limits.attachments_enabled {
+        return vec!["Attachments are disabled on this Jira instance".to_string()];
+    }
+
+    let mut rejections = Vec::new();
+    for (path, size) in files {
+        let largest_upload = limits.chunk_bytes.map_or(*size, |chunk| chunk.min(*size));
+        if largest_upload > limits.upload_limit {
+            rejections.push(format!(
+                "{} is {} and Jira accepts files up to {}",
+                file_name(path),
+                format_mb(*size),
+                format_mb(limits.upload_limit)
+            ));
+        }
+    }
+    rejections
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / MB as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(chunk_bytes: Option<u64>, ticket_budget: Option<u64>) -> QuotaLimits {
+        QuotaLimits {
+            attachments_enabled: true,
+            upload_limit: 10 * MB,
+            chunk_bytes,
+            ticket_budget,
+        }
+    }
+
+    #[test]
+    fn test_file_over_upload_limit() {
+        let files = vec![("/logs/capture.pcap".to_string(), 25 * MB), ("/logs/vpn.log".to_string(), MB)];
+
+        let warnings = check(&files, 0, &limits(None, None));
+        assert_eq!(warnings, vec!["capture.pcap is 25.0 MB and Jira accepts files up to 10.0 MB"]);
+        assert_eq!(rejections(&files, &limits(None, None)), warnings);
+
+        // Split into 5 MB parts, every upload fits
+        assert!(check(&files, 0, &limits(Some(5 * MB), None)).is_empty());
+    }
+
+    #[test]
+    fn test_ticket_budget() {
+        let files = vec![("/logs/vpn.log".to_string(), 4 * MB)];
+        assert!(check(&files, 6 * MB, &limits(None, Some(10 * MB))).is_empty());
+
+        let warnings = check(&files, 8 * MB, &limits(None, Some(10 * MB)));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("already has 8.0 MB"));
+        assert!(rejections(&files, &limits(None, Some(10 * MB))).is_empty());
+    }
+
+    #[test]
+    fn test_attachments_disabled() {
+        let mut disabled = limits(None, None);
+        disabled.attachments_enabled = false;
+        assert_eq!(check(&[("a.log".to_string(), 1)], 0, &disabled).len(), 1);
+        assert!(check(&[], 0, &disabled).is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+/// Staged copies of escalation attachments under `<app data>/attachments`
+///
+/// Files are copied on save so an escalation still has its evidence after the
+/// originals are moved or deleted. Layout is `<escalation id>/<n>/<file name>`,
+/// which keeps original file names intact even when two attachments share one.
+/// Only files inside this directory are ever deleted by cleanup.
+use crate::error::AppResult;
+use crate::models::AttachmentCleanupReport;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STORAGE_DIR: &str = "attachments";
+
+/// Attachment storage next to the database file; None for in-memory databases
+pub fn storage_dir(conn: &Connection) -> AppResult<Option<PathBuf>> {
+    let db_file: String = conn.query_row(
+        "SELECT file FROM pragma_database_list WHERE name = 'main'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    if db_file.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Path::new(&db_file).parent().map(|parent| parent.join(STORAGE_DIR)))
+}
+
+/// Copy a file into storage, returning the staged path
+pub fn stage(dir: &Path, escalation_id: i64, index: usize, source: &str) -> AppResult<PathBuf> {
+    let source = Path::new(source);
+    let file_name = source
+        .file_name()
+        .ok_or_else(|| crate::error::AppError::File(format!("Not a file: {}", source.display())))?;
+
+    let target_dir = dir.join(escalation_id.to_string()).join(index.to_string());
+    fs::create_dir_all(&target_dir)?;
+
+    let target = target_dir.join(file_name);
+    fs::copy(source, &target)?;
+    Ok(target)
+}
+
+/// Write generated content (such as a pasted screenshot) into storage, returning the staged path
+pub fn stage_bytes(dir: &Path, escalation_id: i64, index: usize, file_name: &str, bytes: &[u8]) -> AppResult<PathBuf> {
+    let target_dir = dir.join(escalation_id.to_string()).join(index.to_string());
+    fs::create_dir_all(&target_dir)?;
+
+    let target = target_dir.join(file_name);
+    fs::write(&target, bytes)?;
+    Ok(target)
+}
+
+pub fn is_staged(dir: &Path, path: &str) -> bool {
+    Path::new(path).starts_with(dir)
+}
+
+/// When the staged file at `path` was removed by cleanup, if it was
+pub fn purged_at(conn: &Connection, path: &str) -> AppResult<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT purged_at FROM attachments WHERE file_path = ? AND purged_at IS NOT NULL LIMIT 1",
+            [path],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Delete one staged file, and its per-attachment directory once empty; paths outside
+/// storage are the agent's own files and are left alone
+pub fn remove_staged(dir: &Path, path: &str) -> AppResult<()> {
+    if !is_staged(dir, path) {
+        return Ok(());
+    }
+    match fs::remove_file(path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e.into()),
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        let _ = fs::remove_dir(parent);
+    }
+    Ok(())
+}
+
+/// Remove every staged file belonging to an escalation
+pub fn remove_escalation_files(dir: &Path, escalation_id: i64) -> AppResult<()> {
+    let escalation_dir = dir.join(escalation_id.to_string());
+    if escalation_dir.exists() {
+        fs::remove_dir_all(escalation_dir)?;
+    }
+    Ok(())
+}
+
+/// Remove staged files of escalations posted more than `retention_days` ago
+///
+/// Attachment rows are kept and stamped with `purged_at` in the same transaction
+/// that deletes their file, so no unmarked row points at a removed file. With
+/// `dry_run` nothing is deleted and the report shows what would be reclaimed.
+pub fn cleanup(
+    conn: &Connection,
+    dir: &Path,
+    retention_days: u32,
+    dry_run: bool,
+) -> AppResult<AttachmentCleanupReport> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.file_path FROM attachments a
+        JOIN escalations e ON e.id = a.escalation_id
+        WHERE a.purged_at IS NULL
+          AND e.posted_at IS NOT NULL
+          AND datetime(e.posted_at) < datetime('now', ?)",
+    )?;
+    let candidates = stmt
+        .query_map([format!("-{} days", retention_days)], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut report = AttachmentCleanupReport {
+        dry_run,
+        retention_days,
+        file_count: 0,
+        reclaimable_bytes: 0,
+        skipped_unstaged: 0,
+    };
+
+    for (id, file_path) in candidates {
+        // Files outside storage belong to the user and are never deleted
+        if !is_staged(dir, &file_path) {
+            report.skipped_unstaged += 1;
+            continue;
+        }
+
+        let size = fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+        report.file_count += 1;
+        report.reclaimable_bytes += size;
+
+        if dry_run {
+            continue;
+        }
+
+        // A failed delete rolls the stamp back
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "UPDATE attachments SET purged_at = datetime('now') WHERE id = ?",
+            params![id],
+        )?;
+        match fs::remove_file(&file_path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        tx.commit()?;
+
+        if let Some(parent) = Path::new(&file_path).parent() {
+            // Only succeeds once the per-attachment directory is empty
+            let _ = fs::remove_dir(parent);
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn setup(dir: &Path) -> Connection {
+        let conn = Connection::open(dir.join("tickets.db")).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY, posted_at TEXT);
+            CREATE TABLE attachments (
+                id INTEGER PRIMARY KEY, escalation_id INTEGER, file_path TEXT, purged_at TEXT
+            );",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_storage_dir_next_to_database() {
+        let dir = temp_dir("location");
+        let conn = setup(&dir);
+        let storage = storage_dir(&conn).unwrap().unwrap();
+        assert!(storage.ends_with(STORAGE_DIR));
+        assert!(storage_dir(&Connection::open_in_memory().unwrap()).unwrap().is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_cleanup_removes_only_old_staged_files() {
+        let dir = temp_dir("cleanup");
+        let conn = setup(&dir);
+        let storage = storage_dir(&conn).unwrap().unwrap();
+
+        let source = dir.join("log.txt");
+        fs::write(&source, b"0123456789").unwrap();
+        let old = stage(&storage, 1, 0, source.to_str().unwrap()).unwrap();
+        let recent = stage(&storage, 2, 0, source.to_str().unwrap()).unwrap();
+
+        conn.execute_batch(&format!(
+            "INSERT INTO escalations VALUES (1, datetime('now', '-100 days')), (2, datetime('now'));
+            INSERT INTO attachments VALUES
+                (1, 1, '{}', NULL), (2, 1, '{}', NULL), (3, 2, '{}', NULL);",
+            old.display(),
+            source.display(),
+            recent.display()
+        ))
+        .unwrap();
+
+        let preview = cleanup(&conn, &storage, 30, true).unwrap();
+        assert_eq!(preview.file_count, 1);
+        assert_eq!(preview.reclaimable_bytes, 10);
+        assert_eq!(preview.skipped_unstaged, 1);
+        assert!(old.exists());
+
+        let report = cleanup(&conn, &storage, 30, false).unwrap();
+        assert_eq!(report.file_count, 1);
+        assert!(!old.exists());
+        assert!(recent.exists());
+        assert!(source.exists());
+
+        let purged: i64 = conn
+            .query_row("SELECT COUNT(*) FROM attachments WHERE purged_at IS NOT NULL", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(purged, 1);
+        assert!(purged_at(&conn, old.to_str().unwrap()).unwrap().is_some());
+        assert!(purged_at(&conn, recent.to_str().unwrap()).unwrap().is_none());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
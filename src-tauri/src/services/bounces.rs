@@ -0,0 +1,130 @@
+/// Bounce-back report: how often L2 rejects escalations, why, and how their
+/// quality scores compare with the escalations L2 accepted
+use crate::error::AppResult;
+use crate::models::{BounceReasonCount, BounceReport, ChecklistItem};
+use crate::services::quality;
+use rusqlite::Connection;
+
+pub fn report(conn: &Connection) -> AppResult<BounceReport> {
+    let posted_count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM escalations WHERE posted_at IS NOT NULL",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let (bounced_count, avg_score_bounced): (i64, Option<f64>) = conn.query_row(
+        "SELECT COUNT(DISTINCT escalation_id), AVG(quality_score) FROM bounces",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT LOWER(TRIM(reason)) AS normalized, COUNT(*) AS count
+        FROM bounces
+        GROUP BY normalized
+        ORDER BY count DESC, normalized
+        LIMIT 5",
+    )?;
+    let top_reasons = stmt
+        .query_map([], |row| {
+            Ok(BounceReasonCount {
+                reason: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Score accepted escalations with the current heuristic to compare against bounced ones
+    let mut stmt = conn.prepare(
+        "SELECT e.checklist, e.problem_summary, e.next_steps, COUNT(a.id)
+        FROM escalations e
+        LEFT JOIN attachments a ON a.escalation_id = e.id
+        WHERE e.status IN ('posted', 'posted_with_errors')
+        GROUP BY e.id",
+    )?;
+    let accepted_scores = stmt
+        .query_map([], |row| {
+            let checklist: Vec<ChecklistItem> = serde_json::from_str(&row.get::<_, String>(0)?).unwrap_or_default();
+            let summary: String = row.get(1)?;
+            let next_steps: String = row.get(2)?;
+            let attachments: i64 = row.get(3)?;
+            Ok(quality::score_fields(&checklist, &summary, &next_steps, attachments as usize).score as f64)
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let avg_score_accepted = if accepted_scores.is_empty() {
+        None
+    } else {
+        Some(accepted_scores.iter().sum::<f64>() / accepted_scores.len() as f64)
+    };
+
+    let bounce_rate = if posted_count > 0 {
+        bounced_count as f64 / posted_count as f64
+    } else {
+        0.0
+    };
+
+    Ok(BounceReport {
+        posted_count,
+        bounced_count,
+        bounce_rate,
+        avg_score_bounced,
+        avg_score_accepted,
+        top_reasons,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (
+                id INTEGER PRIMARY KEY, checklist TEXT NOT NULL, problem_summary TEXT NOT NULL,
+                next_steps TEXT NOT NULL, status TEXT NOT NULL, posted_at TEXT
+            );
+            CREATE TABLE attachments (id INTEGER PRIMARY KEY, escalation_id INTEGER);
+            CREATE TABLE bounces (id INTEGER PRIMARY KEY, escalation_id INTEGER, reason TEXT, quality_score INTEGER);",
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let report = report(&setup()).unwrap();
+        assert_eq!(report.posted_count, 0);
+        assert_eq!(report.bounce_rate, 0.0);
+        assert_eq!(report.avg_score_accepted, None);
+        assert!(report.top_reasons.is_empty());
+    }
+
+    #[test]
+    fn test_report() {
+        let conn = setup();
+        conn.execute_batch(
+            r#"INSERT INTO escalations VALUES
+                (1, '[{"text":"Step","checked":true}]', '', '', 'posted', '2024-01-01'),
+                (2, '[]', '', '', 'posted_with_errors', '2024-01-02'),
+                (3, '[]', '', '', 'bounced', '2024-01-03'),
+                (4, '[]', '', '', 'bounced', '2024-01-04'),
+                (5, '[]', '', '', 'draft', NULL);
+            INSERT INTO attachments (escalation_id) VALUES (2), (2), (2);
+            INSERT INTO bounces (escalation_id, reason, quality_score) VALUES
+                (3, 'Missing logs', 20), (3, ' missing logs ', 30), (4, 'Wrong team', 40);"#,
+        )
+        .unwrap();
+
+        let report = report(&conn).unwrap();
+        assert_eq!(report.posted_count, 4);
+        assert_eq!(report.bounced_count, 2);
+        assert_eq!(report.bounce_rate, 0.5);
+        assert_eq!(report.avg_score_bounced, Some(30.0));
+        // A fully checked checklist (40) and two or more attachments (15)
+        assert_eq!(report.avg_score_accepted, Some(27.5));
+        assert_eq!(report.top_reasons[0].reason, "missing logs");
+        assert_eq!(report.top_reasons[0].count, 2);
+        assert_eq!(report.top_reasons[1].reason, "wrong team");
+    }
+}
@@ -0,0 +1,193 @@
+/// Handoff bundle export: a zip holding the rendered escalation (markdown and
+/// HTML), its attachments, and a manifest with SHA-256 checksums so air-gapped
+/// L2 teams can verify the transfer. Screenshot redactions are burnt in, the
+/// same as for uploads, so a bundle never carries what a redaction hides.
+use crate::error::{AppError, AppResult};
+use crate::models::{Attachment, BundleFile, BundleManifest, Escalation};
+use crate::services::{html, redaction};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+pub fn write_bundle(
+    escalation: &Escalation,
+    markdown: &str,
+    private_notes: Option<&str>,
+    output_path: &Path,
+) -> AppResult<BundleManifest> {
+    let file = std::fs::File::create(output_path)
+        .map_err(|e| AppError::File(format!("Cannot create {}: {}", output_path.display(), e)))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut files = Vec::new();
+    let mut missing_attachments = Vec::new();
+
+    let title = format!("Escalation: {}", escalation.ticket_id);
+    add_entry(&mut zip, options, "escalation.md", markdown.as_bytes(), &mut files)?;
+    add_entry(
+        &mut zip,
+        options,
+        "escalation.html",
+        html::render_document(&title, markdown).as_bytes(),
+        &mut files,
+    )?;
+
+    if let Some(notes) = private_notes {
+        add_entry(&mut zip, options, "private-notes.md", notes.as_bytes(), &mut files)?;
+    }
+
+    for attachment in &escalation.attachments {
+        if attachment.purged_at.is_some() {
+            missing_attachments.push(attachment.file_path.clone());
+            continue;
+        }
+
+        match attachment_contents(attachment) {
+            Ok(bytes) => {
+                let name = unique_attachment_name(&attachment.file_path, &files);
+                add_entry(&mut zip, options, &name, &bytes, &mut files)?;
+            }
+            Err(e) => {
+                log::warn!("Skipping missing attachment {}: {}", attachment.file_path, e);
+                missing_attachments.push(attachment.file_path.clone());
+            }
+        }
+    }
+
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        escalation_id: escalation.id,
+        ticket_id: escalation.ticket_id.clone(),
+        status: escalation.status.clone(),
+        created_at: escalation.created_at.clone(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        files,
+        missing_attachments,
+    };
+
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize manifest: {}", e)))?;
+    zip.start_file("manifest.json", options).map_err(zip_error)?;
+    zip.write_all(&manifest_json)?;
+    zip.finish().map_err(zip_error)?;
+
+    Ok(manifest)
+}
+
+fn add_entry(
+    zip: &mut zip::ZipWriter<std::fs::File>,
+    options: SimpleFileOptions,
+    name: &str,
+    bytes: &[u8],
+    files: &mut Vec<BundleFile>,
+) -> AppResult<()> {
+    zip.start_file(name, options).map_err(zip_error)?;
+    zip.write_all(bytes)?;
+
+    files.push(BundleFile {
+        path: name.to_string(),
+        size_bytes: bytes.len() as u64,
+        sha256: sha256_hex(bytes),
+    });
+
+    Ok(())
+}
+
+/// `attachments/<file name>`, suffixed when two attachments share a name
+/// The file as it would be uploaded, with any redactions applied
+fn attachment_contents(attachment: &Attachment) -> AppResult<Vec<u8>> {
+    let bytes = std::fs::read(&attachment.file_path)?;
+    match attachment.annotations.as_ref().map(|a| a.redactions.as_slice()) {
+        Some(redactions) if !redactions.is_empty() => redaction::redact_png(&bytes, redactions),
+        _ => Ok(bytes),
+    }
+}
+
+fn unique_attachment_name(file_path: &str, existing: &[BundleFile]) -> String {
+    let file_name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("attachment");
+
+    let mut candidate = format!("attachments/{}", file_name);
+    let mut n = 1;
+    while existing.iter().any(|f| f.path == candidate) {
+        n += 1;
+        candidate = format!("attachments/{}-{}", n, file_name);
+    }
+    candidate
+}
+
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn zip_error(err: zip::result::ZipError) -> AppError {
+    AppError::File(format!("Failed to write bundle: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ImageAnnotations, ImageRect};
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_bundle_applies_redactions() {
+        let dir = temp_dir("bundle-redaction");
+        let screenshot = dir.join("screen.png");
+        std::fs::write(&screenshot, redaction::encode_rgba_png(2, 2, &[255; 16]).unwrap()).unwrap();
+
+        let redactions = vec![ImageRect { x: 0, y: 0, width: 1, height: 1 }];
+        let escalation = Escalation {
+            attachments: vec![Attachment {
+                id: 1,
+                escalation_id: 1,
+                file_path: screenshot.to_string_lossy().into_owned(),
+                description: None,
+                created_at: String::new(),
+                purged_at: None,
+                artifact: None,
+                annotations: Some(ImageAnnotations { redactions: redactions.clone(), ..Default::default() }),
+            }],
+            ..Escalation::fixture()
+        };
+
+        let output = dir.join("bundle.zip");
+        write_bundle(&escalation, "# Escalation", None, &output).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        let mut bundled = Vec::new();
+        std::io::Read::read_to_end(&mut archive.by_name("attachments/screen.png").unwrap(), &mut bundled).unwrap();
+        let expected = redaction::redact_png(&std::fs::read(&screenshot).unwrap(), &redactions).unwrap();
+        assert_eq!(bundled, expected);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unique_attachment_name() {
+        let existing = vec![BundleFile {
+            path: "attachments/log.txt".to_string(),
+            size_bytes: 0,
+            sha256: String::new(),
+        }];
+        assert_eq!(unique_attachment_name("/a/log.txt", &[]), "attachments/log.txt");
+        assert_eq!(unique_attachment_name("/b/log.txt", &existing), "attachments/2-log.txt");
+    }
+}
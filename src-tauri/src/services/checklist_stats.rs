@@ -0,0 +1,84 @@
+/// Per-item completion statistics for a template's checklist
+///
+/// Items are matched by trimmed text, so an item reworded in the template
+/// starts a fresh series; old wordings are still reported with
+/// `in_template: false` until they age out of the data.
+use crate::models::{ChecklistItem, ChecklistItemStat};
+use std::collections::HashMap;
+
+pub fn aggregate(template_items: &[ChecklistItem], checklists: &[Vec<ChecklistItem>]) -> Vec<ChecklistItemStat> {
+    let mut stats: Vec<ChecklistItemStat> = template_items
+        .iter()
+        .map(|item| ChecklistItemStat {
+            text: item.text.trim().to_string(),
+            in_template: true,
+            times_used: 0,
+            times_checked: 0,
+            skip_rate: 0.0,
+        })
+        .collect();
+    let mut index: HashMap<String, usize> = stats
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.text.clone(), i))
+        .collect();
+
+    for checklist in checklists {
+        for item in checklist {
+            let text = item.text.trim();
+            let i = *index.entry(text.to_string()).or_insert_with(|| {
+                stats.push(ChecklistItemStat {
+                    text: text.to_string(),
+                    in_template: false,
+                    times_used: 0,
+                    times_checked: 0,
+                    skip_rate: 0.0,
+                });
+                stats.len() - 1
+            });
+
+            stats[i].times_used += 1;
+            if item.checked {
+                stats[i].times_checked += 1;
+            }
+        }
+    }
+
+    for stat in &mut stats {
+        if stat.times_used > 0 {
+            stat.skip_rate = (stat.times_used - stat.times_checked) as f64 / stat.times_used as f64;
+        }
+    }
+
+    // Most-skipped first; ties keep template order
+    stats.sort_by(|a, b| b.skip_rate.total_cmp(&a.skip_rate));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, checked: bool) -> ChecklistItem {
+        ChecklistItem { text: text.to_string(), checked, link: None }
+    }
+
+    #[test]
+    fn test_aggregate_orders_by_skip_rate() {
+        let template = vec![item("Restart", false), item("Check logs", false), item("Never used", false)];
+        let checklists = vec![
+            vec![item("Restart", true), item("Check logs", false)],
+            vec![item("Restart", true), item("Check logs", true)],
+            vec![item("Restart ", false), item("Old wording", false)],
+        ];
+
+        let stats = aggregate(&template, &checklists);
+        let texts: Vec<&str> = stats.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, vec!["Old wording", "Check logs", "Restart", "Never used"]);
+
+        let restart = &stats[2];
+        assert_eq!((restart.times_used, restart.times_checked), (3, 2));
+        assert!(!stats[0].in_template);
+        assert_eq!(stats[3].times_used, 0);
+    }
+}
@@ -0,0 +1,247 @@
+/// Split-and-zip uploads for large attachments on unreliable links
+///
+/// Jira's attachment API has no resumable upload, so a file larger than the
+/// chunk size is zipped (logs usually shrink a lot) and, if still too big,
+/// split into `name.zip.001`, `name.zip.002`, ... plus a README explaining how
+/// to reassemble them. Each part is a separate attachment; parts Jira accepted
+/// are recorded in `upload_parts`, so a retry after a dropped connection only
+/// sends what is missing. Prepared parts are cached under the temp directory
+/// by content hash and reused across attempts.
+use crate::error::{AppError, AppResult};
+use crate::services::bundle::sha256_hex;
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+const COMPLETE_MARKER: &str = ".complete";
+
+pub struct UploadPlan {
+    pub file_sha256: String,
+    /// Files to upload, in order; just the original file when no split is needed
+    pub parts: Vec<PathBuf>,
+}
+
+pub fn work_dir() -> PathBuf {
+    std::env::temp_dir().join("ticket-handoff-uploads")
+}
+
+/// Decide how to upload a file, preparing zip/split parts when it exceeds `chunk_bytes`
+pub fn prepare(file_path: &Path, chunk_bytes: u64, work_dir: &Path) -> AppResult<UploadPlan> {
+    let bytes = fs::read(file_path)
+        .map_err(|_| AppError::File(format!("File not found: {}", file_path.display())))?;
+    let file_sha256 = sha256_hex(&bytes);
+
+    if bytes.len() as u64 <= chunk_bytes {
+        return Ok(UploadPlan {
+            file_sha256,
+            parts: vec![file_path.to_path_buf()],
+        });
+    }
+
+    let file_name = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| AppError::File("Invalid file name".to_string()))?;
+    let dir = work_dir.join(&file_sha256);
+
+    if !dir.join(COMPLETE_MARKER).exists() {
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir)?;
+        write_parts(&dir, file_name, &bytes, &file_sha256, chunk_bytes)?;
+        fs::write(dir.join(COMPLETE_MARKER), b"")?;
+    }
+
+    let mut parts: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.file_name().is_some_and(|n| n != COMPLETE_MARKER))
+        .collect();
+    parts.sort();
+
+    Ok(UploadPlan { file_sha256, parts })
+}
+
+fn write_parts(dir: &Path, file_name: &str, bytes: &[u8], sha: &str, chunk_bytes: u64) -> AppResult<()> {
+    let zip_name = format!("{}.zip", file_name);
+    let zip_path = dir.join(&zip_name);
+
+    let mut zip = zip::ZipWriter::new(fs::File::create(&zip_path)?);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .large_file(bytes.len() as u64 >= u32::MAX as u64);
+    zip.start_file(file_name, options).map_err(zip_error)?;
+    zip.write_all(bytes)?;
+    zip.finish().map_err(zip_error)?;
+
+    let zip_size = fs::metadata(&zip_path)?.len();
+    if zip_size <= chunk_bytes {
+        return Ok(());
+    }
+
+    let mut reader = fs::File::open(&zip_path)?;
+    let mut buffer = vec![0u8; chunk_bytes as usize];
+    let mut count = 0;
+    loop {
+        let read = read_full(&mut reader, &mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        count += 1;
+        fs::write(dir.join(format!("{}.{:03}", zip_name, count)), &buffer[..read])?;
+    }
+    fs::remove_file(&zip_path)?;
+
+    let readme = format!(
+        "{file_name} was split into {count} parts for upload.\n\n\
+        Download all {zip_name}.NNN parts into one folder, then join them:\n\
+        \x20 macOS/Linux: cat {zip_name}.* > {zip_name}\n\
+        \x20 Windows:     copy /b {zip_name}.001+{zip_name}.002+... {zip_name}\n\n\
+        Unzip {zip_name} to get the original file. Its SHA-256 is:\n{sha}\n",
+    );
+    // "~" sorts after the numbered parts, so the README is uploaded last
+    fs::write(dir.join(format!("{}.~README.txt", zip_name)), readme)?;
+
+    Ok(())
+}
+
+fn read_full(reader: &mut impl Read, buffer: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+fn zip_error(err: zip::result::ZipError) -> AppError {
+    AppError::File(format!("Failed to prepare split upload: {}", err))
+}
+
+pub fn part_name(part: &Path) -> String {
+    part.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+pub fn uploaded_parts(conn: &Connection, ticket_id: &str, file_sha256: &str) -> AppResult<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT part_name FROM upload_parts WHERE ticket_id = ? AND file_sha256 = ?")?;
+    let names = stmt
+        .query_map(params![ticket_id, file_sha256], |row| row.get(0))?
+        .collect::<Result<HashSet<_>, _>>()?;
+    Ok(names)
+}
+
+pub fn mark_uploaded(conn: &Connection, ticket_id: &str, file_sha256: &str, part_name: &str) -> AppResult<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO upload_parts (ticket_id, file_sha256, part_name) VALUES (?, ?, ?)",
+        params![ticket_id, file_sha256, part_name],
+    )?;
+    Ok(())
+}
+
+/// Forget progress and cached parts once every part is uploaded
+pub fn finish(conn: &Connection, ticket_id: &str, file_sha256: &str, work_dir: &Path) -> AppResult<()> {
+    conn.execute(
+        "DELETE FROM upload_parts WHERE ticket_id = ? AND file_sha256 = ?",
+        params![ticket_id, file_sha256],
+    )?;
+
+    let dir = work_dir.join(file_sha256);
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    /// Bytes that deflate can't shrink much, so splitting is exercised
+    fn noise(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_f491_u32;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_small_file_uploaded_as_is() {
+        let dir = temp_dir("small");
+        let file = dir.join("small.log");
+        fs::write(&file, b"hello").unwrap();
+
+        let plan = prepare(&file, 1024, &dir.join("work")).unwrap();
+        assert_eq!(plan.parts, vec![file]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_compressible_file_zipped_whole() {
+        let dir = temp_dir("zip");
+        let file = dir.join("app.log");
+        fs::write(&file, "INFO all good\n".repeat(1000)).unwrap();
+
+        let plan = prepare(&file, 4096, &dir.join("work")).unwrap();
+        let names: Vec<String> = plan.parts.iter().map(|p| part_name(p)).collect();
+        assert_eq!(names, vec!["app.log.zip"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_large_file_split_and_reassembles() {
+        let dir = temp_dir("split");
+        let file = dir.join("dump.bin");
+        let content = noise(10_000);
+        fs::write(&file, &content).unwrap();
+        let work = dir.join("work");
+
+        let plan = prepare(&file, 4096, &work).unwrap();
+        let names: Vec<String> = plan.parts.iter().map(|p| part_name(p)).collect();
+        assert_eq!(names.first().unwrap(), "dump.bin.zip.001");
+        assert_eq!(names.last().unwrap(), "dump.bin.zip.~README.txt");
+        assert!(plan.parts.iter().all(|p| fs::metadata(p).unwrap().len() <= 4096));
+
+        // Joining the numbered parts yields a zip holding the original bytes
+        let joined: Vec<u8> = plan.parts[..plan.parts.len() - 1]
+            .iter()
+            .flat_map(|p| fs::read(p).unwrap())
+            .collect();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(joined)).unwrap();
+        let mut restored = Vec::new();
+        archive.by_name("dump.bin").unwrap().read_to_end(&mut restored).unwrap();
+        assert_eq!(restored, content);
+
+        // A second attempt reuses the cached parts
+        let again = prepare(&file, 4096, &work).unwrap();
+        assert_eq!(again.parts, plan.parts);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_progress_tracking() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/014_upload_parts.sql")).unwrap();
+        let work = temp_dir("progress");
+
+        mark_uploaded(&conn, "HELP-1", "abc", "dump.zip.001").unwrap();
+        mark_uploaded(&conn, "HELP-1", "abc", "dump.zip.001").unwrap();
+        assert_eq!(uploaded_parts(&conn, "HELP-1", "abc").unwrap().len(), 1);
+        assert!(uploaded_parts(&conn, "HELP-2", "abc").unwrap().is_empty());
+
+        finish(&conn, "HELP-1", "abc", &work).unwrap();
+        assert!(uploaded_parts(&conn, "HELP-1", "abc").unwrap().is_empty());
+        fs::remove_dir_all(work).unwrap();
+    }
+}
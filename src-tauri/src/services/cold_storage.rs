@@ -0,0 +1,130 @@
+/// zstd compression of large text columns on old, posted escalations
+///
+/// `markdown_output` and `llm_summary` are moved into `*_zstd` BLOB columns and
+/// the text columns set to NULL; reads go through `text_column`, which returns
+/// whichever copy is present. Runs as part of database maintenance so the
+/// following VACUUM reclaims the space.
+use crate::error::{AppError, AppResult};
+use rusqlite::{params, Connection};
+
+/// Short values barely compress and aren't worth a BLOB
+pub const MIN_COMPRESS_BYTES: usize = 1024;
+
+const ZSTD_LEVEL: i32 = 19;
+
+pub fn compress(text: &str) -> AppResult<Vec<u8>> {
+    zstd::encode_all(text.as_bytes(), ZSTD_LEVEL)
+        .map_err(|e| AppError::Db(format!("Failed to compress text: {}", e)))
+}
+
+pub fn decompress(blob: &[u8]) -> AppResult<String> {
+    let bytes = zstd::decode_all(blob).map_err(|e| AppError::Db(format!("Failed to decompress text: {}", e)))?;
+    String::from_utf8(bytes).map_err(|e| AppError::Db(format!("Compressed text is not UTF-8: {}", e)))
+}
+
+/// The value of a column that may have been moved to cold storage
+pub fn text_column(text: Option<String>, compressed: Option<Vec<u8>>) -> AppResult<Option<String>> {
+    match (text, compressed) {
+        (Some(text), _) => Ok(Some(text)),
+        (None, Some(blob)) => decompress(&blob).map(Some),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Compress escalations posted more than `after_days` ago; returns how many changed
+pub fn archive(conn: &mut Connection, after_days: u32) -> AppResult<usize> {
+    let candidates = {
+        let mut stmt = conn.prepare(
+            "SELECT id, markdown_output, llm_summary FROM escalations
+            WHERE posted_at IS NOT NULL
+              AND datetime(posted_at) < datetime('now', ?1)
+              AND (length(markdown_output) >= ?2 OR length(llm_summary) >= ?2)",
+        )?;
+        let rows = stmt
+            .query_map(params![format!("-{} days", after_days), MIN_COMPRESS_BYTES as i64], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?, row.get::<_, Option<String>>(2)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows
+    };
+
+    let tx = conn.transaction()?;
+    for (id, markdown_output, llm_summary) in &candidates {
+        for (column, text) in [("markdown_output", markdown_output), ("llm_summary", llm_summary)] {
+            let Some(text) = text.as_deref().filter(|t| t.len() >= MIN_COMPRESS_BYTES) else {
+                continue;
+            };
+            tx.execute(
+                &format!("UPDATE escalations SET {0}_zstd = ?, {0} = NULL WHERE id = ?", column),
+                params![compress(text)?, id],
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(candidates.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (
+                id INTEGER PRIMARY KEY, markdown_output TEXT, llm_summary TEXT, posted_at TEXT
+            );",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/021_cold_storage.sql")).unwrap();
+        conn
+    }
+
+    fn stored(conn: &Connection, id: i64) -> (Option<String>, Option<String>) {
+        conn.query_row(
+            "SELECT markdown_output, markdown_output_zstd, llm_summary, llm_summary_zstd FROM escalations WHERE id = ?",
+            [id],
+            |row| {
+                Ok((
+                    text_column(row.get(0)?, row.get(1)?).unwrap(),
+                    text_column(row.get(2)?, row.get(3)?).unwrap(),
+                ))
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_archive_compresses_old_large_text() {
+        let mut conn = setup();
+        let long = "## Escalation\n".repeat(200);
+        conn.execute(
+            "INSERT INTO escalations (id, markdown_output, llm_summary, posted_at) VALUES
+            (1, ?1, 'short summary', datetime('now', '-400 days')),
+            (2, ?1, NULL, datetime('now', '-10 days')),
+            (3, ?1, NULL, NULL)",
+            [&long],
+        )
+        .unwrap();
+
+        assert_eq!(archive(&mut conn, 365).unwrap(), 1);
+
+        let (text, blob): (Option<String>, Option<Vec<u8>>) = conn
+            .query_row("SELECT markdown_output, markdown_output_zstd FROM escalations WHERE id = 1", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert!(text.is_none() && blob.is_some());
+        assert_eq!(stored(&conn, 1), (Some(long.clone()), Some("short summary".to_string())));
+        assert_eq!(stored(&conn, 2).0, Some(long));
+
+        // Already archived rows aren't picked up again
+        assert_eq!(archive(&mut conn, 365).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_decompress_rejects_garbage() {
+        assert!(decompress(b"not zstd").is_err());
+    }
+}
@@ -0,0 +1,68 @@
+/// Correlation footers link posted Jira comments back to local escalations
+///
+/// Posted comments end with a line like `TicketHandoff escalation #42`. ADF has
+/// no hidden node type, so the footer is rendered as a small italic line after
+/// the "Generated by" trailer rather than being truly invisible.
+use regex::Regex;
+
+pub fn footer(marker: &str, escalation_id: i64) -> String {
+    format!("{} #{}", marker, escalation_id)
+}
+
+/// Append the correlation footer to rendered markdown (no-op for an empty marker)
+pub fn append_footer(markdown: &str, marker: &str, escalation_id: i64) -> String {
+    let marker = marker.trim();
+    if marker.is_empty() {
+        return markdown.to_string();
+    }
+
+    format!("{}\n\n*{}*\n", markdown.trim_end(), footer(marker, escalation_id))
+}
+
+/// Extract the escalation id from text containing a correlation footer
+pub fn extract_escalation_id(text: &str, marker: &str) -> Option<i64> {
+    let marker = marker.trim();
+    if marker.is_empty() {
+        return None;
+    }
+
+    let pattern = format!(r"{}\s+#(\d+)", regex::escape(marker));
+    let re = Regex::new(&pattern).ok()?;
+
+    // The footer is always appended last, so prefer the final match
+    re.captures_iter(text)
+        .last()
+        .and_then(|caps| caps[1].parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKER: &str = "TicketHandoff escalation";
+
+    #[test]
+    fn test_append_footer() {
+        let markdown = append_footer("## Escalation: TEST-1\n\n", MARKER, 42);
+        assert!(markdown.ends_with("*TicketHandoff escalation #42*\n"));
+    }
+
+    #[test]
+    fn test_empty_marker_disables_footer() {
+        assert_eq!(append_footer("body", "  ", 42), "body");
+        assert_eq!(extract_escalation_id("TicketHandoff escalation #42", ""), None);
+    }
+
+    #[test]
+    fn test_extract_escalation_id() {
+        let text = "Some comment\nTicketHandoff escalation #17";
+        assert_eq!(extract_escalation_id(text, MARKER), Some(17));
+        assert_eq!(extract_escalation_id("Unrelated comment #5", MARKER), None);
+    }
+
+    #[test]
+    fn test_extract_escapes_marker() {
+        let text = "Handoff (v2) #9";
+        assert_eq!(extract_escalation_id(text, "Handoff (v2)"), Some(9));
+    }
+}
@@ -0,0 +1,174 @@
+/// Rows that point at escalations which no longer exist
+///
+/// `delete_escalation` removes child rows first, but SQLite only enforces the
+/// `ON DELETE CASCADE` foreign keys on connections that enable them, so a row
+/// deleted from a SQLite shell, or a crash partway through a delete, leaves
+/// audit entries, field history, attachments and jobs behind. The activity log
+/// is left alone: it records what happened on a day, deleted escalations included.
+use crate::error::AppResult;
+use crate::models::{DataIntegrityReport, OrphanCount};
+use crate::services::attachment_storage;
+use rusqlite::Connection;
+
+/// Tables whose `escalation_id` must refer to an existing escalation
+pub const CHILD_TABLES: &[&str] = &[
+    "audit_log",
+    "audit_field_history",
+    "attachments",
+    "attachment_upload_failures",
+    "bounces",
+    "escalation_locks",
+    "escalation_embeddings",
+    "jobs",
+    "kb_drafts",
+];
+
+const ORPHANED: &str = "escalation_id NOT IN (SELECT id FROM escalations)";
+const DANGLING_PARENT: &str =
+    "parent_escalation_id IS NOT NULL AND parent_escalation_id NOT IN (SELECT id FROM escalations)";
+
+pub fn check(conn: &Connection) -> AppResult<DataIntegrityReport> {
+    let mut orphans = Vec::new();
+    for table in CHILD_TABLES {
+        let count: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE {}", table, ORPHANED),
+            [],
+            |row| row.get(0),
+        )?;
+        orphans.push(OrphanCount { table: table.to_string(), count });
+    }
+    let dangling_parents: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM escalations WHERE {}", DANGLING_PARENT),
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(DataIntegrityReport {
+        total: orphans.iter().map(|o| o.count).sum::<i64>() + dangling_parents,
+        orphans,
+        dangling_parents,
+        repaired: false,
+    })
+}
+
+/// Delete orphaned rows and clear re-escalation links to deleted escalations, in one transaction.
+/// The report holds what was found (and so removed).
+///
+/// Staged files of orphaned attachments are deleted once the transaction has committed,
+/// so a rolled-back repair never leaves rows pointing at removed files.
+pub fn repair(conn: &mut Connection) -> AppResult<DataIntegrityReport> {
+    let tx = conn.transaction()?;
+    let mut report = check(&tx)?;
+    let orphaned_files: Vec<String> = tx
+        .prepare(&format!("SELECT file_path FROM attachments WHERE {}", ORPHANED))?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    let storage = attachment_storage::storage_dir(&tx)?;
+    for orphan in report.orphans.iter().filter(|o| o.count > 0) {
+        tx.execute(&format!("DELETE FROM {} WHERE {}", orphan.table, ORPHANED), [])?;
+    }
+    if report.dangling_parents > 0 {
+        tx.execute(
+            &format!("UPDATE escalations SET parent_escalation_id = NULL WHERE {}", DANGLING_PARENT),
+            [],
+        )?;
+    }
+    tx.commit()?;
+
+    // The rows are gone either way; a file that can't be deleted is only wasted space
+    if let Some(dir) = storage {
+        for path in &orphaned_files {
+            if let Err(e) = attachment_storage::remove_staged(&dir, path) {
+                log::warn!("Failed to delete orphaned attachment {}: {}", path, e);
+            }
+        }
+    }
+
+    report.repaired = true;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        populate(Connection::open_in_memory().unwrap())
+    }
+
+    fn populate(conn: Connection) -> Connection {
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY, parent_escalation_id INTEGER);
+            INSERT INTO escalations (id, parent_escalation_id) VALUES (1, NULL), (2, 1), (3, 9);",
+        )
+        .unwrap();
+        for table in CHILD_TABLES {
+            conn.execute_batch(&format!(
+                "CREATE TABLE {0} (escalation_id INTEGER NOT NULL, file_path TEXT);
+                INSERT INTO {0} (escalation_id) VALUES (1), (2);",
+                table
+            ))
+            .unwrap();
+        }
+        conn
+    }
+
+    fn count(report: &DataIntegrityReport, table: &str) -> i64 {
+        report.orphans.iter().find(|o| o.table == table).unwrap().count
+    }
+
+    #[test]
+    fn test_check_counts_per_table() {
+        let report = check(&setup()).unwrap();
+        assert_eq!(report.orphans.len(), CHILD_TABLES.len());
+        assert_eq!(report.total, 1);
+        assert_eq!(report.dangling_parents, 1);
+    }
+
+    #[test]
+    fn test_repair_removes_orphans() {
+        let mut conn = setup();
+        conn.execute_batch(
+            "INSERT INTO audit_log (escalation_id) VALUES (7), (7);
+            INSERT INTO jobs (escalation_id) VALUES (8);",
+        )
+        .unwrap();
+
+        let found = check(&conn).unwrap();
+        assert_eq!((count(&found, "audit_log"), count(&found, "jobs"), count(&found, "attachments")), (2, 1, 0));
+        assert_eq!(found.total, 4);
+        assert!(!found.repaired);
+
+        let repaired = repair(&mut conn).unwrap();
+        assert!(repaired.repaired);
+        assert_eq!(repaired.total, 4);
+
+        let after = check(&conn).unwrap();
+        assert_eq!(after.total, 0);
+        let kept: i64 = conn.query_row("SELECT COUNT(*) FROM audit_log", [], |row| row.get(0)).unwrap();
+        assert_eq!(kept, 2);
+        let parent: Option<i64> = conn
+            .query_row("SELECT parent_escalation_id FROM escalations WHERE id = 2", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(parent, Some(1));
+    }
+
+    #[test]
+    fn test_repair_deletes_staged_files() {
+        let dir = crate::test_support::temp_dir("integrity");
+        let mut conn = populate(Connection::open(dir.join("app.db")).unwrap());
+        let storage = dir.join("attachments");
+        let staged = attachment_storage::stage_bytes(&storage, 7, 0, "vpn.log", b"log").unwrap();
+        let original = dir.join("original.log");
+        std::fs::write(&original, "log").unwrap();
+        conn.execute(
+            "INSERT INTO attachments (escalation_id, file_path) VALUES (7, ?), (7, ?)",
+            [staged.to_string_lossy(), original.to_string_lossy()],
+        )
+        .unwrap();
+
+        assert_eq!(count(&repair(&mut conn).unwrap(), "attachments"), 2);
+        assert!(!staged.exists());
+        assert!(original.exists());
+    }
+}
@@ -0,0 +1,115 @@
+/// File-level recovery for a database that failed to open at startup
+///
+/// Nothing here deletes data: damaged or reset databases are renamed aside
+/// (with their -wal/-shm files) so they can still be sent in for recovery.
+use crate::error::{AppError, AppResult};
+use rusqlite::{Connection, OpenFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const SIDECARS: [&str; 2] = ["-wal", "-shm"];
+
+/// Whether the file opens and passes SQLite's quick_check
+pub fn integrity_ok(db_path: &Path) -> bool {
+    let check = || -> rusqlite::Result<bool> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        conn.busy_timeout(Duration::from_secs(2))?;
+        let result: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+        Ok(result == "ok")
+    };
+
+    check().unwrap_or(false)
+}
+
+/// Rename the database and its sidecar files to `<name>.<label>-<timestamp>`
+pub fn move_aside(db_path: &Path, label: &str) -> AppResult<PathBuf> {
+    let suffix = format!("{}-{}", label, chrono::Utc::now().format("%Y%m%d%H%M%S"));
+    let backup = with_suffix(db_path, &format!(".{}", suffix));
+
+    fs::rename(db_path, &backup)?;
+    for sidecar in SIDECARS {
+        let from = with_suffix(db_path, sidecar);
+        if from.exists() {
+            fs::rename(&from, with_suffix(&backup, sidecar))?;
+        }
+    }
+
+    Ok(backup)
+}
+
+/// Copy whatever SQLite can still read into a fresh file next to the original
+pub fn salvage(db_path: &Path) -> AppResult<PathBuf> {
+    let out = with_suffix(db_path, ".salvaged");
+    if out.exists() {
+        fs::remove_file(&out)?;
+    }
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.execute("VACUUM INTO ?1", [out.to_string_lossy()])?;
+    drop(conn);
+
+    if !integrity_ok(&out) {
+        let _ = fs::remove_file(&out);
+        return Err(AppError::Validation(
+            "The salvaged copy failed its integrity check. Reset the database instead.".to_string(),
+        ));
+    }
+
+    Ok(out)
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    fn create_db(path: &Path) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch("CREATE TABLE t (v TEXT); INSERT INTO t VALUES ('kept');")
+            .unwrap();
+    }
+
+    #[test]
+    fn test_integrity_check() {
+        let dir = temp_dir("integrity");
+        let good = dir.join("good.db");
+        create_db(&good);
+        assert!(integrity_ok(&good));
+
+        let bad = dir.join("bad.db");
+        fs::write(&bad, b"definitely not a sqlite database").unwrap();
+        assert!(!integrity_ok(&bad));
+    }
+
+    #[test]
+    fn test_move_aside_keeps_sidecars() {
+        let dir = temp_dir("aside");
+        let db = dir.join("tickets.db");
+        fs::write(&db, b"db").unwrap();
+        fs::write(dir.join("tickets.db-wal"), b"wal").unwrap();
+
+        let backup = move_aside(&db, "reset").unwrap();
+        assert!(!db.exists());
+        assert!(backup.to_string_lossy().contains("tickets.db.reset-"));
+        assert_eq!(fs::read(with_suffix(&backup, "-wal")).unwrap(), b"wal");
+    }
+
+    #[test]
+    fn test_salvage_copies_rows() {
+        let dir = temp_dir("salvage");
+        let db = dir.join("tickets.db");
+        create_db(&db);
+
+        let out = salvage(&db).unwrap();
+        let conn = Connection::open(&out).unwrap();
+        let value: String = conn.query_row("SELECT v FROM t", [], |row| row.get(0)).unwrap();
+        assert_eq!(value, "kept");
+    }
+}
@@ -0,0 +1,130 @@
+/// Embedding cache for semantic similar-escalation lookup
+///
+/// Vectors are stored as little-endian f32 BLOBs keyed by escalation. A row is
+/// reused only while both the model and the SHA-256 of the embedded text match,
+/// so switching models or editing a summary triggers a re-embed of just that row.
+use crate::error::AppResult;
+use crate::services::bundle::sha256_hex;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+pub fn encode(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+pub fn decode(bytes: &[u8]) -> Vec<f32> {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+        .collect()
+}
+
+pub fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+
+    let dot: f64 = a.iter().zip(b).map(|(x, y)| *x as f64 * *y as f64).sum();
+    let norm_a: f64 = a.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    let norm_b: f64 = b.iter().map(|x| (*x as f64).powi(2)).sum::<f64>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+
+    dot / (norm_a * norm_b)
+}
+
+/// Escalations with no cached embedding for `model`, or whose text changed since
+pub fn stale(conn: &Connection, model: &str) -> AppResult<Vec<(i64, String)>> {
+    let mut stmt = conn.prepare(
+        "SELECT e.id, e.problem_summary, m.content_hash
+        FROM escalations e
+        LEFT JOIN escalation_embeddings m ON m.escalation_id = e.id AND m.model = ?",
+    )?;
+
+    let rows = stmt
+        .query_map([model], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rows
+        .into_iter()
+        .filter(|(_, text, hash)| hash.as_deref() != Some(sha256_hex(text.as_bytes()).as_str()))
+        .map(|(id, text, _)| (id, text))
+        .collect())
+}
+
+pub fn store(conn: &Connection, escalation_id: i64, model: &str, text: &str, vector: &[f32]) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO escalation_embeddings (escalation_id, model, content_hash, embedding)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(escalation_id) DO UPDATE SET
+            model = excluded.model,
+            content_hash = excluded.content_hash,
+            embedding = excluded.embedding,
+            updated_at = datetime('now')",
+        params![escalation_id, model, sha256_hex(text.as_bytes()), encode(vector)],
+    )?;
+    Ok(())
+}
+
+pub fn load_all(conn: &Connection, model: &str) -> AppResult<HashMap<i64, Vec<f32>>> {
+    let mut stmt = conn.prepare("SELECT escalation_id, embedding FROM escalation_embeddings WHERE model = ?")?;
+
+    let vectors = stmt
+        .query_map([model], |row| {
+            Ok((row.get::<_, i64>(0)?, decode(&row.get::<_, Vec<u8>>(1)?)))
+        })?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok(vectors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY, problem_summary TEXT NOT NULL);
+            INSERT INTO escalations VALUES (1, 'VPN drops'), (2, 'Printer jams');",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/011_escalation_embeddings.sql"))
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_encode_roundtrip() {
+        let vector = vec![0.5, -1.25, 3.0];
+        assert_eq!(decode(&encode(&vector)), vector);
+    }
+
+    #[test]
+    fn test_cosine() {
+        assert!((cosine(&[1.0, 0.0], &[1.0, 0.0]) - 1.0).abs() < 1e-9);
+        assert!(cosine(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-9);
+        assert_eq!(cosine(&[1.0], &[1.0, 2.0]), 0.0);
+    }
+
+    #[test]
+    fn test_only_new_or_edited_are_stale() {
+        let conn = setup();
+        assert_eq!(stale(&conn, "nomic").unwrap().len(), 2);
+
+        store(&conn, 1, "nomic", "VPN drops", &[1.0, 0.0]).unwrap();
+        store(&conn, 2, "nomic", "Printer jams", &[0.0, 1.0]).unwrap();
+        assert!(stale(&conn, "nomic").unwrap().is_empty());
+        assert_eq!(load_all(&conn, "nomic").unwrap()[&2], vec![0.0, 1.0]);
+
+        conn.execute("UPDATE escalations SET problem_summary = 'VPN drops hourly' WHERE id = 1", [])
+            .unwrap();
+        assert_eq!(stale(&conn, "nomic").unwrap(), vec![(1, "VPN drops hourly".to_string())]);
+
+        // A different model has no usable cache
+        assert_eq!(stale(&conn, "mxbai").unwrap().len(), 2);
+    }
+}
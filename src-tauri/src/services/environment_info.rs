@@ -0,0 +1,136 @@
+/// Capture of machine details L2 asks for first
+///
+/// The OS version is always captured; anything else (VPN client, endpoint
+/// agent, browser versions) comes from a command list in Settings, since which
+/// tools matter differs by team. Each command gets a short timeout and only the
+/// first line of its output is kept, so a chatty or hung tool can't stall a
+/// draft or flood the comment.
+use crate::models::{EnvironmentCommand, EnvironmentInfo, EnvironmentItem};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_VALUE_CHARS: usize = 200;
+
+pub fn capture(commands: &[EnvironmentCommand]) -> EnvironmentInfo {
+    let mut items = vec![os_version()];
+    items.extend(commands.iter().map(|command| {
+        item(&command.label, run(&command.program, &command.args, COMMAND_TIMEOUT))
+    }));
+
+    EnvironmentInfo {
+        captured_at: chrono::Utc::now().to_rfc3339(),
+        items,
+    }
+}
+
+fn os_version() -> EnvironmentItem {
+    let version = if cfg!(target_os = "macos") {
+        run("sw_vers", &["-productVersion".to_string()], COMMAND_TIMEOUT).map(|v| format!("macOS {}", v))
+    } else if cfg!(target_os = "windows") {
+        run("cmd", &["/C".to_string(), "ver".to_string()], COMMAND_TIMEOUT)
+    } else {
+        run("uname", &["-sr".to_string()], COMMAND_TIMEOUT)
+    };
+    item("OS", version)
+}
+
+fn item(label: &str, result: Result<String, String>) -> EnvironmentItem {
+    match result {
+        Ok(value) => EnvironmentItem { label: label.to_string(), value: Some(value), error: None },
+        Err(error) => EnvironmentItem { label: label.to_string(), value: None, error: Some(error) },
+    }
+}
+
+/// First non-empty line of the command's output (stdout, else stderr), or why there isn't one
+fn run(program: &str, args: &[String], timeout: Duration) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run {}: {}", program, e))?;
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("{} timed out after {}s", program, timeout.as_secs()));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("{} failed: {}", program, e)),
+        }
+    };
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    if let Some(mut out) = child.stdout.take() {
+        let _ = out.read_to_string(&mut stdout);
+    }
+    if let Some(mut err) = child.stderr.take() {
+        let _ = err.read_to_string(&mut stderr);
+    }
+
+    // Some tools print their version to stderr
+    let line = first_line(&stdout).or_else(|| first_line(&stderr));
+    match (status.success(), line) {
+        (true, Some(line)) => Ok(line),
+        (true, None) => Err(format!("{} printed nothing", program)),
+        (false, line) => Err(format!(
+            "{} exited with {}{}",
+            program,
+            status,
+            line.map(|l| format!(": {}", l)).unwrap_or_default()
+        )),
+    }
+}
+
+fn first_line(output: &str) -> Option<String> {
+    let line = output.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(line.chars().take(MAX_VALUE_CHARS).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_first_line() {
+        assert_eq!(first_line("\n  Cisco AnyConnect 4.10.07061  \nCopyright").as_deref(), Some("Cisco AnyConnect 4.10.07061"));
+        assert_eq!(first_line(" \n"), None);
+        assert_eq!(first_line(&"x".repeat(500)).unwrap().len(), MAX_VALUE_CHARS);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run() {
+        assert_eq!(run("sh", &args(&["-c", "echo 5.1.2; echo more"]), COMMAND_TIMEOUT), Ok("5.1.2".to_string()));
+        assert_eq!(run("sh", &args(&["-c", "echo v9 >&2"]), COMMAND_TIMEOUT), Ok("v9".to_string()));
+        assert!(run("sh", &args(&["-c", "echo broken; exit 3"]), COMMAND_TIMEOUT).unwrap_err().contains("broken"));
+        assert!(run("sh", &args(&["-c", "sleep 5"]), Duration::from_millis(100)).unwrap_err().contains("timed out"));
+        assert!(run("no-such-program-here", &[], COMMAND_TIMEOUT).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_keeps_failures() {
+        let commands = vec![
+            EnvironmentCommand { label: "VPN client".to_string(), program: "sh".to_string(), args: args(&["-c", "echo 4.10"]) },
+            EnvironmentCommand { label: "Agent".to_string(), program: "no-such-program-here".to_string(), args: Vec::new() },
+        ];
+        let info = capture(&commands);
+        let labels: Vec<&str> = info.items.iter().map(|i| i.label.as_str()).collect();
+        assert_eq!(labels, vec!["OS", "VPN client", "Agent"]);
+        assert_eq!(info.items[1].value.as_deref(), Some("4.10"));
+        assert!(info.items[2].value.is_none() && info.items[2].error.is_some());
+    }
+}
@@ -0,0 +1,82 @@
+/// Shared queue of tickets waiting for an L2 handoff
+///
+/// Other tools flag tickets (by label, project or status) rather than assigning
+/// them; a configured JQL query picks those up. The background task polls it
+/// alongside the open-ticket prefetch and the queue view reads this cache, so
+/// agents can take work from inside the app without watching a Jira board.
+use crate::error::{AppError, AppResult};
+use crate::models::{EscalationQueue, OpenTicket};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub fn save(conn: &Connection, tickets: &[OpenTicket], fetched_at: DateTime<Utc>) -> AppResult<()> {
+    let json = serde_json::to_string(tickets)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize tickets: {}", e)))?;
+    conn.execute(
+        "INSERT INTO escalation_queue_cache (id, tickets, fetched_at) VALUES (1, ?1, ?2)
+        ON CONFLICT(id) DO UPDATE SET tickets = ?1, fetched_at = ?2",
+        params![json, fetched_at.to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+pub fn load(conn: &Connection, enabled: bool) -> AppResult<EscalationQueue> {
+    let row: Option<(String, String)> = conn
+        .query_row("SELECT tickets, fetched_at FROM escalation_queue_cache WHERE id = 1", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .optional()?;
+
+    Ok(match row {
+        // An unreadable cache is treated as empty; the next poll overwrites it
+        Some((json, fetched_at)) if enabled => EscalationQueue {
+            enabled,
+            tickets: serde_json::from_str(&json).unwrap_or_default(),
+            fetched_at: Some(fetched_at),
+        },
+        _ => EscalationQueue { enabled, tickets: Vec::new(), fetched_at: None },
+    })
+}
+
+/// The configured queue query, if any
+pub fn configured_jql(jql: Option<&str>) -> Option<&str> {
+    jql.map(str::trim).filter(|jql| !jql.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticket(key: &str) -> OpenTicket {
+        OpenTicket {
+            key: key.to_string(),
+            summary: "Needs network team".to_string(),
+            status: "Open".to_string(),
+            priority: None,
+            updated: None,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/030_escalation_queue_cache.sql")).unwrap();
+        assert!(load(&conn, true).unwrap().fetched_at.is_none());
+
+        save(&conn, &[ticket("SUP-1"), ticket("SUP-2")], Utc::now()).unwrap();
+        let queue = load(&conn, true).unwrap();
+        assert_eq!(queue.tickets.len(), 2);
+        assert!(queue.fetched_at.is_some());
+
+        // A queue turned off doesn't show what it last fetched
+        let off = load(&conn, false).unwrap();
+        assert!(!off.enabled && off.tickets.is_empty());
+    }
+
+    #[test]
+    fn test_configured_jql() {
+        assert_eq!(configured_jql(Some(" labels = needs-escalation ")), Some("labels = needs-escalation"));
+        assert_eq!(configured_jql(Some("  ")), None);
+        assert_eq!(configured_jql(None), None);
+    }
+}
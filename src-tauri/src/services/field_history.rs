@@ -0,0 +1,138 @@
+/// Per-field change history for escalations
+///
+/// The audit log records what happened to an escalation; this records which
+/// field changed and when, so a review can tell whether the problem summary
+/// was edited after it was approved. Values are stored as SHA-256 hashes: two
+/// entries with the same hash held the same text, and no field content is
+/// copied into the history.
+use crate::error::{AppError, AppResult};
+use crate::models::FieldChange;
+use crate::services::bundle::sha256_hex;
+use rusqlite::{params, Connection};
+
+/// Fields whose changes are recorded
+pub const TRACKED_FIELDS: &[&str] = &[
+    "problem_summary",
+    "checklist",
+    "current_status",
+    "next_steps",
+    "llm_summary",
+    "private_notes",
+    "status",
+    "markdown_output",
+    "acknowledged_at",
+    "environment_info",
+    "incident_id",
+];
+
+pub fn hash(value: &str) -> String {
+    sha256_hex(value.as_bytes())
+}
+
+/// Record a change to one field; returns false (and records nothing) when the value didn't change
+pub fn record(
+    conn: &Connection,
+    escalation_id: i64,
+    field: &str,
+    old: Option<&str>,
+    new: Option<&str>,
+) -> AppResult<bool> {
+    ensure_tracked(field)?;
+    let old_hash = old.map(hash);
+    let new_hash = new.map(hash);
+    if old_hash == new_hash {
+        return Ok(false);
+    }
+
+    conn.execute(
+        "INSERT INTO audit_field_history (escalation_id, field, old_hash, new_hash, actor)
+        VALUES (?, ?, ?, ?, (SELECT display_name FROM jira_identity WHERE id = 1))",
+        params![escalation_id, field, old_hash, new_hash],
+    )?;
+    Ok(true)
+}
+
+/// Changes to one field, oldest first
+pub fn history(conn: &Connection, escalation_id: i64, field: &str) -> AppResult<Vec<FieldChange>> {
+    ensure_tracked(field)?;
+    let mut stmt = conn.prepare(
+        "SELECT field, old_hash, new_hash, actor, changed_at FROM audit_field_history
+        WHERE escalation_id = ? AND field = ? ORDER BY id",
+    )?;
+    let changes = stmt
+        .query_map(params![escalation_id, field], |row| {
+            Ok(FieldChange {
+                field: row.get(0)?,
+                old_hash: row.get(1)?,
+                new_hash: row.get(2)?,
+                actor: row.get(3)?,
+                changed_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(changes)
+}
+
+fn ensure_tracked(field: &str) -> AppResult<()> {
+    if TRACKED_FIELDS.contains(&field) {
+        return Ok(());
+    }
+    Err(AppError::Validation(format!(
+        "\"{}\" has no field history (tracked: {})",
+        field,
+        TRACKED_FIELDS.join(", ")
+    )))
+}
+
+/// The current value of a tracked column, for recording what it changed from
+pub fn current(conn: &Connection, escalation_id: i64, column: &str) -> AppResult<Option<String>> {
+    ensure_tracked(column)?;
+    let value = conn.query_row(
+        &format!("SELECT {} FROM escalations WHERE id = ?", column),
+        [escalation_id],
+        |row| row.get(0),
+    )?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/017_jira_identity.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/031_field_history.sql")).unwrap();
+        conn.execute(
+            "INSERT INTO escalations (ticket_id, problem_summary, checklist, current_status, next_steps, status)
+            VALUES ('IT-1', 'VPN drops', '[]', '', '', 'draft')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_records_only_changes() {
+        let conn = setup();
+        assert!(record(&conn, 1, "problem_summary", None, Some("VPN drops")).unwrap());
+        assert!(!record(&conn, 1, "problem_summary", Some("VPN drops"), Some("VPN drops")).unwrap());
+        assert!(record(&conn, 1, "problem_summary", Some("VPN drops"), Some("VPN drops hourly")).unwrap());
+
+        let changes = history(&conn, 1, "problem_summary").unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].old_hash, None);
+        assert_eq!(changes[1].old_hash, changes[0].new_hash);
+        assert_eq!(changes[1].new_hash, Some(hash("VPN drops hourly")));
+        assert!(history(&conn, 1, "status").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_untracked_fields_rejected() {
+        let conn = setup();
+        assert!(record(&conn, 1, "ticket_id", None, Some("IT-1")).is_err());
+        assert!(history(&conn, 1, "id; DROP TABLE escalations").is_err());
+        assert_eq!(current(&conn, 1, "status").unwrap(), Some("draft".to_string()));
+    }
+}
@@ -0,0 +1,134 @@
+/// Change-freeze windows for customer-visible updates
+///
+/// Some orgs don't allow ticket updates during nightly maintenance. Inside a
+/// configured window, posting is refused unless the caller confirms, and a
+/// confirmed post is audited as a freeze override.
+use crate::error::{AppError, AppResult};
+use crate::models::FreezeWindow;
+use chrono::{Datelike, Duration, NaiveDateTime, NaiveTime, Weekday};
+
+const DAYS: [(&str, Weekday); 7] = [
+    ("mon", Weekday::Mon),
+    ("tue", Weekday::Tue),
+    ("wed", Weekday::Wed),
+    ("thu", Weekday::Thu),
+    ("fri", Weekday::Fri),
+    ("sat", Weekday::Sat),
+    ("sun", Weekday::Sun),
+];
+
+/// Reject windows with unreadable times or day names
+pub fn validate(windows: &[FreezeWindow]) -> AppResult<()> {
+    for window in windows {
+        parse_time(&window.start)?;
+        parse_time(&window.end)?;
+        for day in &window.days {
+            parse_day(day)?;
+        }
+    }
+    Ok(())
+}
+
+/// The first window `now` (local time) falls in
+pub fn active(windows: &[FreezeWindow], now: NaiveDateTime) -> Option<&FreezeWindow> {
+    windows.iter().find(|window| contains(window, now).unwrap_or(false))
+}
+
+/// Refuse to post inside a window unless `confirmed`; returns the window a confirmed post overrides
+pub fn check(windows: &[FreezeWindow], now: NaiveDateTime, confirmed: bool) -> AppResult<Option<FreezeWindow>> {
+    match active(windows, now) {
+        None => Ok(None),
+        Some(window) if confirmed => Ok(Some(window.clone())),
+        Some(window) => Err(AppError::Validation(format!(
+            "Posting is frozen during {} ({}–{}). Confirm to post anyway; the override is audited.",
+            window.label, window.start, window.end
+        ))),
+    }
+}
+
+fn contains(window: &FreezeWindow, now: NaiveDateTime) -> AppResult<bool> {
+    let (start, end) = (parse_time(&window.start)?, parse_time(&window.end)?);
+    let time = now.time();
+    let starts_on = |day: Weekday| -> AppResult<bool> {
+        if window.days.is_empty() {
+            return Ok(true);
+        }
+        for name in &window.days {
+            if parse_day(name)? == day {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    };
+
+    if start <= end {
+        return Ok(time >= start && time < end && starts_on(now.weekday())?);
+    }
+    // Overnight: the part after midnight belongs to the previous day's window
+    let yesterday = (now - Duration::days(1)).weekday();
+    Ok((time >= start && starts_on(now.weekday())?) || (time < end && starts_on(yesterday)?))
+}
+
+fn parse_time(time: &str) -> AppResult<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M")
+        .map_err(|_| AppError::Validation(format!("Invalid freeze window time '{}', expected HH:MM", time)))
+}
+
+fn parse_day(day: &str) -> AppResult<Weekday> {
+    let key = day.trim().to_lowercase();
+    DAYS.iter()
+        .find(|(name, _)| key.starts_with(name))
+        .map(|(_, weekday)| *weekday)
+        .ok_or_else(|| AppError::Validation(format!("Invalid freeze window day '{}'", day)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(start: &str, end: &str, days: &[&str]) -> FreezeWindow {
+        FreezeWindow {
+            label: "Nightly maintenance".to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    fn at(datetime: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_same_day_window() {
+        let windows = vec![window("01:00", "03:00", &[])];
+        assert!(active(&windows, at("2024-03-05 01:00")).is_some());
+        assert!(active(&windows, at("2024-03-05 02:59")).is_some());
+        assert!(active(&windows, at("2024-03-05 03:00")).is_none());
+    }
+
+    #[test]
+    fn test_overnight_window_days() {
+        // 2024-03-08 is a Friday
+        let windows = vec![window("22:00", "02:00", &["Fri"])];
+        assert!(active(&windows, at("2024-03-08 23:30")).is_some());
+        assert!(active(&windows, at("2024-03-09 01:30")).is_some());
+        assert!(active(&windows, at("2024-03-09 23:30")).is_none());
+        assert!(active(&windows, at("2024-03-08 01:30")).is_none());
+    }
+
+    #[test]
+    fn test_check_requires_confirmation() {
+        let windows = vec![window("00:00", "23:59", &[])];
+        assert!(check(&windows, at("2024-03-05 12:00"), false).is_err());
+        assert_eq!(check(&windows, at("2024-03-05 12:00"), true).unwrap(), Some(windows[0].clone()));
+        assert_eq!(check(&[], at("2024-03-05 12:00"), false).unwrap(), None);
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&[window("22:00", "02:00", &["mon", "Tuesday"])]).is_ok());
+        assert!(validate(&[window("25:00", "02:00", &[])]).is_err());
+        assert!(validate(&[window("22:00", "02:00", &["someday"])]).is_err());
+    }
+}
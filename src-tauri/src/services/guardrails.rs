@@ -0,0 +1,211 @@
+/// Post-processing for the model's troubleshooting summary
+///
+/// Models occasionally list steps that were never on the checklist, or list a
+/// step under the wrong heading. Every bullet under "Completed" has to fuzzily
+/// match a checked item and every bullet under "Not attempted" an unchecked
+/// one, or it is removed; recommendations are free text and are left alone.
+use crate::models::ChecklistItem;
+
+/// Share of a checklist item's significant words a bullet must mention to match it
+const MIN_WORD_COVERAGE: f64 = 0.6;
+
+/// Endings that make two words the same word ("restart" / "restarted",
+/// "cache" / "cached", "configure" / "configuring", "reset" / "resetting")
+const INFLECTIONS: &[&str] = &["s", "es", "d", "ed", "ing"];
+
+/// Bullets models use to say a section is empty
+const EMPTY_MARKERS: &[&str] = &["none", "n/a", "na", "nothing", "no steps", "-"];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Section {
+    /// Steps the agent did: checked items
+    Completed,
+    /// Steps the agent skipped: unchecked items
+    NotAttempted,
+    /// A steps heading that doesn't say which
+    Steps,
+    Other,
+}
+
+/// Strip unmatched step bullets; returns the cleaned summary and one warning per removal
+pub fn check_summary(summary: &str, checklist: &[ChecklistItem]) -> (String, Vec<String>) {
+    let items: Vec<(Vec<String>, bool)> = checklist.iter().map(|item| (words(&item.text), item.checked)).collect();
+    let mut section = Section::Other;
+    let mut kept = Vec::new();
+    let mut warnings = Vec::new();
+
+    for line in summary.lines() {
+        let trimmed = line.trim();
+        if let Some(next) = heading(trimmed) {
+            section = next;
+            kept.push(line);
+            continue;
+        }
+
+        let bullet = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* "));
+        match bullet {
+            Some(step) if section != Section::Other && !is_empty_marker(step) => {
+                let step_words = words(step);
+                let matched: Vec<bool> = items
+                    .iter()
+                    .filter(|(item, _)| matches(&step_words, item))
+                    .map(|(_, checked)| *checked)
+                    .collect();
+                let fits = match section {
+                    Section::Completed => matched.contains(&true),
+                    Section::NotAttempted => matched.contains(&false),
+                    _ => !matched.is_empty(),
+                };
+
+                if fits {
+                    kept.push(line);
+                } else if matched.is_empty() {
+                    warnings.push(format!("Removed a step that isn't on the checklist: \"{}\"", step.trim()));
+                } else if section == Section::Completed {
+                    warnings.push(format!("Removed a completed step that isn't checked on the checklist: \"{}\"", step.trim()));
+                } else {
+                    warnings.push(format!("Removed a step listed as not attempted that is checked on the checklist: \"{}\"", step.trim()));
+                }
+            }
+            _ => kept.push(line),
+        }
+    }
+
+    (kept.join("\n"), warnings)
+}
+
+fn heading(line: &str) -> Option<Section> {
+    if !line.ends_with(':') {
+        return None;
+    }
+
+    let lower = line.to_lowercase();
+    if lower.contains("recommendation") {
+        Some(Section::Other)
+    } else if lower.contains("not attempted") || lower.contains("not tried") {
+        Some(Section::NotAttempted)
+    } else if lower.contains("completed") {
+        Some(Section::Completed)
+    } else if lower.contains("step") {
+        Some(Section::Steps)
+    } else {
+        None
+    }
+}
+
+fn is_empty_marker(step: &str) -> bool {
+    let step = step.trim().trim_end_matches('.').to_lowercase();
+    step.is_empty() || EMPTY_MARKERS.contains(&step.as_str())
+}
+
+/// Lowercased words of 3+ characters, with URLs and procedure links dropped
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .filter(|w| !w.contains("://"))
+        .flat_map(|w| w.split(|c: char| !c.is_alphanumeric()))
+        .filter(|w| w.len() >= 3)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Equal, or the longer word is the shorter one plus an inflection, allowing for
+/// a dropped final "e" and a doubled final consonant
+fn same_word(a: &str, b: &str) -> bool {
+    if a == b {
+        return true;
+    }
+
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let stems = [short, short.strip_suffix('e').unwrap_or(short)];
+    stems.iter().any(|stem| {
+        let Some(rest) = long.strip_prefix(stem) else {
+            return false;
+        };
+        let undoubled = stem.chars().last().and_then(|last| rest.strip_prefix(last));
+        INFLECTIONS.contains(&rest) || undoubled.is_some_and(|rest| INFLECTIONS.contains(&rest))
+    })
+}
+
+fn matches(step: &[String], item: &[String]) -> bool {
+    if item.is_empty() {
+        return false;
+    }
+
+    let covered = item
+        .iter()
+        .filter(|word| step.iter().any(|candidate| same_word(candidate, word)))
+        .count();
+    covered as f64 / item.len() as f64 >= MIN_WORD_COVERAGE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(text: &str, checked: bool) -> ChecklistItem {
+        ChecklistItem {
+            text: text.to_string(),
+            checked,
+            link: None,
+        }
+    }
+
+    #[test]
+    fn test_strips_invented_steps() {
+        let checklist = vec![item("Restart the VPN client", true), item("Clear DNS cache", false)];
+        let summary = "✓ Completed steps:\n- Restarted the VPN client\n- Reinstalled the network driver\n\n\
+                       ✗ Steps not attempted:\n- Clear the DNS cache\n\n\
+                       ? Recommendations for L2:\n- Check the VPN concentrator logs";
+
+        let (cleaned, warnings) = check_summary(summary, &checklist);
+        assert!(cleaned.contains("- Restarted the VPN client"));
+        assert!(cleaned.contains("- Clear the DNS cache"));
+        assert!(cleaned.contains("- Check the VPN concentrator logs"));
+        assert!(!cleaned.contains("network driver"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Reinstalled the network driver"));
+    }
+
+    #[test]
+    fn test_steps_must_match_their_checked_state() {
+        let checklist = vec![item("Restart the VPN client", true), item("Clear DNS cache", false)];
+        let summary = "✓ Completed steps:\n- Cleared the DNS cache\n\n\
+                       ✗ Steps not attempted:\n- Restart the VPN client";
+
+        let (cleaned, warnings) = check_summary(summary, &checklist);
+        assert_eq!(cleaned, "✓ Completed steps:\n\n✗ Steps not attempted:");
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("isn't checked"));
+        assert!(warnings[1].contains("is checked"));
+    }
+
+    #[test]
+    fn test_same_word() {
+        assert!(same_word("restart", "restarted"));
+        assert!(same_word("cache", "cached"));
+        assert!(same_word("configure", "configuring"));
+        assert!(same_word("reset", "resetting"));
+        assert!(!same_word("configure", "confirm"));
+        assert!(!same_word("connect", "connection"));
+    }
+
+    #[test]
+    fn test_keeps_empty_markers_and_links() {
+        let mut linked = item("Reset MFA token", true);
+        linked.link = Some("https://kb.example.com/mfa".to_string());
+        let summary = "✓ Completed steps:\n- Reset MFA token (procedure: https://kb.example.com/mfa)\n\n\
+                       ✗ Steps not attempted:\n- None";
+
+        let (cleaned, warnings) = check_summary(summary, &[linked]);
+        assert_eq!(cleaned, summary);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unstructured_summary_untouched() {
+        let summary = "- The agent tried everything they could think of";
+        let (cleaned, warnings) = check_summary(summary, &[item("Restart", true)]);
+        assert_eq!(cleaned, summary);
+        assert!(warnings.is_empty());
+    }
+}
@@ -0,0 +1,163 @@
+/// Markdown to standalone HTML for exports and offline viewing
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+const STYLE: &str = "body{font-family:-apple-system,Helvetica,Arial,sans-serif;max-width:820px;margin:2em auto;padding:0 1em;line-height:1.5;color:#172b4d}\
+h2,h3{border-bottom:1px solid #dfe1e6;padding-bottom:.2em}\
+code,pre{background:#f4f5f7;border-radius:3px}pre{padding:.75em;overflow-x:auto}\
+ul{padding-left:1.5em}";
+
+/// Monochrome, paper-sized styling; every section after the first starts a new page
+const PRINT_STYLE: &str = "@page{size:A4;margin:18mm}\
+body{font-family:Georgia,'Times New Roman',serif;font-size:11pt;line-height:1.4;color:#000;background:#fff;margin:0}\
+h2,h3{border-bottom:1px solid #000;padding-bottom:.2em}\
+code,pre{font-family:'Courier New',monospace;border:1px solid #000}pre{padding:.5em;white-space:pre-wrap}\
+a{color:#000;text-decoration:none}a[href]::after{content:\" (\" attr(href) \")\";font-size:9pt}\
+table.meta{border-collapse:collapse;margin-bottom:1em}table.meta td{border:1px solid #000;padding:.2em .6em}\
+section{break-inside:avoid}section+section{break-before:page;page-break-before:always}\
+footer{margin-top:2em;font-size:9pt;border-top:1px solid #000}";
+
+/// Render Markdown written by agents (or copied from tickets) as HTML
+///
+/// The output is served by the local API and opened in browsers, so raw HTML in
+/// the Markdown is shown as text and links other than http(s)/mailto lose their target.
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+
+    let parser = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        Event::Start(Tag::Link { link_type, dest_url, title, id }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image { link_type, dest_url, title, id }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+    let mut out = String::new();
+    html::push_html(&mut out, parser);
+    out
+}
+
+fn safe_url(url: CowStr) -> CowStr {
+    let lower = url.trim_start().to_ascii_lowercase();
+    if ["http://", "https://", "mailto:"].iter().any(|scheme| lower.starts_with(scheme)) {
+        url
+    } else {
+        CowStr::Borrowed("")
+    }
+}
+
+/// Wrap rendered markdown in a complete HTML document
+pub fn render_document(title: &str, markdown: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape(title),
+        STYLE,
+        markdown_to_html(markdown)
+    )
+}
+
+/// Print-optimized document: a metadata table, then one page per `###` section
+pub fn render_print_document(title: &str, meta: &[(&str, String)], markdown: &str, footer: &str) -> String {
+    let rows: String = meta
+        .iter()
+        .map(|(label, value)| format!("<tr><td>{}</td><td>{}</td></tr>\n", escape(label), escape(value)))
+        .collect();
+
+    let sections: String = split_sections(markdown)
+        .iter()
+        .map(|section| format!("<section>\n{}</section>\n", markdown_to_html(section)))
+        .collect();
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<table class=\"meta\">\n{}</table>\n{}<footer>{}</footer>\n</body>\n</html>\n",
+        escape(title),
+        PRINT_STYLE,
+        rows,
+        sections,
+        escape(footer)
+    )
+}
+
+/// Split at `###` headings; anything before the first one stays with the first section
+fn split_sections(markdown: &str) -> Vec<String> {
+    let mut sections: Vec<String> = vec![String::new()];
+    for line in markdown.lines() {
+        let last = sections.last().expect("at least one section");
+        if line.starts_with("### ") && last.lines().any(|l| l.starts_with("### ")) {
+            sections.push(String::new());
+        }
+        let current = sections.last_mut().expect("at least one section");
+        current.push_str(line);
+        current.push('\n');
+    }
+    sections
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_document() {
+        let doc = render_document("Escalation <TEST-1>", "## Summary\n\n- [x] Done");
+        assert!(doc.contains("<title>Escalation &lt;TEST-1&gt;</title>"));
+        assert!(doc.contains("<h2>Summary</h2>"));
+        assert!(doc.contains("checkbox"));
+    }
+
+    #[test]
+    fn test_raw_html_is_shown_as_text() {
+        let html = markdown_to_html("<script>alert(1)</script>\n\nHi <img src=x onerror=alert(1)> there");
+        assert!(!html.contains("<script>"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn test_unsafe_link_targets_are_dropped() {
+        let html = markdown_to_html("[runbook](javascript:alert(1)) and [kb](https://kb.example.com/a)");
+        assert!(!html.contains("javascript:"));
+        assert!(html.contains("href=\"https://kb.example.com/a\""));
+    }
+
+    #[test]
+    fn test_escape_quotes() {
+        assert_eq!(escape(r#"<a href='x'>"#), "&lt;a href=&#39;x&#39;&gt;");
+    }
+
+    #[test]
+    fn test_print_document_sections() {
+        let markdown = "## Escalation: TEST-1\n\n### Problem Summary\nVPN drops\n\n### Next Steps\nCheck RADIUS\n";
+        let doc = render_print_document(
+            "Escalation TEST-1",
+            &[("Ticket", "TEST-1".to_string()), ("Status", "posted".to_string())],
+            markdown,
+            "Printed 2026-01-01",
+        );
+
+        assert_eq!(doc.matches("<section>").count(), 2);
+        assert!(doc.contains("<td>Ticket</td><td>TEST-1</td>"));
+        assert!(doc.contains("page-break-before:always"));
+        // The title heading shares the first page with the first section
+        let first = doc.split("</section>").next().unwrap();
+        assert!(first.contains("Escalation: TEST-1") && first.contains("Problem Summary"));
+    }
+}
@@ -0,0 +1,48 @@
+/// Links from escalations to incident management records
+///
+/// The incident ID is whatever the incident tool calls it (a PagerDuty incident
+/// number, a Statuspage incident code, a Jira incident key), kept as entered so
+/// it can be pasted back into that tool. Matching is case-insensitive, since
+/// agents type keys like `inc-2041` as often as `INC-2041`.
+use crate::error::{AppError, AppResult};
+
+const MAX_LEN: usize = 64;
+
+/// Trimmed ID, or None when blank
+pub fn normalize_id(raw: Option<&str>) -> AppResult<Option<String>> {
+    let Some(id) = raw.map(str::trim).filter(|id| !id.is_empty()) else {
+        return Ok(None);
+    };
+    if id.chars().count() > MAX_LEN {
+        return Err(AppError::Validation(format!(
+            "Incident ID is too long ({} characters max)",
+            MAX_LEN
+        )));
+    }
+    if id.chars().any(char::is_whitespace) {
+        return Err(AppError::Validation(format!(
+            "Incident ID '{}' contains spaces; enter the ID, not a title",
+            id
+        )));
+    }
+    Ok(Some(id.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_id() {
+        assert_eq!(normalize_id(Some("  INC-2041 ")).unwrap().as_deref(), Some("INC-2041"));
+        assert_eq!(normalize_id(Some("Q3T9ZB1A")).unwrap().as_deref(), Some("Q3T9ZB1A"));
+        assert_eq!(normalize_id(Some("   ")).unwrap(), None);
+        assert_eq!(normalize_id(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_normalize_id_rejects_titles() {
+        assert!(normalize_id(Some("VPN outage in EMEA")).is_err());
+        assert!(normalize_id(Some(&"x".repeat(MAX_LEN + 1))).is_err());
+    }
+}
@@ -1,11 +1,16 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{JiraComment, JiraTicket, JiraUser};
+use crate::models::{
+    BackendCapabilities, JiraAccount, JiraComment, JiraIdentity, JiraLinkedIssue, JiraTicket, JiraUser, OpenTicket,
+    OutputFormat, StatusCategory,
+};
 use crate::services::adf;
+use crate::services::output_format;
 use crate::services::retry::retry_with_backoff;
 use crate::services::ticket_system::TicketSystemClient;
 use async_trait::async_trait;
 use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
 
@@ -15,6 +20,7 @@ pub struct JiraClient {
     api_token: String,
     default_client: reqwest::Client,
     upload_client: reqwest::Client,
+    comment_format: OutputFormat,
 }
 
 impl JiraClient {
@@ -35,24 +41,41 @@ impl JiraClient {
             api_token,
             default_client,
             upload_client,
+            comment_format: OutputFormat::Adf,
         })
     }
 
+    /// Post comments as wiki markup or plain text (REST v2) instead of ADF
+    pub fn with_comment_format(mut self, format: OutputFormat) -> Self {
+        self.comment_format = format;
+        self
+    }
+
     fn auth_header(&self) -> String {
         let credentials = format!("{}:{}", self.email, self.api_token);
         let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, credentials.as_bytes());
         format!("Basic {}", encoded)
     }
 
+    /// Fetch a ticket; if Jira refuses the full field set (e.g. no permission on comments),
+    /// fall back to the basics and return it marked `partial` rather than failing
     pub async fn fetch_issue(&self, key: &str) -> AppResult<JiraTicket> {
-        retry_with_backoff(|| self.fetch_issue_impl(key)).await
+        match retry_with_backoff(|| self.fetch_issue_impl(key, FULL_FIELDS)).await {
+            Err(e) if falls_back_to_minimal(&e) => {
+                log::warn!("Full fetch of {} failed ({}); retrying with minimal fields", key, e);
+                retry_with_backoff(|| self.fetch_issue_impl(key, MINIMAL_FIELDS)).await
+            }
+            result => result,
+        }
     }
 
-    async fn fetch_issue_impl(&self, key: &str) -> AppResult<JiraTicket> {
-        let url = format!(
-            "{}/rest/api/3/issue/{}?fields=summary,description,status,reporter,assignee,comment",
-            self.base_url, key
-        );
+    /// Just the fields that classify a ticket (type, components, labels), for template matching
+    pub async fn fetch_issue_classification(&self, key: &str) -> AppResult<JiraTicket> {
+        retry_with_backoff(|| self.fetch_issue_impl(key, CLASSIFICATION_FIELDS)).await
+    }
+
+    async fn fetch_issue_impl(&self, key: &str, fields: &str) -> AppResult<JiraTicket> {
+        let url = format!("{}/rest/api/3/issue/{}?fields={}", self.base_url, key, fields);
 
         let response = self
             .default_client
@@ -82,24 +105,30 @@ impl JiraClient {
         }
 
         let jira_response: JiraIssueResponse = response.json().await?;
+        let missing_sections = missing_sections(&jira_response.fields, fields);
+        let linked_issues = linked_issues(jira_response.fields.issuelinks.unwrap_or_default());
+        let subtasks = jira_response
+            .fields
+            .subtasks
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| s.into_linked("sub-task"))
+            .collect();
 
         Ok(JiraTicket {
             key: jira_response.key,
             summary: jira_response.fields.summary,
             description: jira_response.fields.description.or(Some("No description provided".to_string())),
+            status_category: jira_response.fields.status.category(),
             status: jira_response.fields.status.name,
-            reporter: jira_response.fields.reporter.map(|r| JiraUser {
-                display_name: r.display_name,
-                email: r.email_address,
-            }),
-            assignee: jira_response.fields.assignee.map(|a| JiraUser {
-                display_name: a.display_name,
-                email: a.email_address,
-            }),
+            issue_type: jira_response.fields.issuetype.map(|t| t.name),
+            reporter: jira_response.fields.reporter.map(JiraUser::from),
+            assignee: jira_response.fields.assignee.map(JiraUser::from),
             comments: jira_response
                 .fields
                 .comment
-                .comments
+                .map(|c| c.comments)
+                .unwrap_or_default()
                 .into_iter()
                 .map(|c| JiraComment {
                     author: c.author.display_name,
@@ -107,27 +136,53 @@ impl JiraClient {
                     created: c.created,
                 })
                 .collect(),
+            linked_issues,
+            subtasks,
+            components: jira_response.fields.components.unwrap_or_default().into_iter().map(|c| c.name).collect(),
+            labels: jira_response.fields.labels.unwrap_or_default(),
+            partial: !missing_sections.is_empty(),
+            missing_sections,
         })
     }
 
     pub async fn post_comment(&self, key: &str, body: &str) -> AppResult<()> {
+        self.post_comment_with_id(key, body).await.map(|_| ())
+    }
+
+    /// Post a comment and return the id Jira assigned to it
+    pub async fn post_comment_with_id(&self, key: &str, body: &str) -> AppResult<String> {
         retry_with_backoff(|| self.post_comment_impl(key, body)).await
     }
 
-    async fn post_comment_impl(&self, key: &str, body: &str) -> AppResult<()> {
-        let url = format!("{}/rest/api/3/issue/{}/comment", self.base_url, key);
+    /// Browser URL pointing at a specific comment on a ticket
+    pub fn comment_url(&self, key: &str, comment_id: &str) -> String {
+        format!(
+            "{}/browse/{}?focusedCommentId={}",
+            self.base_url.trim_end_matches('/'),
+            key,
+            comment_id
+        )
+    }
 
-        // Convert markdown to ADF
-        let adf_body = serde_json::json!({
-            "body": adf::markdown_to_adf(body)
-        });
+    async fn post_comment_impl(&self, key: &str, body: &str) -> AppResult<String> {
+        // Cloud takes ADF on v3; wiki markup and plain text go through v2
+        let (url, payload) = match self.comment_format {
+            OutputFormat::Adf | OutputFormat::Markdown => (
+                format!("{}/rest/api/3/issue/{}/comment", self.base_url, key),
+                serde_json::json!({ "body": adf::markdown_to_adf(body) }),
+            ),
+            format => (
+                format!("{}/rest/api/2/issue/{}/comment", self.base_url, key),
+                serde_json::json!({ "body": output_format::render(body, format) }),
+            ),
+        };
 
         let response = self
             .default_client
             .post(&url)
             .header(AUTHORIZATION, self.auth_header())
             .header(CONTENT_TYPE, "application/json")
-            .json(&adf_body)
+            .json(&payload)
             .send()
             .await?;
 
@@ -141,14 +196,20 @@ impl JiraClient {
             return Err(AppError::Jira(format!("Failed to post comment: {}", status)));
         }
 
-        Ok(())
+        let created: JiraCreatedCommentResponse = response.json().await?;
+        Ok(created.id)
     }
 
     pub async fn attach_file(&self, key: &str, file_path: &Path) -> AppResult<()> {
+        self.attach_file_with_url(key, file_path).await.map(|_| ())
+    }
+
+    /// Attach a file and return its download URL, when Jira reports one
+    pub async fn attach_file_with_url(&self, key: &str, file_path: &Path) -> AppResult<Option<String>> {
         retry_with_backoff(|| self.attach_file_impl(key, file_path)).await
     }
 
-    async fn attach_file_impl(&self, key: &str, file_path: &Path) -> AppResult<()> {
+    async fn attach_file_impl(&self, key: &str, file_path: &Path) -> AppResult<Option<String>> {
         // Validate file exists and size
         let metadata = tokio::fs::metadata(file_path)
             .await
@@ -203,10 +264,294 @@ impl JiraClient {
             return Err(AppError::Jira(format!("Failed to attach file: {}", status)));
         }
 
+        // The upload already succeeded; a response we can't read just means no link
+        let attached: Vec<JiraAttachmentResponse> = response.json().await.unwrap_or_default();
+        Ok(attached.into_iter().next().and_then(|a| a.content))
+    }
+
+    /// Create an issue and return its key. Not retried: a timed-out create may
+    /// still have succeeded and retrying would mint a duplicate ticket.
+    pub async fn create_issue(
+        &self,
+        project: &str,
+        issue_type: &str,
+        summary: &str,
+        description: &str,
+    ) -> AppResult<String> {
+        let url = format!("{}/rest/api/3/issue", self.base_url);
+
+        let body = serde_json::json!({
+            "fields": {
+                "project": { "key": project },
+                "issuetype": { "name": issue_type },
+                "summary": summary,
+                "description": adf::markdown_to_adf(description),
+            }
+        });
+
+        let response = self
+            .default_client
+            .post(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to create issues in {}. Check your API token permissions.",
+                project
+            )));
+        } else if status == 400 {
+            // Jira explains invalid project/issue type/required fields in the body
+            let detail = response.text().await.unwrap_or_default();
+            return Err(AppError::Jira(format!("Jira rejected the new issue: {}", detail)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to create issue: {}", status)));
+        }
+
+        let created: JiraCreatedIssueResponse = response.json().await?;
+        Ok(created.key)
+    }
+
+    /// Every comment on an issue, oldest first, paging through Jira's 100-comment pages
+    pub async fn list_comments(&self, key: &str) -> AppResult<Vec<IssueComment>> {
+        let mut comments = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let page = retry_with_backoff(|| self.list_comments_page(key, start_at)).await?;
+            let next = page.next_start();
+            comments.extend(page.comments.into_iter().map(|c| IssueComment {
+                id: c.id,
+                author: c.author.display_name,
+                body_text: adf::adf_to_plain_text(&c.body),
+                created: c.created,
+            }));
+
+            match next {
+                Some(next) => start_at = next,
+                None => return Ok(comments),
+            }
+        }
+    }
+
+    async fn list_comments_page(&self, key: &str, start_at: u32) -> AppResult<JiraCommentPage> {
+        let url = format!(
+            "{}/rest/api/3/issue/{}/comment?startAt={}&maxResults=100&orderBy=created",
+            self.base_url, key, start_at
+        );
+
+        let response = self
+            .default_client
+            .get(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to list comments: {}", status)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    pub async fn add_remote_link(&self, key: &str, url: &str, title: &str) -> AppResult<()> {
+        retry_with_backoff(|| self.add_remote_link_impl(key, url, title)).await
+    }
+
+    async fn add_remote_link_impl(&self, key: &str, url: &str, title: &str) -> AppResult<()> {
+        let endpoint = format!("{}/rest/api/3/issue/{}/remotelink", self.base_url, key);
+
+        // Using the URL as globalId makes Jira update an existing link instead of duplicating it
+        let body = serde_json::json!({
+            "globalId": url,
+            "object": {
+                "url": url,
+                "title": title,
+            }
+        });
+
+        let response = self
+            .default_client
+            .post(&endpoint)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to link resources to {}. Check your API token permissions.",
+                key
+            )));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to add remote link: {}", status)));
+        }
+
         Ok(())
     }
 
-    pub async fn test_connection(&self) -> AppResult<String> {
+    /// Set the ticket's priority by name (e.g. "High")
+    pub async fn set_priority(&self, key: &str, priority: &str) -> AppResult<()> {
+        retry_with_backoff(|| self.set_priority_impl(key, priority)).await
+    }
+
+    async fn set_priority_impl(&self, key: &str, priority: &str) -> AppResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url, key);
+        let body = serde_json::json!({ "fields": { "priority": { "name": priority } } });
+
+        let response = self
+            .default_client
+            .put(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to change the priority of {}. Check your API token permissions.",
+                key
+            )));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if status == 400 {
+            return Err(AppError::Jira(format!(
+                "Jira rejected priority \"{}\" for {}",
+                priority, key
+            )));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to set priority: {}", status)));
+        }
+
+        Ok(())
+    }
+
+    /// Add components to the ticket by name, keeping the ones it already has
+    pub async fn add_components(&self, key: &str, components: &[String]) -> AppResult<()> {
+        retry_with_backoff(|| self.add_components_impl(key, components)).await
+    }
+
+    async fn add_components_impl(&self, key: &str, components: &[String]) -> AppResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}", self.base_url, key);
+        let adds: Vec<_> = components
+            .iter()
+            .map(|name| serde_json::json!({ "add": { "name": name } }))
+            .collect();
+        let body = serde_json::json!({ "update": { "components": adds } });
+
+        let response = self
+            .default_client
+            .put(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to change the components of {}. Check your API token permissions.",
+                key
+            )));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if status == 400 {
+            return Err(AppError::Jira(format!(
+                "Jira rejected components \"{}\" for {}",
+                components.join(", "),
+                key
+            )));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to add components: {}", status)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn assign_issue(&self, key: &str, account_id: &str) -> AppResult<()> {
+        retry_with_backoff(|| self.assign_issue_impl(key, account_id)).await
+    }
+
+    async fn assign_issue_impl(&self, key: &str, account_id: &str) -> AppResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}/assignee", self.base_url, key);
+
+        let response = self
+            .default_client
+            .put(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&serde_json::json!({ "accountId": account_id }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to assign {}. Check your API token permissions.",
+                key
+            )));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to assign ticket: {}", status)));
+        }
+
+        Ok(())
+    }
+
+    /// Add the authenticated user to the ticket's watcher list
+    pub async fn watch_issue(&self, key: &str) -> AppResult<()> {
+        let myself = self.fetch_myself().await?;
+        retry_with_backoff(|| self.add_watcher_impl(key, &myself.account_id)).await
+    }
+
+    async fn add_watcher_impl(&self, key: &str, account_id: &str) -> AppResult<()> {
+        let url = format!("{}/rest/api/3/issue/{}/watchers", self.base_url, key);
+
+        // The watchers endpoint takes a bare JSON string as its body
+        let response = self
+            .default_client
+            .post(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .json(&account_id)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 403 {
+            return Err(AppError::Jira(format!(
+                "No permission to watch {}. Check your API token permissions.",
+                key
+            )));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to add watcher: {}", status)));
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_myself(&self) -> AppResult<JiraMyselfResponse> {
         let url = format!("{}/rest/api/3/myself", self.base_url);
 
         let response = self
@@ -221,12 +566,205 @@ impl JiraClient {
         if status == 401 {
             return Err(AppError::Jira("Invalid credentials".to_string()));
         } else if !status.is_success() {
-            return Err(AppError::Jira(format!("Connection test failed: {}", status)));
+            return Err(AppError::Jira(format!("Failed to fetch current user: {}", status)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Search users by name or email; app and customer accounts are filtered out
+    pub async fn search_users(&self, query: &str) -> AppResult<Vec<JiraAccount>> {
+        retry_with_backoff(|| self.search_users_impl(query)).await
+    }
+
+    async fn search_users_impl(&self, query: &str) -> AppResult<Vec<JiraAccount>> {
+        let url = format!("{}/rest/api/3/user/search", self.base_url);
+
+        let response = self
+            .default_client
+            .get(&url)
+            .query(&[("query", query), ("maxResults", "20")])
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if status == 403 {
+            return Err(AppError::Jira(
+                "Permission denied: browsing users requires the 'Browse users and groups' permission".to_string(),
+            ));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to search users: {}", status)));
+        }
+
+        let users: Vec<JiraAccountResponse> = response.json().await?;
+        Ok(people_only(users))
+    }
+
+    /// Issues matching a JQL query, most recently updated first (first page only)
+    pub async fn search_issues(&self, jql: &str, max_results: u32) -> AppResult<Vec<OpenTicket>> {
+        retry_with_backoff(|| self.search_issues_impl(jql, max_results)).await
+    }
+
+    async fn search_issues_impl(&self, jql: &str, max_results: u32) -> AppResult<Vec<OpenTicket>> {
+        let url = format!("{}/rest/api/3/search/jql", self.base_url);
+        let max_results = max_results.to_string();
+
+        let response = self
+            .default_client
+            .get(&url)
+            .query(&[
+                ("jql", jql),
+                ("fields", "summary,status,priority,updated"),
+                ("maxResults", max_results.as_str()),
+            ])
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if status == 400 {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::Jira(format!("Jira rejected the JQL query: {}", body)));
+        } else if status == 429 {
+            return Err(AppError::Jira("Rate limited while searching issues".to_string()));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to search issues: {}", status)));
+        }
+
+        let page: JiraSearchResponse = response.json().await?;
+        Ok(page.issues.into_iter().map(JiraSearchIssue::into_open_ticket).collect())
+    }
+
+    /// Whether the instance accepts attachments, and its per-file upload limit
+    pub async fn attachment_settings(&self) -> AppResult<AttachmentSettings> {
+        retry_with_backoff(|| self.attachment_settings_impl()).await
+    }
+
+    async fn attachment_settings_impl(&self) -> AppResult<AttachmentSettings> {
+        let url = format!("{}/rest/api/3/attachment/meta", self.base_url);
+
+        let response = self
+            .default_client
+            .get(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to read attachment settings: {}", status)));
+        }
+
+        let meta: JiraAttachmentMetaResponse = response.json().await?;
+        Ok(AttachmentSettings {
+            enabled: meta.enabled,
+            upload_limit: meta.upload_limit,
+        })
+    }
+
+    /// What the site and the configured account's permissions allow, on one ticket when `issue_key` is given
+    /// (otherwise in any project). Transitions and internal comments aren't implemented by this client.
+    pub async fn capabilities_for(&self, issue_key: Option<&str>) -> AppResult<BackendCapabilities> {
+        let settings = self.attachment_settings().await?;
+        let permissions = retry_with_backoff(|| self.my_permissions_impl(issue_key)).await?;
+        Ok(capabilities_from(settings.enabled, &permissions))
+    }
+
+    async fn my_permissions_impl(&self, issue_key: Option<&str>) -> AppResult<JiraPermissionsResponse> {
+        let mut url = format!("{}/rest/api/3/mypermissions?permissions={}", self.base_url, CAPABILITY_PERMISSIONS);
+        if let Some(key) = issue_key {
+            url.push_str(&format!("&issueKey={}", key));
         }
 
-        let myself: JiraMyselfResponse = response.json().await?;
+        let response = self
+            .default_client
+            .get(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to read permissions: {}", status)));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Total size in bytes of the files already attached to a ticket
+    pub async fn attachment_bytes(&self, key: &str) -> AppResult<u64> {
+        let attachments = self.list_attachments(key).await?;
+        Ok(attachments.iter().map(|a| a.size).sum())
+    }
+
+    /// Files already attached to a ticket
+    pub async fn list_attachments(&self, key: &str) -> AppResult<Vec<TicketAttachment>> {
+        retry_with_backoff(|| self.list_attachments_impl(key)).await
+    }
+
+    async fn list_attachments_impl(&self, key: &str) -> AppResult<Vec<TicketAttachment>> {
+        let url = format!("{}/rest/api/3/issue/{}?fields=attachment", self.base_url, key);
+
+        let response = self
+            .default_client
+            .get(&url)
+            .header(AUTHORIZATION, self.auth_header())
+            .header(CONTENT_TYPE, "application/json")
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == 401 {
+            return Err(AppError::Jira("Invalid credentials".to_string()));
+        } else if status == 404 {
+            return Err(AppError::NotFound(format!("Ticket {} not found", key)));
+        } else if !status.is_success() {
+            return Err(AppError::Jira(format!("Failed to list attachments: {}", status)));
+        }
+
+        let issue: JiraAttachmentsIssueResponse = response.json().await?;
+        Ok(issue.fields.attachment)
+    }
+
+    pub async fn test_connection(&self) -> AppResult<String> {
+        let myself = self.fetch_myself().await?;
         Ok(myself.display_name)
     }
+
+    /// Who the configured credentials authenticate as
+    pub async fn fetch_identity(&self) -> AppResult<JiraIdentity> {
+        Ok(self.fetch_myself().await?.into())
+    }
+}
+
+/// A ticket comment with its ADF body flattened to plain text
+#[derive(Debug, Clone)]
+pub struct IssueComment {
+    pub id: String,
+    pub author: String,
+    pub body_text: String,
+    pub created: String,
+}
+
+/// Attachment support as reported by `/rest/api/3/attachment/meta`
+#[derive(Debug, Clone)]
+pub struct AttachmentSettings {
+    pub enabled: bool,
+    /// Largest single file the instance accepts, in bytes
+    pub upload_limit: u64,
 }
 
 // Jira API response structures
@@ -241,14 +779,158 @@ struct JiraFields {
     summary: String,
     description: Option<String>,
     status: JiraStatus,
+    #[serde(default)]
+    issuetype: Option<JiraIssueTypeResponse>,
     reporter: Option<JiraUserResponse>,
     assignee: Option<JiraUserResponse>,
-    comment: JiraComments,
+    // Sections below are absent when not requested or hidden from this user
+    #[serde(default)]
+    comment: Option<JiraComments>,
+    #[serde(default)]
+    issuelinks: Option<Vec<JiraIssueLinkResponse>>,
+    #[serde(default)]
+    subtasks: Option<Vec<JiraIssueRefResponse>>,
+    #[serde(default)]
+    components: Option<Vec<JiraComponentResponse>>,
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+}
+
+const FULL_FIELDS: &str =
+    "summary,description,status,issuetype,reporter,assignee,comment,issuelinks,subtasks,components,labels";
+const MINIMAL_FIELDS: &str = "summary,status,issuetype,reporter,assignee";
+const CLASSIFICATION_FIELDS: &str = "summary,status,issuetype,components,labels";
+
+/// Sections of the ticket Jira didn't return, named as on `JiraTicket`.
+/// A hidden description comes back as null like an empty one, so it only counts when not requested.
+fn missing_sections(fields: &JiraFields, requested: &str) -> Vec<String> {
+    let requested: Vec<&str> = requested.split(',').collect();
+    [
+        ("description", !requested.contains(&"description")),
+        ("comments", fields.comment.is_none()),
+        ("linked_issues", fields.issuelinks.is_none()),
+        ("subtasks", fields.subtasks.is_none()),
+        ("components", fields.components.is_none()),
+        ("labels", fields.labels.is_none()),
+    ]
+    .into_iter()
+    .filter(|(_, missing)| *missing)
+    .map(|(section, _)| section.to_string())
+    .collect()
+}
+
+/// Failures a smaller field set may get past: a field the user can't read (400/403),
+/// one Jira fails to render (500), or one in a shape we can't parse
+fn falls_back_to_minimal(error: &AppError) -> bool {
+    match error {
+        AppError::Jira(msg) => ["400", "403", "500"].iter().any(|code| msg.contains(code)),
+        AppError::Http(e) => e.is_decode(),
+        _ => false,
+    }
 }
 
 #[derive(Debug, Deserialize)]
+struct JiraIssueTypeResponse {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraComponentResponse {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraIssueLinkResponse {
+    #[serde(rename = "type")]
+    link_type: JiraIssueLinkType,
+    inward_issue: Option<JiraIssueRefResponse>,
+    outward_issue: Option<JiraIssueRefResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueLinkType {
+    inward: String,
+    outward: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueRefResponse {
+    key: String,
+    fields: JiraIssueRefFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueRefFields {
+    summary: String,
+    status: JiraStatus,
+}
+
+impl JiraIssueRefResponse {
+    fn into_linked(self, relation: &str) -> JiraLinkedIssue {
+        JiraLinkedIssue {
+            key: self.key,
+            summary: self.fields.summary,
+            status: self.fields.status.name,
+            relation: relation.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct JiraStatus {
     name: String,
+    #[serde(default)]
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    key: String,
+}
+
+impl JiraStatus {
+    fn category(&self) -> Option<StatusCategory> {
+        self.status_category.as_ref().and_then(|c| StatusCategory::from_key(&c.key))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraSearchResponse {
+    #[serde(default)]
+    issues: Vec<JiraSearchIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraSearchIssue {
+    key: String,
+    fields: JiraSearchFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraSearchFields {
+    summary: String,
+    status: JiraStatus,
+    priority: Option<JiraPriority>,
+    updated: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraPriority {
+    name: String,
+}
+
+impl JiraSearchIssue {
+    fn into_open_ticket(self) -> OpenTicket {
+        OpenTicket {
+            key: self.key,
+            summary: self.fields.summary,
+            status: self.fields.status.name,
+            priority: self.fields.priority.map(|p| p.name),
+            updated: self.fields.updated,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -256,6 +938,23 @@ struct JiraStatus {
 struct JiraUserResponse {
     display_name: String,
     email_address: Option<String>,
+    #[serde(default)]
+    avatar_urls: HashMap<String, String>,
+}
+
+impl From<JiraUserResponse> for JiraUser {
+    fn from(user: JiraUserResponse) -> Self {
+        JiraUser {
+            avatar_url: largest_avatar(&user.avatar_urls),
+            display_name: user.display_name,
+            email: user.email_address,
+        }
+    }
+}
+
+/// Jira returns 16/24/32/48px variants; take the largest
+fn largest_avatar(avatar_urls: &HashMap<String, String>) -> Option<String> {
+    avatar_urls.get("48x48").or_else(|| avatar_urls.values().next()).cloned()
 }
 
 #[derive(Debug, Deserialize)]
@@ -270,10 +969,134 @@ struct JiraCommentResponse {
     created: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct JiraCreatedCommentResponse {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraAttachmentResponse {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraAttachmentMetaResponse {
+    enabled: bool,
+    upload_limit: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraAttachmentsIssueResponse {
+    fields: JiraAttachmentsFields,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraAttachmentsFields {
+    #[serde(default)]
+    attachment: Vec<TicketAttachment>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TicketAttachment {
+    #[serde(default)]
+    pub filename: String,
+    pub size: u64,
+    /// Download URL
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraCreatedIssueResponse {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraCommentPage {
+    #[serde(rename = "startAt", default)]
+    start_at: u32,
+    #[serde(default)]
+    total: u32,
+    comments: Vec<JiraAdfCommentResponse>,
+}
+
+impl JiraCommentPage {
+    /// Where the next page starts, or None once every comment has been read
+    fn next_start(&self) -> Option<u32> {
+        let next = self.start_at + self.comments.len() as u32;
+        (!self.comments.is_empty() && next < self.total).then_some(next)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraAdfCommentResponse {
+    id: String,
+    author: JiraUserResponse,
+    body: serde_json::Value,
+    created: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraAccountResponse {
+    account_id: String,
+    account_type: Option<String>,
+    display_name: String,
+    email_address: Option<String>,
+    #[serde(default)]
+    active: bool,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct JiraMyselfResponse {
+    account_id: String,
     display_name: String,
+    email_address: Option<String>,
+    #[serde(default)]
+    avatar_urls: HashMap<String, String>,
+}
+
+impl From<JiraMyselfResponse> for JiraIdentity {
+    fn from(myself: JiraMyselfResponse) -> Self {
+        let avatar_url = largest_avatar(&myself.avatar_urls);
+
+        JiraIdentity {
+            account_id: myself.account_id,
+            display_name: myself.display_name,
+            email: myself.email_address,
+            avatar_url,
+            fetched_at: None,
+        }
+    }
+}
+
+/// Keep human accounts; bots ("app") and service desk customers can't be assigned
+fn people_only(users: Vec<JiraAccountResponse>) -> Vec<JiraAccount> {
+    users
+        .into_iter()
+        .filter(|u| u.account_type.as_deref().unwrap_or("atlassian") == "atlassian")
+        .map(|u| JiraAccount {
+            account_id: u.account_id,
+            display_name: u.display_name,
+            email: u.email_address,
+            active: u.active,
+        })
+        .collect()
+}
+
+/// Each link carries only the other end; its relation is phrased from this ticket's side
+/// (an inward issue "is caused by" ours reads as "is caused by <key>")
+fn linked_issues(links: Vec<JiraIssueLinkResponse>) -> Vec<JiraLinkedIssue> {
+    links
+        .into_iter()
+        .filter_map(|link| match (link.inward_issue, link.outward_issue) {
+            (Some(issue), _) => Some(issue.into_linked(&link.link_type.inward)),
+            (None, Some(issue)) => Some(issue.into_linked(&link.link_type.outward)),
+            (None, None) => None,
+        })
+        .collect()
 }
 
 #[async_trait]
@@ -289,6 +1112,40 @@ impl TicketSystemClient for JiraClient {
     async fn test_connection(&self) -> AppResult<String> {
         self.test_connection().await
     }
+
+    async fn capabilities(&self) -> AppResult<BackendCapabilities> {
+        self.capabilities_for(None).await
+    }
+}
+
+/// Permissions that gate the optional actions in `BackendCapabilities`
+const CAPABILITY_PERMISSIONS: &str = "CREATE_ATTACHMENTS,LINK_ISSUES,EDIT_ISSUES,ASSIGN_ISSUES";
+
+#[derive(Debug, Deserialize)]
+struct JiraPermissionsResponse {
+    permissions: HashMap<String, JiraPermission>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraPermission {
+    #[serde(rename = "havePermission")]
+    have_permission: bool,
+}
+
+fn capabilities_from(attachments_enabled: bool, response: &JiraPermissionsResponse) -> BackendCapabilities {
+    let has = |key: &str| response.permissions.get(key).is_some_and(|p| p.have_permission);
+    BackendCapabilities {
+        backend: "jira".to_string(),
+        attachments: attachments_enabled && has("CREATE_ATTACHMENTS"),
+        internal_comments: false,
+        transitions: false,
+        // Anyone who can see the ticket can watch it
+        watchers: true,
+        remote_links: has("LINK_ISSUES"),
+        priority: has("EDIT_ISSUES"),
+        components: has("EDIT_ISSUES"),
+        assign: has("ASSIGN_ISSUES"),
+    }
 }
 
 #[cfg(test)]
@@ -307,4 +1164,182 @@ mod tests {
         let auth = client.auth_header();
         assert!(auth.starts_with("Basic "));
     }
+
+    #[test]
+    fn test_capabilities_from_permissions() {
+        let response: JiraPermissionsResponse = serde_json::from_str(
+            r#"{"permissions": {
+                "CREATE_ATTACHMENTS": {"id": "19", "key": "CREATE_ATTACHMENTS", "havePermission": true},
+                "LINK_ISSUES": {"id": "21", "key": "LINK_ISSUES", "havePermission": false},
+                "EDIT_ISSUES": {"id": "12", "key": "EDIT_ISSUES", "havePermission": true},
+                "ASSIGN_ISSUES": {"id": "13", "key": "ASSIGN_ISSUES", "havePermission": false}
+            }}"#,
+        )
+        .unwrap();
+
+        let capabilities = capabilities_from(true, &response);
+        assert!(capabilities.attachments);
+        assert!(!capabilities.remote_links);
+        assert!(capabilities.priority);
+        assert!(capabilities.components);
+        assert!(!capabilities.assign);
+        assert!(!capabilities.transitions);
+
+        // Attachments switched off site-wide win over the permission
+        assert!(!capabilities_from(false, &response).attachments);
+    }
+
+    #[test]
+    fn test_people_only() {
+        let users: Vec<JiraAccountResponse> = serde_json::from_str(
+            r#"[
+                {"accountId": "5b10a", "accountType": "atlassian", "displayName": "Dana L2", "active": true},
+                {"accountId": "5b10b", "accountType": "app", "displayName": "Automation for Jira", "active": true},
+                {"accountId": "qm:1", "accountType": "customer", "displayName": "Customer", "active": true}
+            ]"#,
+        )
+        .unwrap();
+
+        let accounts = people_only(users);
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].account_id, "5b10a");
+        assert!(accounts[0].email.is_none());
+    }
+
+    #[test]
+    fn test_linked_issues() {
+        let fields: JiraFields = serde_json::from_str(
+            r#"{
+                "summary": "VPN drops",
+                "description": null,
+                "status": {"name": "Open"},
+                "reporter": null,
+                "assignee": null,
+                "comment": {"comments": []},
+                "issuelinks": [
+                    {
+                        "type": {"name": "Problem/Incident", "inward": "is caused by", "outward": "causes"},
+                        "inwardIssue": {"key": "PRB-12", "fields": {"summary": "VPN concentrator firmware", "status": {"name": "Known Error"}}}
+                    },
+                    {
+                        "type": {"name": "Relates", "inward": "relates to", "outward": "relates to"},
+                        "outwardIssue": {"key": "IT-9", "fields": {"summary": "Office WiFi", "status": {"name": "Done"}}}
+                    }
+                ],
+                "subtasks": [
+                    {"key": "IT-101", "fields": {"summary": "Collect logs", "status": {"name": "To Do"}}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let links = linked_issues(fields.issuelinks.unwrap());
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].key, "PRB-12");
+        assert_eq!(links[0].relation, "is caused by");
+        assert_eq!(links[0].status, "Known Error");
+        assert_eq!(links[1].relation, "relates to");
+
+        let subtask = fields.subtasks.unwrap().into_iter().next().unwrap().into_linked("sub-task");
+        assert_eq!(subtask.key, "IT-101");
+    }
+
+    #[test]
+    fn test_status_category_and_avatars() {
+        let fields: JiraFields = serde_json::from_str(
+            r#"{
+                "summary": "VPN drops",
+                "description": null,
+                "status": {"name": "Waiting for Vendor", "statusCategory": {"key": "indeterminate", "name": "In Progress"}},
+                "issuetype": {"name": "Incident"},
+                "reporter": {"displayName": "Sam User", "avatarUrls": {"24x24": "https://avatar/24", "48x48": "https://avatar/48"}},
+                "assignee": {"displayName": "Dana Agent", "emailAddress": "dana@example.com"},
+                "comment": {"comments": []}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(fields.status.category(), Some(StatusCategory::InProgress));
+        assert_eq!(fields.issuetype.unwrap().name, "Incident");
+        let reporter = JiraUser::from(fields.reporter.unwrap());
+        assert_eq!(reporter.avatar_url.as_deref(), Some("https://avatar/48"));
+        assert!(JiraUser::from(fields.assignee.unwrap()).avatar_url.is_none());
+        assert_eq!(StatusCategory::from_key("undefined"), None);
+    }
+
+    #[test]
+    fn test_missing_sections() {
+        // Comments hidden by permissions: Jira leaves the field out instead of failing
+        let hidden: JiraFields = serde_json::from_str(
+            r#"{"summary": "VPN drops", "description": "x", "status": {"name": "Open"},
+                "issuelinks": [], "subtasks": [], "components": [], "labels": []}"#,
+        )
+        .unwrap();
+        assert_eq!(missing_sections(&hidden, FULL_FIELDS), vec!["comments"]);
+
+        let minimal: JiraFields =
+            serde_json::from_str(r#"{"summary": "VPN drops", "status": {"name": "Open"}}"#).unwrap();
+        assert_eq!(
+            missing_sections(&minimal, MINIMAL_FIELDS),
+            vec!["description", "comments", "linked_issues", "subtasks", "components", "labels"]
+        );
+    }
+
+    #[test]
+    fn test_falls_back_to_minimal() {
+        assert!(falls_back_to_minimal(&AppError::Jira("Jira server error: 403 Forbidden".to_string())));
+        assert!(falls_back_to_minimal(&AppError::Jira("Jira server error: 400 Bad Request".to_string())));
+        assert!(!falls_back_to_minimal(&AppError::Jira("Invalid credentials".to_string())));
+        assert!(!falls_back_to_minimal(&AppError::NotFound("Ticket IT-1 not found".to_string())));
+    }
+
+    #[test]
+    fn test_identity_from_myself() {
+        let myself: JiraMyselfResponse = serde_json::from_str(
+            r#"{
+                "accountId": "5b10a",
+                "displayName": "Dana Agent",
+                "emailAddress": "dana@example.com",
+                "avatarUrls": {"24x24": "https://avatar/24", "48x48": "https://avatar/48"}
+            }"#,
+        )
+        .unwrap();
+
+        let identity = JiraIdentity::from(myself);
+        assert_eq!(identity.account_id, "5b10a");
+        assert_eq!(identity.avatar_url.as_deref(), Some("https://avatar/48"));
+    }
+
+    #[test]
+    fn test_comment_url() {
+        let client = JiraClient::new(
+            "https://test.atlassian.net/".to_string(),
+            "test@example.com".to_string(),
+            "token123".to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            client.comment_url("SANDBOX-1", "10042"),
+            "https://test.atlassian.net/browse/SANDBOX-1?focusedCommentId=10042"
+        );
+    }
+
+    #[test]
+    fn test_comment_page_next_start() {
+        let comment = r#"{"id": "1", "author": {"displayName": "Lee"}, "body": {}, "created": "2024-01-01"}"#;
+        let page = |start_at: u32, count: usize, total: u32| -> JiraCommentPage {
+            let comments = vec![comment; count].join(",");
+            serde_json::from_str(&format!(
+                r#"{{"startAt": {}, "total": {}, "comments": [{}]}}"#,
+                start_at, total, comments
+            ))
+            .unwrap()
+        };
+
+        assert_eq!(page(0, 100, 250).next_start(), Some(100));
+        assert_eq!(page(200, 50, 250).next_start(), None);
+        // An empty page ends the walk even if the total is stale
+        assert_eq!(page(100, 0, 250).next_start(), None);
+    }
 }
@@ -0,0 +1,139 @@
+/// Persistent record of in-flight posts and uploads
+///
+/// A job row is written before a post or upload starts and removed when it
+/// finishes, successfully or not (failures are already persisted on the
+/// escalation). Rows left `running` by a quit or crash become `interrupted`
+/// and are offered for resumption on the next launch.
+use crate::error::{AppError, AppResult};
+use crate::models::InterruptedJob;
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Running jobs older than this at startup were left behind by a crashed instance
+pub const STALE_JOB_MINUTES: i64 = 30;
+
+pub const KIND_POST: &str = "post";
+pub const KIND_RETRY_POST: &str = "retry_post";
+pub const KIND_RETRY_ATTACHMENTS: &str = "retry_attachments";
+
+pub fn start(
+    conn: &Connection,
+    kind: &str,
+    escalation_id: i64,
+    file_paths: &[String],
+    instance_id: &str,
+) -> AppResult<i64> {
+    let file_paths = serde_json::to_string(file_paths)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize job files: {}", e)))?;
+
+    conn.execute(
+        "INSERT INTO jobs (kind, escalation_id, file_paths, instance_id) VALUES (?, ?, ?, ?)",
+        params![kind, escalation_id, file_paths, instance_id],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn finish(conn: &Connection, job_id: i64) -> AppResult<()> {
+    conn.execute("DELETE FROM jobs WHERE id = ?", [job_id])?;
+    Ok(())
+}
+
+/// Mark this instance's running jobs interrupted (called when the app is quitting)
+pub fn interrupt_instance(conn: &Connection, instance_id: &str) -> AppResult<usize> {
+    Ok(conn.execute(
+        "UPDATE jobs SET state = 'interrupted', interrupted_at = datetime('now')
+        WHERE state = 'running' AND instance_id = ?",
+        [instance_id],
+    )?)
+}
+
+/// Mark running jobs that no live instance can still be working on (called at startup)
+pub fn interrupt_stale(conn: &Connection) -> AppResult<usize> {
+    Ok(conn.execute(
+        "UPDATE jobs SET state = 'interrupted', interrupted_at = datetime('now')
+        WHERE state = 'running' AND started_at < datetime('now', ?)",
+        [format!("-{} minutes", STALE_JOB_MINUTES)],
+    )?)
+}
+
+pub fn list_interrupted(conn: &Connection) -> AppResult<Vec<InterruptedJob>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, kind, escalation_id, file_paths, started_at, interrupted_at
+        FROM jobs WHERE state = 'interrupted' ORDER BY id",
+    )?;
+
+    let jobs = stmt
+        .query_map([], job_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(jobs)
+}
+
+pub fn get_interrupted(conn: &Connection, job_id: i64) -> AppResult<InterruptedJob> {
+    conn.query_row(
+        "SELECT id, kind, escalation_id, file_paths, started_at, interrupted_at
+        FROM jobs WHERE id = ? AND state = 'interrupted'",
+        [job_id],
+        job_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Interrupted job {} not found", job_id)))
+}
+
+fn job_from_row(row: &rusqlite::Row) -> rusqlite::Result<InterruptedJob> {
+    let file_paths: String = row.get(3)?;
+    Ok(InterruptedJob {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        escalation_id: row.get(2)?,
+        file_paths: serde_json::from_str(&file_paths).unwrap_or_default(),
+        started_at: row.get(4)?,
+        interrupted_at: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY);
+            INSERT INTO escalations (id) VALUES (1);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/018_jobs.sql")).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_finished_jobs_disappear() {
+        let conn = setup();
+        let id = start(&conn, KIND_POST, 1, &["/tmp/log.txt".to_string()], "me").unwrap();
+        finish(&conn, id).unwrap();
+
+        assert_eq!(interrupt_instance(&conn, "me").unwrap(), 0);
+        assert!(list_interrupted(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_interrupt_only_own_or_stale_jobs() {
+        let conn = setup();
+        let mine = start(&conn, KIND_POST, 1, &["/tmp/log.txt".to_string()], "me").unwrap();
+        start(&conn, KIND_RETRY_ATTACHMENTS, 1, &[], "other").unwrap();
+        let abandoned = start(&conn, KIND_RETRY_POST, 1, &[], "crashed").unwrap();
+        conn.execute(
+            "UPDATE jobs SET started_at = datetime('now', '-2 hours') WHERE id = ?",
+            [abandoned],
+        )
+        .unwrap();
+
+        assert_eq!(interrupt_instance(&conn, "me").unwrap(), 1);
+        assert_eq!(interrupt_stale(&conn).unwrap(), 1);
+
+        let interrupted = list_interrupted(&conn).unwrap();
+        let ids: Vec<i64> = interrupted.iter().map(|j| j.id).collect();
+        assert_eq!(ids, vec![mine, abandoned]);
+        assert_eq!(interrupted[0].file_paths, vec!["/tmp/log.txt".to_string()]);
+        assert!(get_interrupted(&conn, mine).is_ok());
+    }
+}
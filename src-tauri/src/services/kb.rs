@@ -0,0 +1,133 @@
+/// Knowledge-base drafts built from resolved escalations
+///
+/// Only completed checklist steps go into the source material: steps that
+/// were never attempted say nothing about how the issue was fixed. Private
+/// notes are never included.
+use crate::error::{AppError, AppResult};
+use crate::models::{Escalation, KbDraft};
+use rusqlite::{params, Connection, OptionalExtension};
+
+/// Source material handed to the LLM for one escalation
+pub fn source_text(escalation: &Escalation, resolution: &str) -> String {
+    let mut text = format!("Problem:\n{}\n", escalation.problem_summary.trim());
+
+    let completed: Vec<_> = escalation.checklist.iter().filter(|item| item.checked).collect();
+    if !completed.is_empty() {
+        text.push_str("\nCompleted troubleshooting steps:\n");
+        for item in completed {
+            match &item.link {
+                Some(link) => text.push_str(&format!("- {} (procedure: {})\n", item.text, link)),
+                None => text.push_str(&format!("- {}\n", item.text)),
+            }
+        }
+    }
+
+    text.push_str(&format!("\nResolution:\n{}\n", resolution.trim()));
+    text
+}
+
+/// Split the article's leading `# ` heading off as its title
+pub fn split_title(article: &str, fallback: &str) -> (String, String) {
+    let article = article.trim();
+    match article.lines().next().and_then(|line| line.strip_prefix("# ")) {
+        Some(title) if !title.trim().is_empty() => (title.trim().to_string(), article.to_string()),
+        _ => (fallback.to_string(), format!("# {}\n\n{}", fallback, article)),
+    }
+}
+
+pub fn save(conn: &Connection, escalation_id: i64, title: &str, markdown: &str) -> AppResult<KbDraft> {
+    conn.execute(
+        "INSERT INTO kb_drafts (escalation_id, title, markdown) VALUES (?, ?, ?)",
+        params![escalation_id, title, markdown],
+    )?;
+    get(conn, conn.last_insert_rowid())
+}
+
+pub fn list(conn: &Connection) -> AppResult<Vec<KbDraft>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, escalation_id, title, markdown, created_at
+        FROM kb_drafts ORDER BY created_at DESC, id DESC",
+    )?;
+
+    let drafts = stmt
+        .query_map([], draft_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(drafts)
+}
+
+pub fn get(conn: &Connection, id: i64) -> AppResult<KbDraft> {
+    conn.query_row(
+        "SELECT id, escalation_id, title, markdown, created_at FROM kb_drafts WHERE id = ?",
+        [id],
+        draft_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("KB draft {} not found", id)))
+}
+
+fn draft_from_row(row: &rusqlite::Row) -> rusqlite::Result<KbDraft> {
+    Ok(KbDraft {
+        id: row.get(0)?,
+        escalation_id: row.get(1)?,
+        title: row.get(2)?,
+        markdown: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ChecklistItem, EscalationStatus};
+
+    fn escalation() -> Escalation {
+        Escalation {
+            ticket_id: "SD-42".to_string(),
+            problem_summary: "Outlook prompts for a password in a loop".to_string(),
+            checklist: vec![
+                ChecklistItem { text: "Cleared cached credentials".to_string(), checked: true, link: None },
+                ChecklistItem { text: "Rebuilt the profile".to_string(), checked: false, link: None },
+            ],
+            status: EscalationStatus::Posted,
+            private_notes: Some("Caller was rude".to_string()),
+            ..Escalation::fixture()
+        }
+    }
+
+    #[test]
+    fn test_source_text_keeps_only_completed_steps() {
+        let text = source_text(&escalation(), "Modern auth was disabled for the mailbox");
+
+        assert!(text.contains("- Cleared cached credentials\n"));
+        assert!(!text.contains("Rebuilt the profile"));
+        assert!(text.ends_with("Resolution:\nModern auth was disabled for the mailbox\n"));
+        assert!(!text.contains("rude"));
+    }
+
+    #[test]
+    fn test_split_title() {
+        let (title, markdown) = split_title("# Outlook password loop\n\n## Symptoms\n...", "Fallback");
+        assert_eq!(title, "Outlook password loop");
+        assert!(markdown.starts_with("# Outlook password loop"));
+
+        let (title, markdown) = split_title("## Symptoms\n...", "SD-42");
+        assert_eq!(title, "SD-42");
+        assert!(markdown.starts_with("# SD-42\n\n## Symptoms"));
+    }
+
+    #[test]
+    fn test_save_and_list() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY);
+            INSERT INTO escalations (id) VALUES (1);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/019_kb_drafts.sql")).unwrap();
+
+        let draft = save(&conn, 1, "Outlook password loop", "# Outlook password loop").unwrap();
+        assert_eq!(list(&conn).unwrap().len(), 1);
+        assert_eq!(get(&conn, draft.id).unwrap().title, "Outlook password loop");
+        assert!(get(&conn, draft.id + 1).is_err());
+    }
+}
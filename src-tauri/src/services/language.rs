@@ -0,0 +1,90 @@
+/// Lightweight language detection by counting common function words
+///
+/// Good enough to tell the handful of languages our L1 teams write in apart
+/// from English; short or ambiguous text returns None rather than guessing.
+const LANGUAGES: &[(&str, &str, &[&str])] = &[
+    ("en", "English", &[
+        "the", "and", "is", "are", "was", "not", "with", "for", "to", "of", "it", "this", "user", "when", "after",
+    ]),
+    ("fr", "French", &[
+        "le", "la", "les", "des", "est", "et", "une", "pas", "avec", "pour", "dans", "sur", "ne", "utilisateur",
+        "après", "depuis",
+    ]),
+    ("es", "Spanish", &[
+        "el", "los", "las", "es", "y", "una", "no", "con", "para", "en", "del", "usuario", "después", "desde",
+    ]),
+    ("de", "German", &[
+        "der", "die", "das", "ist", "und", "nicht", "mit", "für", "ein", "eine", "auf", "nach", "seit", "benutzer",
+    ]),
+    ("it", "Italian", &[
+        "il", "lo", "gli", "è", "e", "non", "con", "per", "una", "della", "dopo", "utente", "da",
+    ]),
+    ("pt", "Portuguese", &[
+        "o", "os", "as", "é", "e", "não", "com", "para", "uma", "do", "da", "usuário", "depois", "desde",
+    ]),
+    ("nl", "Dutch", &[
+        "de", "het", "een", "is", "en", "niet", "met", "voor", "op", "van", "na", "sinds", "gebruiker",
+    ]),
+];
+
+/// Fewer matched words than this is too little evidence to call a language
+const MIN_MATCHES: usize = 2;
+
+/// ISO 639-1 code of the most likely language
+pub fn detect(text: &str) -> Option<&'static str> {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+
+    let mut scores: Vec<(&str, usize)> = LANGUAGES
+        .iter()
+        .map(|(code, _, stopwords)| {
+            let hits = words.iter().filter(|w| stopwords.contains(&w.as_str())).count();
+            (*code, hits)
+        })
+        .collect();
+    scores.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+
+    let (best, hits) = scores[0];
+    // A tie means the text is ambiguous (e.g. only shared words like "de")
+    if hits < MIN_MATCHES || scores[1].1 == hits {
+        return None;
+    }
+    Some(best)
+}
+
+pub fn name(code: &str) -> Option<&'static str> {
+    LANGUAGES
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map(|(_, name, _)| *name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(
+            detect("L'utilisateur ne peut pas se connecter au VPN depuis la mise à jour"),
+            Some("fr")
+        );
+        assert_eq!(detect("The user is not able to connect to the VPN after the update"), Some("en"));
+        assert_eq!(detect("Der Benutzer kann sich seit dem Update nicht mit dem VPN verbinden"), Some("de"));
+    }
+
+    #[test]
+    fn test_short_text_is_undetermined() {
+        assert_eq!(detect("VPN"), None);
+        assert_eq!(detect(""), None);
+    }
+
+    #[test]
+    fn test_name() {
+        assert_eq!(name("fr"), Some("French"));
+        assert_eq!(name("xx"), None);
+    }
+}
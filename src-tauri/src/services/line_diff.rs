@@ -0,0 +1,77 @@
+/// Line-by-line diff of two rendered outputs
+///
+/// A longest-common-subsequence diff: good enough for escalation-sized text
+/// (a few hundred lines), and it keeps unchanged lines so a preview can show
+/// context around what moved.
+use crate::models::{DiffLine, DiffOp};
+
+pub fn diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j]: common lines between old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(line(DiffOp::Same, old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(line(DiffOp::Removed, old[i]));
+            i += 1;
+        } else {
+            lines.push(line(DiffOp::Added, new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+pub fn has_changes(lines: &[DiffLine]) -> bool {
+    lines.iter().any(|l| l.op != DiffOp::Same)
+}
+
+fn line(op: DiffOp, text: &str) -> DiffLine {
+    DiffLine { op, text: text.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let lines = diff("## Problem\nVPN drops\n## Steps\n", "## Problem\nVPN drops\n## Environment\n## Steps\n");
+        let ops: Vec<&DiffOp> = lines.iter().map(|l| &l.op).collect();
+        assert_eq!(ops, vec![&DiffOp::Same, &DiffOp::Same, &DiffOp::Added, &DiffOp::Same]);
+        assert_eq!(lines[2].text, "## Environment");
+        assert!(has_changes(&lines));
+    }
+
+    #[test]
+    fn test_replaced_line() {
+        let lines = diff("a\nb\nc", "a\nB\nc");
+        assert_eq!(
+            lines,
+            vec![
+                line(DiffOp::Same, "a"),
+                line(DiffOp::Removed, "b"),
+                line(DiffOp::Added, "B"),
+                line(DiffOp::Same, "c"),
+            ]
+        );
+        assert!(!has_changes(&diff("same\ntext", "same\ntext")));
+    }
+}
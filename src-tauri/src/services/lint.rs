@@ -0,0 +1,183 @@
+/// Pre-posting text checks: common misspellings and house terminology
+///
+/// There's no full dictionary here; the spelling list covers typos that
+/// regularly show up in support write-ups, which keeps false positives on
+/// product names, hostnames and error codes close to zero. Valid British and
+/// French spellings (`instal`, `connexion`, `dependant`) are deliberately left
+/// out since write-ups come from L1 teams in several locales.
+use crate::models::{LintKind, LintWarning};
+use regex::{Regex, RegexBuilder};
+
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accross", "across"),
+    ("acess", "access"),
+    ("accesible", "accessible"),
+    ("adress", "address"),
+    ("aparently", "apparently"),
+    ("appologies", "apologies"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("comming", "coming"),
+    ("commited", "committed"),
+    ("completly", "completely"),
+    ("conection", "connection"),
+    ("definately", "definitely"),
+    ("enviroment", "environment"),
+    ("firewal", "firewall"),
+    ("freind", "friend"),
+    ("goverment", "government"),
+    ("immediatly", "immediately"),
+    ("independant", "independent"),
+    ("intermitent", "intermittent"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("occuring", "occurring"),
+    ("passowrd", "password"),
+    ("paswword", "password"),
+    ("persistant", "persistent"),
+    ("priviledge", "privilege"),
+    ("publically", "publicly"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("reccomend", "recommend"),
+    ("recomend", "recommend"),
+    ("refered", "referred"),
+    ("relevent", "relevant"),
+    ("reproducable", "reproducible"),
+    ("resolveed", "resolved"),
+    ("responce", "response"),
+    ("seperate", "separate"),
+    ("seperately", "separately"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("sucessfully", "successfully"),
+    ("supress", "suppress"),
+    ("teh", "the"),
+    ("throught", "through"),
+    ("truely", "truly"),
+    ("untill", "until"),
+    ("usefull", "useful"),
+    ("wich", "which"),
+    ("writting", "writing"),
+];
+
+/// Whole words only: letters glued to digits or underscores (`acess_log`) are identifiers
+fn word_regex() -> Regex {
+    Regex::new(r"\b[A-Za-z]+\b").expect("valid word regex")
+}
+
+/// `\b` only works next to word characters, so terms like "C++" or ".NET" check their neighbours instead
+fn is_whole_term(text: &str, start: usize, end: usize) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..start].chars().next_back().is_some_and(is_word) && !text[end..].chars().next().is_some_and(is_word)
+}
+
+/// Check one free-text field, tagging warnings with `field`
+pub fn lint_text(field: &str, text: &str, terminology: &[String]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for word in word_regex().find_iter(text) {
+        let lower = word.as_str().to_lowercase();
+        if let Some((_, correct)) = MISSPELLINGS.iter().find(|(wrong, _)| *wrong == lower) {
+            warnings.push(LintWarning {
+                field: field.to_string(),
+                kind: LintKind::Spelling,
+                found: word.as_str().to_string(),
+                suggestion: match_case(word.as_str(), correct),
+                offset: word.start(),
+            });
+        }
+    }
+
+    for term in terminology.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        let Ok(re) = RegexBuilder::new(&regex::escape(term))
+            .case_insensitive(true)
+            .build()
+        else {
+            continue;
+        };
+
+        let matches = re
+            .find_iter(text)
+            .filter(|m| is_whole_term(text, m.start(), m.end()) && m.as_str() != term);
+        for found in matches {
+            warnings.push(LintWarning {
+                field: field.to_string(),
+                kind: LintKind::Terminology,
+                found: found.as_str().to_string(),
+                suggestion: term.to_string(),
+                offset: found.start(),
+            });
+        }
+    }
+
+    warnings.sort_by_key(|w| w.offset);
+    warnings
+}
+
+/// Keep a leading capital so sentence-initial typos get a sentence-initial fix
+fn match_case(original: &str, correction: &str) -> String {
+    if original.chars().next().is_some_and(char::is_uppercase) {
+        let mut chars = correction.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default()
+    } else {
+        correction.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spelling() {
+        let warnings = lint_text("next_steps", "Recieve logs untill Friday", &[]);
+        let found: Vec<(&str, &str)> = warnings
+            .iter()
+            .map(|w| (w.found.as_str(), w.suggestion.as_str()))
+            .collect();
+        assert_eq!(found, vec![("Recieve", "Receive"), ("untill", "until")]);
+        assert_eq!(warnings[1].offset, 13);
+    }
+
+    #[test]
+    fn test_terminology_case() {
+        let terms = vec!["GlobalProtect".to_string(), "SharePoint Online".to_string()];
+        let warnings = lint_text(
+            "problem_summary",
+            "globalprotect fails; GlobalProtect logs show sharepoint online timeouts",
+            &terms,
+        );
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].found, "globalprotect");
+        assert_eq!(warnings[1].suggestion, "SharePoint Online");
+        assert!(matches!(warnings[1].kind, LintKind::Terminology));
+    }
+
+    #[test]
+    fn test_locale_spellings_and_identifiers() {
+        let text = "Connexion lost after the instal; see acess_log and teh2 output";
+        assert!(lint_text("problem_summary", text, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_terminology_with_symbols() {
+        let terms = vec!["C++".to_string(), ".NET".to_string()];
+        let warnings = lint_text("next_steps", "Rebuild the c++ runtime, then .net and ASP.NET apps", &terms);
+
+        let found: Vec<&str> = warnings.iter().map(|w| w.found.as_str()).collect();
+        assert_eq!(found, vec!["c++", ".net"]);
+
+        // Letters inside a longer word are not the term
+        assert!(lint_text("next_steps", "Open the Netflix app", &["NET".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_clean_text() {
+        assert!(lint_text("current_status", "User can access the VPN again.", &[]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+/// Ledger of LLM requests
+///
+/// Every request the LLM client makes is recorded with its model, operation,
+/// prompt size, duration and outcome, so usage can be reported per model and
+/// per agent: how much the GPUs are asked to do, and who keeps timing out.
+/// Prompts themselves are never stored.
+use crate::db;
+use crate::error::{AppError, AppResult};
+use crate::models::{LlmUsageReport, LlmUsageRow};
+use rusqlite::{params, Connection};
+use std::time::Duration;
+
+pub const OUTCOME_OK: &str = "ok";
+pub const OUTCOME_TIMEOUT: &str = "timeout";
+pub const OUTCOME_ERROR: &str = "error";
+
+pub struct LlmCall<'a> {
+    pub provider: &'a str,
+    pub model: &'a str,
+    pub operation: &'a str,
+    pub prompt_chars: usize,
+    pub duration: Duration,
+    pub outcome: &'static str,
+}
+
+/// How a request ended, for the ledger
+pub fn outcome<T>(result: &AppResult<T>) -> &'static str {
+    match result {
+        Ok(_) => OUTCOME_OK,
+        Err(AppError::Http(e)) if e.is_timeout() => OUTCOME_TIMEOUT,
+        Err(_) => OUTCOME_ERROR,
+    }
+}
+
+pub fn record(conn: &Connection, call: &LlmCall) -> AppResult<()> {
+    conn.execute(
+        "INSERT INTO llm_usage (provider, model, operation, prompt_chars, duration_ms, outcome, agent)
+        VALUES (?, ?, ?, ?, ?, ?, (SELECT display_name FROM jira_identity WHERE id = 1))",
+        params![
+            call.provider,
+            call.model,
+            call.operation,
+            call.prompt_chars as i64,
+            call.duration.as_millis() as i64,
+            call.outcome,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Record from the LLM client, which has no connection of its own; the ledger never fails a request
+pub fn log_call(call: &LlmCall) {
+    if let Err(e) = db::get_connection().and_then(|conn| record(&conn, call)) {
+        log::warn!("Failed to record LLM usage for {}: {}", call.operation, e);
+    }
+}
+
+/// Calls made between `from` and `to` (inclusive UTC dates, YYYY-MM-DD)
+pub fn report(conn: &Connection, from: &str, to: &str) -> AppResult<LlmUsageReport> {
+    let mut stmt = conn.prepare(
+        "SELECT provider, model, operation, agent, COUNT(*),
+            SUM(outcome != 'ok'), SUM(outcome = 'timeout'), SUM(prompt_chars),
+            AVG(duration_ms), MAX(duration_ms)
+        FROM llm_usage
+        WHERE date(created_at) BETWEEN ?1 AND ?2
+        GROUP BY provider, model, operation, agent
+        ORDER BY COUNT(*) DESC, provider, model, operation",
+    )?;
+    let rows = stmt
+        .query_map(params![from, to], |row| {
+            Ok(LlmUsageRow {
+                provider: row.get(0)?,
+                model: row.get(1)?,
+                operation: row.get(2)?,
+                agent: row.get(3)?,
+                calls: row.get(4)?,
+                failures: row.get(5)?,
+                timeouts: row.get(6)?,
+                prompt_chars: row.get(7)?,
+                avg_duration_ms: row.get(8)?,
+                max_duration_ms: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(LlmUsageReport {
+        from: from.to_string(),
+        to: to.to_string(),
+        total_calls: rows.iter().map(|r| r.calls).sum(),
+        failed_calls: rows.iter().map(|r| r.failures).sum(),
+        timed_out_calls: rows.iter().map(|r| r.timeouts).sum(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/017_jira_identity.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/032_llm_usage.sql")).unwrap();
+        conn
+    }
+
+    fn call(operation: &'static str, millis: u64, outcome: &'static str) -> LlmCall<'static> {
+        LlmCall {
+            provider: "ollama",
+            model: "llama3.2",
+            operation,
+            prompt_chars: 1200,
+            duration: Duration::from_millis(millis),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_report_groups_calls() {
+        let conn = setup();
+        record(&conn, &call("summarize", 2000, OUTCOME_OK)).unwrap();
+        record(&conn, &call("summarize", 30000, OUTCOME_TIMEOUT)).unwrap();
+        record(&conn, &call("summarize", 1000, OUTCOME_ERROR)).unwrap();
+        record(&conn, &call("embed", 100, OUTCOME_OK)).unwrap();
+
+        let report = report(&conn, "2000-01-01", "2999-12-31").unwrap();
+        assert_eq!((report.total_calls, report.failed_calls, report.timed_out_calls), (4, 2, 1));
+
+        let summarize = &report.rows[0];
+        assert_eq!(summarize.operation, "summarize");
+        assert_eq!((summarize.calls, summarize.failures, summarize.timeouts), (3, 2, 1));
+        assert_eq!(summarize.prompt_chars, 3600);
+        assert_eq!(summarize.avg_duration_ms, 11000.0);
+        assert_eq!(summarize.max_duration_ms, 30000);
+        assert_eq!(report.rows[1].operation, "embed");
+    }
+
+    #[test]
+    fn test_report_range() {
+        let conn = setup();
+        record(&conn, &call("summarize", 2000, OUTCOME_OK)).unwrap();
+        conn.execute("UPDATE llm_usage SET created_at = '2024-03-01 09:00:00'", []).unwrap();
+
+        assert_eq!(report(&conn, "2024-03-01", "2024-03-01").unwrap().total_calls, 1);
+        let empty = report(&conn, "2024-03-02", "2024-03-31").unwrap();
+        assert_eq!(empty.total_calls, 0);
+        assert!(empty.rows.is_empty());
+    }
+
+    #[test]
+    fn test_outcome() {
+        assert_eq!(outcome(&Ok(())), OUTCOME_OK);
+        assert_eq!(outcome::<()>(&Err(AppError::Ollama("model not found".to_string()))), OUTCOME_ERROR);
+    }
+}
@@ -0,0 +1,307 @@
+/// Optional HTTP API for integrations on the agent's machine (off by default)
+///
+/// Runs on its own thread with a blocking server; requests are routed through
+/// `handle` so routing can be tested without opening a socket. Enabling it or
+/// changing the bind address takes effect on the next app start.
+use crate::error::{AppError, AppResult};
+use crate::keychain;
+use crate::models::EscalationInput;
+use crate::services::metrics::METRICS;
+use crate::services::{share_links, slack};
+use std::io::Read;
+use tiny_http::{Header, Method, Response, Server};
+
+/// Slash-command payloads are a few hundred bytes; refuse anything much larger
+const MAX_BODY_BYTES: u64 = 16 * 1024;
+
+pub struct ApiRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl ApiRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct ApiResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl ApiResponse {
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+
+    fn html(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/html; charset=utf-8",
+            body,
+        }
+    }
+
+    fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+}
+
+pub fn handle(request: &ApiRequest) -> ApiResponse {
+    route(request, || keychain::get_slack_signing_secret().ok())
+}
+
+fn route(request: &ApiRequest, slack_secret: impl Fn() -> Option<String>) -> ApiResponse {
+    let (path, query) = request.url.split_once('?').unwrap_or((&request.url, ""));
+
+    match (&request.method, path) {
+        (Method::Get, "/metrics") => ApiResponse {
+            status: 200,
+            content_type: "text/plain; version=0.0.4; charset=utf-8",
+            body: METRICS.render(),
+        },
+        (Method::Post, "/slack/handoff") => slack_handoff(
+            request,
+            slack_secret(),
+            chrono::Utc::now().timestamp(),
+            crate::commands::escalations::save_escalation_impl,
+        ),
+        (Method::Get, path) if path.starts_with("/share/") => shared_view(
+            share_links::verify(path, query, chrono::Utc::now().timestamp()),
+            crate::commands::escalations::render_shared_view,
+        ),
+        (_, "/metrics") | (_, "/slack/handoff") => ApiResponse::text(405, "Method not allowed"),
+        _ => ApiResponse::text(404, "Not found"),
+    }
+}
+
+/// Create a draft escalation from a verified Slack slash command
+fn slack_handoff(
+    request: &ApiRequest,
+    signing_secret: Option<String>,
+    now: i64,
+    create_draft: impl Fn(EscalationInput) -> AppResult<i64>,
+) -> ApiResponse {
+    let Some(secret) = signing_secret else {
+        return ApiResponse::text(503, "Slack intake is not configured");
+    };
+
+    let timestamp = request.header("X-Slack-Request-Timestamp").unwrap_or_default();
+    let signature = request.header("X-Slack-Signature").unwrap_or_default();
+    if !slack::verify_signature(&secret, timestamp, &request.body, signature, now) {
+        return ApiResponse::text(401, "Invalid Slack signature");
+    }
+
+    // Slack treats non-200 replies as a failed dispatch, so user errors are 200 + message
+    let form = slack::parse_form(&String::from_utf8_lossy(&request.body));
+    let text = form.get("text").map(String::as_str).unwrap_or_default();
+    let Some((ticket_id, problem_summary)) = slack::parse_command(text) else {
+        let command = form.get("command").map(String::as_str).unwrap_or("/handoff");
+        return ApiResponse::json(200, slack::ephemeral(&format!("Usage: {} PROJ-123 short problem summary", command)));
+    };
+
+    let user = form.get("user_name").map(String::as_str).unwrap_or("unknown user");
+    let input = EscalationInput {
+        ticket_id,
+        template_id: None,
+        problem_summary,
+        checklist: Vec::new(),
+        current_status: String::new(),
+        next_steps: String::new(),
+        llm_summary: None,
+        llm_confidence: None,
+        attachments: Vec::new(),
+        private_notes: Some(format!("Started from Slack by @{}", user)),
+        ticket_snapshot: None,
+        summary_language: None,
+        summary_translation: None,
+        parent_escalation_id: None,
+        previous_escalation: None,
+        render_version: None,
+        l2_teams: Vec::new(),
+        environment_info: None,
+        incident_id: None,
+    };
+    let ticket_id = input.ticket_id.clone();
+
+    let message = match create_draft(input) {
+        Ok(id) => format!(
+            "Draft escalation #{} created for {}. Open Ticket Handoff to finish it.",
+            id, ticket_id
+        ),
+        Err(e) => format!("Couldn't create a draft for {}: {}", ticket_id, e),
+    };
+    ApiResponse::json(200, slack::ephemeral(&message))
+}
+
+/// Read-only HTML view of an escalation behind a verified share link
+fn shared_view(escalation_id: Option<i64>, render: impl Fn(i64) -> AppResult<String>) -> ApiResponse {
+    let Some(escalation_id) = escalation_id else {
+        return ApiResponse::text(403, "This share link is invalid or has expired");
+    };
+
+    match render(escalation_id) {
+        Ok(html) => ApiResponse::html(200, html),
+        Err(AppError::NotFound(_)) | Err(AppError::DbSql(rusqlite::Error::QueryReturnedNoRows)) => {
+            ApiResponse::text(404, "Escalation not found")
+        }
+        Err(e) => {
+            log::warn!("Failed to render shared escalation {}: {}", escalation_id, e);
+            ApiResponse::text(500, "Could not render this escalation")
+        }
+    }
+}
+
+fn read_request(request: &mut tiny_http::Request) -> std::io::Result<ApiRequest> {
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(MAX_BODY_BYTES)
+        .read_to_end(&mut body)?;
+
+    Ok(ApiRequest {
+        method: request.method().clone(),
+        url: request.url().to_string(),
+        headers: request
+            .headers()
+            .iter()
+            .map(|h| (h.field.to_string(), h.value.to_string()))
+            .collect(),
+        body,
+    })
+}
+
+/// Bind the server and serve requests on a background thread
+pub fn spawn(bind: &str) -> AppResult<()> {
+    let server = Server::http(bind)
+        .map_err(|e| AppError::Validation(format!("Cannot start local API on {}: {}", bind, e)))?;
+    log::info!("Local API listening on {}", bind);
+
+    std::thread::spawn(move || {
+        for mut request in server.incoming_requests() {
+            let response = match read_request(&mut request) {
+                Ok(api_request) => handle(&api_request),
+                Err(e) => ApiResponse::text(400, format!("Could not read request: {}", e)),
+            };
+            let content_type = Header::from_bytes("Content-Type", response.content_type)
+                .expect("static content type header is valid");
+
+            let reply = Response::from_string(response.body)
+                .with_status_code(response.status)
+                .with_header(content_type);
+            if let Err(e) = request.respond(reply) {
+                log::warn!("Local API failed to send response: {}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use std::cell::RefCell;
+
+    const SECRET: &str = "test-secret";
+    const NOW: i64 = 1_700_000_000;
+
+    fn request(method: Method, url: &str) -> ApiRequest {
+        ApiRequest {
+            method,
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    fn signed_slack_request(body: &str) -> ApiRequest {
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:{}", NOW, body).as_bytes());
+        let signature: String = mac.finalize().into_bytes().iter().map(|b| format!("{:02x}", b)).collect();
+
+        ApiRequest {
+            method: Method::Post,
+            url: "/slack/handoff".to_string(),
+            headers: vec![
+                ("X-Slack-Request-Timestamp".to_string(), NOW.to_string()),
+                ("X-Slack-Signature".to_string(), format!("v0={}", signature)),
+            ],
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_routes() {
+        let route = |method, url| route(&request(method, url), || None);
+
+        let metrics = route(Method::Get, "/metrics?name[]=x");
+        assert_eq!(metrics.status, 200);
+        assert!(metrics.body.contains("ticket_handoff_posts_total"));
+
+        assert_eq!(route(Method::Post, "/metrics").status, 405);
+        assert_eq!(route(Method::Get, "/slack/handoff").status, 405);
+        assert_eq!(route(Method::Post, "/slack/handoff").status, 503);
+        assert_eq!(route(Method::Get, "/").status, 404);
+        assert_eq!(route(Method::Get, "/share/1?expires=1&sig=x").status, 403);
+    }
+
+    #[test]
+    fn test_shared_view() {
+        let rendered = shared_view(Some(3), |id| Ok(format!("<h1>Escalation #{}</h1>", id)));
+        assert_eq!(rendered.status, 200);
+        assert_eq!(rendered.content_type, "text/html; charset=utf-8");
+        assert_eq!(rendered.body, "<h1>Escalation #3</h1>");
+
+        let missing = shared_view(Some(3), |id| Err(AppError::NotFound(format!("Escalation {}", id))));
+        assert_eq!(missing.status, 404);
+    }
+
+    #[test]
+    fn test_slack_handoff_creates_draft() {
+        let created = RefCell::new(None);
+        let request = signed_slack_request("command=%2Fhandoff&user_name=sam&text=PROJ-123+VPN+drops+hourly");
+
+        let response = slack_handoff(&request, Some(SECRET.to_string()), NOW, |input| {
+            created.replace(Some(input));
+            Ok(42)
+        });
+
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("Draft escalation #42 created for PROJ-123"));
+        let input = created.into_inner().unwrap();
+        assert_eq!(input.ticket_id, "PROJ-123");
+        assert_eq!(input.problem_summary, "VPN drops hourly");
+        assert_eq!(input.private_notes.as_deref(), Some("Started from Slack by @sam"));
+    }
+
+    #[test]
+    fn test_slack_handoff_rejects_bad_requests() {
+        let never = |_: EscalationInput| -> AppResult<i64> { panic!("draft must not be created") };
+        let request = signed_slack_request("text=PROJ-1");
+
+        assert_eq!(slack_handoff(&request, None, NOW, never).status, 503);
+        assert_eq!(slack_handoff(&request, Some("other".to_string()), NOW, never).status, 401);
+
+        let usage = slack_handoff(&signed_slack_request("command=%2Fhandoff&text="), Some(SECRET.to_string()), NOW, never);
+        assert_eq!(usage.status, 200);
+        assert!(usage.body.contains("Usage: /handoff PROJ-123"));
+    }
+}
@@ -0,0 +1,145 @@
+/// Advisory draft locks shared between app instances through the database
+///
+/// Each running app gets a random instance id; a lock held by another instance
+/// blocks acquiring until it is released, force-unlocked, or goes stale after
+/// `LOCK_TTL_HOURS` (covering instances that crashed while holding one).
+use crate::error::{AppError, AppResult};
+use once_cell::sync::Lazy;
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub const LOCK_TTL_HOURS: i64 = 12;
+
+/// Identifies this running app instance
+pub static INSTANCE_ID: Lazy<String> =
+    Lazy::new(|| format!("{}-{:08x}", std::process::id(), rand::random::<u32>()));
+
+/// OS login name of the agent running this instance
+pub fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockHolder {
+    pub locked_by: String,
+    pub locked_at: String,
+}
+
+/// The live lock on an escalation if another instance holds it
+pub fn held_by_other(conn: &Connection, escalation_id: i64, instance_id: &str) -> AppResult<Option<LockHolder>> {
+    let holder = conn
+        .query_row(
+            "SELECT locked_by, locked_at FROM escalation_locks
+            WHERE escalation_id = ? AND instance_id != ? AND locked_at >= datetime('now', ?)",
+            params![escalation_id, instance_id, format!("-{} hours", LOCK_TTL_HOURS)],
+            |row| {
+                Ok(LockHolder {
+                    locked_by: row.get(0)?,
+                    locked_at: row.get(1)?,
+                })
+            },
+        )
+        .optional()?;
+
+    Ok(holder)
+}
+
+/// Take (or refresh) the lock for this instance
+///
+/// A single upsert that only overwrites our own or a stale lock, so two
+/// instances racing for the same draft cannot both succeed.
+pub fn acquire(conn: &Connection, escalation_id: i64, instance_id: &str, user: &str) -> AppResult<()> {
+    let changed = conn.execute(
+        "INSERT INTO escalation_locks (escalation_id, locked_by, instance_id) VALUES (?1, ?2, ?3)
+        ON CONFLICT(escalation_id) DO UPDATE SET
+            locked_by = excluded.locked_by,
+            instance_id = excluded.instance_id,
+            locked_at = datetime('now')
+        WHERE escalation_locks.instance_id = ?3 OR escalation_locks.locked_at < datetime('now', ?4)",
+        params![escalation_id, user, instance_id, format!("-{} hours", LOCK_TTL_HOURS)],
+    )?;
+
+    if changed == 0 {
+        let holder = held_by_other(conn, escalation_id, instance_id)?;
+        return Err(AppError::Validation(match holder {
+            Some(holder) => format!(
+                "Escalation {} is open on another instance by {} since {}",
+                escalation_id, holder.locked_by, holder.locked_at
+            ),
+            None => format!("Escalation {} is open on another instance", escalation_id),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Release a lock held by this instance; locks held elsewhere are left alone
+pub fn release(conn: &Connection, escalation_id: i64, instance_id: &str) -> AppResult<()> {
+    conn.execute(
+        "DELETE FROM escalation_locks WHERE escalation_id = ? AND instance_id = ?",
+        params![escalation_id, instance_id],
+    )?;
+    Ok(())
+}
+
+/// Drop any lock on the escalation; returns whether one existed
+pub fn force_release(conn: &Connection, escalation_id: i64) -> AppResult<bool> {
+    let removed = conn.execute("DELETE FROM escalation_locks WHERE escalation_id = ?", [escalation_id])?;
+    Ok(removed > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE escalations (id INTEGER PRIMARY KEY);
+            INSERT INTO escalations VALUES (1);",
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/010_escalation_locks.sql"))
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_lock_blocks_other_instances() {
+        let conn = setup();
+        acquire(&conn, 1, "a", "alex").unwrap();
+
+        // Re-acquiring from the same instance just refreshes
+        acquire(&conn, 1, "a", "alex").unwrap();
+        assert!(held_by_other(&conn, 1, "a").unwrap().is_none());
+
+        let holder = held_by_other(&conn, 1, "b").unwrap().unwrap();
+        assert_eq!(holder.locked_by, "alex");
+        assert!(acquire(&conn, 1, "b", "sam").is_err());
+
+        // Releasing from the wrong instance does nothing
+        release(&conn, 1, "b").unwrap();
+        assert!(acquire(&conn, 1, "b", "sam").is_err());
+
+        release(&conn, 1, "a").unwrap();
+        acquire(&conn, 1, "b", "sam").unwrap();
+    }
+
+    #[test]
+    fn test_force_release_and_stale_locks() {
+        let conn = setup();
+        acquire(&conn, 1, "a", "alex").unwrap();
+        assert!(force_release(&conn, 1).unwrap());
+        assert!(!force_release(&conn, 1).unwrap());
+
+        conn.execute(
+            "INSERT INTO escalation_locks (escalation_id, locked_by, instance_id, locked_at)
+            VALUES (1, 'alex', 'a', datetime('now', '-13 hours'))",
+            [],
+        )
+        .unwrap();
+        assert!(held_by_other(&conn, 1, "b").unwrap().is_none());
+        acquire(&conn, 1, "b", "sam").unwrap();
+    }
+}
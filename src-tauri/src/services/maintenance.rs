@@ -0,0 +1,104 @@
+/// Database housekeeping: VACUUM, ANALYZE and `PRAGMA integrity_check`
+use crate::error::AppResult;
+use crate::models::MaintenanceReport;
+use rusqlite::{params, Connection};
+use std::time::Instant;
+
+/// Automatic runs happen at most once per this many days
+pub const MAINTENANCE_INTERVAL_DAYS: i64 = 30;
+
+pub fn run(conn: &Connection) -> AppResult<MaintenanceReport> {
+    let started = Instant::now();
+    let size_before_bytes = database_size(conn)?;
+
+    // integrity_check returns a single "ok" row when the database is healthy
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let integrity_messages = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    let integrity_ok = integrity_messages.len() == 1 && integrity_messages[0] == "ok";
+
+    // Only compact a healthy database; VACUUM on a corrupt file can make things worse
+    if integrity_ok {
+        conn.execute_batch("VACUUM; ANALYZE;")?;
+    } else {
+        log::error!("Integrity check failed: {}", integrity_messages.join("; "));
+    }
+
+    let size_after_bytes = database_size(conn)?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let messages_json = serde_json::to_string(&integrity_messages).unwrap_or_else(|_| "[]".to_string());
+    let ran_at: String = conn.query_row(
+        "INSERT INTO maintenance_runs (integrity_ok, integrity_messages, size_before_bytes, size_after_bytes, duration_ms)
+         VALUES (?, ?, ?, ?, ?)
+         RETURNING ran_at",
+        params![integrity_ok, messages_json, size_before_bytes, size_after_bytes, duration_ms as i64],
+        |row| row.get(0),
+    )?;
+
+    Ok(MaintenanceReport {
+        integrity_ok,
+        integrity_messages,
+        size_before_bytes,
+        size_after_bytes,
+        duration_ms,
+        ran_at,
+        compressed_escalations: 0,
+        orphans_removed: 0,
+    })
+}
+
+/// Whether the last run is older than the maintenance interval (or never happened)
+pub fn is_due(conn: &Connection) -> AppResult<bool> {
+    let due: bool = conn.query_row(
+        "SELECT COALESCE(MAX(ran_at) < datetime('now', ?), 1) FROM maintenance_runs",
+        [format!("-{} days", MAINTENANCE_INTERVAL_DAYS)],
+        |row| row.get(0),
+    )?;
+
+    Ok(due)
+}
+
+fn database_size(conn: &Connection) -> AppResult<i64> {
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    Ok(page_count * page_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/006_maintenance.sql"))
+            .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_run_records_history() {
+        let conn = test_conn();
+        assert!(is_due(&conn).unwrap());
+
+        let report = run(&conn).unwrap();
+        assert!(report.integrity_ok);
+        assert_eq!(report.integrity_messages, vec!["ok".to_string()]);
+
+        assert!(!is_due(&conn).unwrap());
+    }
+
+    #[test]
+    fn test_due_after_interval() {
+        let conn = test_conn();
+        conn.execute(
+            "INSERT INTO maintenance_runs (integrity_ok, size_before_bytes, size_after_bytes, duration_ms, ran_at)
+             VALUES (1, 0, 0, 0, datetime('now', '-31 days'))",
+            [],
+        )
+        .unwrap();
+
+        assert!(is_due(&conn).unwrap());
+    }
+}
@@ -0,0 +1,110 @@
+/// Process-wide usage counters, rendered in the Prometheus text exposition format
+///
+/// Counters reset when the app restarts; Prometheus' `rate()`/`increase()`
+/// handle that, so nothing is persisted.
+use once_cell::sync::Lazy;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the LLM latency histogram buckets
+const LLM_LATENCY_BUCKETS: [f64; 7] = [0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0];
+
+#[derive(Default)]
+pub struct Metrics {
+    posts: AtomicU64,
+    post_failures: AtomicU64,
+    retries: AtomicU64,
+    llm_latency_buckets: [AtomicU64; LLM_LATENCY_BUCKETS.len()],
+    llm_latency_count: AtomicU64,
+    llm_latency_sum_ms: AtomicU64,
+}
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+impl Metrics {
+    pub fn record_post(&self) {
+        self.posts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_post_failure(&self) {
+        self.post_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_llm_latency(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        for (bound, bucket) in LLM_LATENCY_BUCKETS.iter().zip(&self.llm_latency_buckets) {
+            if seconds <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.llm_latency_count.fetch_add(1, Ordering::Relaxed);
+        self.llm_latency_sum_ms
+            .fetch_add(elapsed.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+
+        counter(&mut out, "ticket_handoff_posts_total", "Escalations posted to Jira", &self.posts);
+        counter(
+            &mut out,
+            "ticket_handoff_post_failures_total",
+            "Escalation posts that failed",
+            &self.post_failures,
+        );
+        counter(
+            &mut out,
+            "ticket_handoff_retries_total",
+            "Retried Jira/Ollama requests",
+            &self.retries,
+        );
+
+        let name = "ticket_handoff_llm_latency_seconds";
+        let _ = writeln!(out, "# HELP {} Ollama generation latency (summaries and KB drafts)", name);
+        let _ = writeln!(out, "# TYPE {} histogram", name);
+        for (bound, bucket) in LLM_LATENCY_BUCKETS.iter().zip(&self.llm_latency_buckets) {
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, bucket.load(Ordering::Relaxed));
+        }
+        let count = self.llm_latency_count.load(Ordering::Relaxed);
+        let sum = self.llm_latency_sum_ms.load(Ordering::Relaxed) as f64 / 1000.0;
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, count);
+        let _ = writeln!(out, "{}_sum {}", name, sum);
+        let _ = writeln!(out, "{}_count {}", name, count);
+
+        out
+    }
+}
+
+fn counter(out: &mut String, name: &str, help: &str, value: &AtomicU64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} counter", name);
+    let _ = writeln!(out, "{} {}", name, value.load(Ordering::Relaxed));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counters_and_histogram() {
+        let metrics = Metrics::default();
+        metrics.record_post();
+        metrics.record_post();
+        metrics.record_retry();
+        metrics.record_llm_latency(Duration::from_millis(1500));
+
+        let text = metrics.render();
+        assert!(text.contains("ticket_handoff_posts_total 2\n"));
+        assert!(text.contains("ticket_handoff_post_failures_total 0\n"));
+        assert!(text.contains("ticket_handoff_retries_total 1\n"));
+        assert!(text.contains("ticket_handoff_llm_latency_seconds_bucket{le=\"1\"} 0\n"));
+        assert!(text.contains("ticket_handoff_llm_latency_seconds_bucket{le=\"2\"} 1\n"));
+        assert!(text.contains("ticket_handoff_llm_latency_seconds_bucket{le=\"+Inf\"} 1\n"));
+        assert!(text.contains("ticket_handoff_llm_latency_seconds_sum 1.5\n"));
+    }
+}
@@ -1,6 +1,58 @@
+pub mod acknowledgment;
+pub mod activity;
 pub mod adf;
+pub mod anonymized_dataset;
+pub mod attachment_quota;
+pub mod attachment_storage;
+pub mod audit;
+pub mod bounces;
+pub mod bundle;
+pub mod checklist_stats;
+pub mod chunked_upload;
+pub mod cold_storage;
+pub mod correlation;
+pub mod data_integrity;
+pub mod db_recovery;
+pub mod embeddings;
+pub mod environment_info;
+pub mod escalation_queue;
+pub mod field_history;
+pub mod freeze_window;
+pub mod guardrails;
+pub mod html;
+pub mod incident;
 pub mod jira;
+pub mod jobs;
+pub mod kb;
+pub mod language;
+pub mod line_diff;
+pub mod lint;
+pub mod llm_usage;
+pub mod local_api;
+pub mod locks;
+pub mod maintenance;
+pub mod metrics;
+pub mod next_step_blocks;
 pub mod ollama;
+pub mod onboarding;
+pub mod output_format;
+pub mod output_size;
+pub mod priority;
+pub mod quality;
+pub mod redaction;
+pub mod remote_links;
 pub mod retry;
+pub mod screening;
+pub mod share_links;
+pub mod similarity;
+pub mod slack;
+pub mod team_routing;
 pub mod template_engine;
+pub mod template_files;
+pub mod template_filter;
+pub mod template_pack;
+pub mod ticket_cache;
+pub mod ticket_guard;
+pub mod ticket_keys;
 pub mod ticket_system;
+pub mod watch_folders;
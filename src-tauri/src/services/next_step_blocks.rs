@@ -0,0 +1,166 @@
+/// Library of reusable "next steps" blocks
+///
+/// A block is referenced as `{{> name}}`: in an escalation's next steps the
+/// reference is replaced with the block's text when the draft is saved, and in
+/// a template body it is a regular handlebars partial.
+use crate::error::{AppError, AppResult};
+use crate::models::{NextStepBlock, NextStepBlockInput};
+use handlebars::Handlebars;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rusqlite::{params, Connection, OptionalExtension};
+
+static REFERENCE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{>\s*([A-Za-z0-9_-]+)\s*\}\}").expect("valid block reference regex"));
+
+pub fn list(conn: &Connection) -> AppResult<Vec<NextStepBlock>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, body, created_at, updated_at FROM next_step_blocks ORDER BY name COLLATE NOCASE",
+    )?;
+    let blocks = stmt
+        .query_map([], block_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(blocks)
+}
+
+pub fn get(conn: &Connection, id: i64) -> AppResult<NextStepBlock> {
+    conn.query_row(
+        "SELECT id, name, body, created_at, updated_at FROM next_step_blocks WHERE id = ?",
+        [id],
+        block_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| AppError::NotFound(format!("Next steps block {} not found", id)))
+}
+
+/// Create a block, or update it when the input has an id
+pub fn save(conn: &Connection, input: &NextStepBlockInput) -> AppResult<NextStepBlock> {
+    let name = input.name.trim();
+    validate(name, &input.body)?;
+
+    let taken: Option<i64> = conn
+        .query_row("SELECT id FROM next_step_blocks WHERE name = ? COLLATE NOCASE", [name], |row| row.get(0))
+        .optional()?;
+    if taken.is_some() && taken != input.id {
+        return Err(AppError::Validation(format!("A next steps block named '{}' already exists", name)));
+    }
+
+    let id = match input.id {
+        Some(id) => {
+            let updated = conn.execute(
+                "UPDATE next_step_blocks SET name = ?, body = ?, updated_at = datetime('now') WHERE id = ?",
+                params![name, input.body, id],
+            )?;
+            if updated == 0 {
+                return Err(AppError::NotFound(format!("Next steps block {} not found", id)));
+            }
+            id
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO next_step_blocks (name, body) VALUES (?, ?)",
+                params![name, input.body],
+            )?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    get(conn, id)
+}
+
+pub fn delete(conn: &Connection, id: i64) -> AppResult<()> {
+    if conn.execute("DELETE FROM next_step_blocks WHERE id = ?", [id])? == 0 {
+        return Err(AppError::NotFound(format!("Next steps block {} not found", id)));
+    }
+    Ok(())
+}
+
+/// Replace `{{> name}}` references with block text; unknown names are left as typed
+pub fn expand(text: &str, blocks: &[NextStepBlock]) -> String {
+    REFERENCE_RE
+        .replace_all(text, |caps: &Captures| {
+            blocks
+                .iter()
+                .find(|block| block.name.eq_ignore_ascii_case(&caps[1]))
+                .map(|block| block.body.trim().to_string())
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+fn validate(name: &str, body: &str) -> AppResult<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(AppError::Validation(
+            "Block names may only contain letters, digits, '-' and '_'".to_string(),
+        ));
+    }
+    if body.trim().is_empty() {
+        return Err(AppError::Validation("Block text is empty".to_string()));
+    }
+
+    // Blocks double as template partials, so they must parse as handlebars
+    Handlebars::new().register_partial(name, body)?;
+    Ok(())
+}
+
+fn block_from_row(row: &rusqlite::Row) -> rusqlite::Result<NextStepBlock> {
+    Ok(NextStepBlock {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        body: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/026_next_step_blocks.sql")).unwrap();
+        conn
+    }
+
+    fn input(id: Option<i64>, name: &str, body: &str) -> NextStepBlockInput {
+        NextStepBlockInput {
+            id,
+            name: name.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_crud() {
+        let conn = setup();
+        let block = save(&conn, &input(None, "vpn-logs", "Collect VPN gateway logs")).unwrap();
+        assert!(save(&conn, &input(None, "VPN-LOGS", "Duplicate")).is_err());
+
+        let updated = save(&conn, &input(Some(block.id), "vpn-logs", "Collect gateway and client logs")).unwrap();
+        assert_eq!(updated.body, "Collect gateway and client logs");
+        assert_eq!(list(&conn).unwrap().len(), 1);
+
+        delete(&conn, block.id).unwrap();
+        assert!(list(&conn).unwrap().is_empty());
+        assert!(delete(&conn, block.id).is_err());
+    }
+
+    #[test]
+    fn test_validation() {
+        let conn = setup();
+        assert!(save(&conn, &input(None, "has space", "text")).is_err());
+        assert!(save(&conn, &input(None, "empty", "  ")).is_err());
+        assert!(save(&conn, &input(None, "broken", "{{#if x}}")).is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let conn = setup();
+        save(&conn, &input(None, "vpn-logs", "Collect VPN gateway logs\n")).unwrap();
+        let blocks = list(&conn).unwrap();
+
+        let text = "Please:\n{{> VPN-logs}}\n{{> unknown}}";
+        assert_eq!(expand(text, &blocks), "Please:\nCollect VPN gateway logs\n{{> unknown}}");
+    }
+}
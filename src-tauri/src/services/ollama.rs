@@ -1,33 +1,47 @@
 use crate::error::{AppError, AppResult};
-use crate::models::{ChecklistItem, LLMSummaryResult};
+use crate::models::{ChecklistItem, JiraLinkedIssue, LLMSummaryResult};
+use crate::services::guardrails;
+use crate::services::llm_usage::{self, LlmCall};
 use crate::services::retry::retry_with_backoff;
 use reqwest::header::CONTENT_TYPE;
 use serde::{Deserialize, Serialize};
-use std::time::Duration;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+pub const PROVIDER: &str = "ollama";
 
 pub struct OllamaClient {
     endpoint: String,
     model: String,
     client: reqwest::Client,
+    timeout: Duration,
 }
 
 impl OllamaClient {
     pub fn new(endpoint: String, model: String) -> AppResult<Self> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()?;
+        let client = reqwest::Client::builder().build()?;
 
         Ok(Self {
             endpoint,
             model,
             client,
+            timeout: Duration::from_secs(DEFAULT_TIMEOUT_SECS),
         })
     }
 
+    /// Per-request timeout; model loading alone can take longer than the default
+    pub fn with_timeout(mut self, secs: u64) -> Self {
+        self.timeout = Duration::from_secs(secs.max(1));
+        self
+    }
+
     pub async fn is_available(&self) -> AppResult<bool> {
         let url = format!("{}/api/tags", self.endpoint);
 
-        match self.client.get(&url).send().await {
+        // Listing models never waits on a model load, so a long timeout isn't needed here
+        let timeout = self.timeout.min(Duration::from_secs(DEFAULT_TIMEOUT_SECS));
+        match self.client.get(&url).timeout(timeout).send().await {
             Ok(response) => Ok(response.status().is_success()),
             Err(e) if e.is_timeout() => Ok(false), // Ollama not responding
             Err(e) if e.is_connect() => Ok(false), // Cannot reach endpoint
@@ -41,55 +55,204 @@ impl OllamaClient {
         }
     }
 
-    pub async fn summarize(&self, checklist: &[ChecklistItem], problem: &str) -> AppResult<LLMSummaryResult> {
-        retry_with_backoff(|| self.summarize_impl(checklist, problem)).await
+    pub async fn summarize(
+        &self,
+        checklist: &[ChecklistItem],
+        problem: &str,
+        related: &[JiraLinkedIssue],
+    ) -> AppResult<LLMSummaryResult> {
+        retry_with_backoff(|| self.summarize_impl(checklist, problem, related)).await
+    }
+
+    /// Embed text with this client's model (must be an embedding model)
+    pub async fn embed(&self, text: &str) -> AppResult<Vec<f32>> {
+        retry_with_backoff(|| self.logged("embed", text.chars().count(), self.embed_impl(text))).await
+    }
+
+    async fn embed_impl(&self, text: &str) -> AppResult<Vec<f32>> {
+        let url = format!("{}/api/embed", self.endpoint);
+
+        let request_body = OllamaEmbedRequest {
+            model: self.model.clone(),
+            input: text.to_string(),
+        };
+
+        let response = self
+            .client
+            .post(&url)
+            .timeout(self.timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error("Ollama embedding error", response).await);
+        }
+
+        let ollama_response: OllamaEmbedResponse = response.json().await?;
+        ollama_response
+            .embeddings
+            .into_iter()
+            .next()
+            .ok_or_else(|| AppError::Ollama("Ollama returned no embedding".to_string()))
+    }
+
+    /// Translate text to English, preserving technical terms and formatting
+    pub async fn translate_to_english(&self, text: &str, language: &str) -> AppResult<String> {
+        retry_with_backoff(|| self.translate_impl(text, language)).await
+    }
+
+    /// Ask the model to pick one of `priorities`; returns the raw reply
+    pub async fn suggest_priority(&self, escalation_text: &str, priorities: &[&str]) -> AppResult<String> {
+        let prompt = format!(
+            r#"You triage IT support escalations. Choose the Jira priority for this escalation
+from: {}. Weigh how many users are affected, data loss or security risk, and
+whether standard troubleshooting is exhausted.
+Reply with only the priority name on the first line.
+
+{}"#,
+            priorities.join(", "),
+            escalation_text
+        );
+
+        retry_with_backoff(|| self.generate_impl("suggest_priority", &prompt)).await
+    }
+
+    /// Turn a resolved escalation's source material into a markdown KB article
+    pub async fn draft_kb_article(&self, source: &str) -> AppResult<String> {
+        let prompt = format!(
+            r##"You write internal knowledge-base articles for an IT service desk.
+Turn the resolved support case below into a reusable article in markdown.
+Start with a single "# " title describing the problem, then use these sections:
+## Symptoms, ## Cause, ## Resolution, ## Related steps.
+Generalize: leave out ticket keys, names and other customer details.
+Only use facts from the case. If the cause is not stated, write "Not confirmed".
+
+{}"##,
+            source
+        );
+
+        retry_with_backoff(|| self.generate_impl("draft_kb_article", &prompt)).await
+    }
+
+    /// Turn a day's activity list into a short handover paragraph
+    pub async fn narrate_day(&self, activity: &str) -> AppResult<String> {
+        let prompt = format!(
+            r#"Write a short shift handover note (3-5 sentences, first person) from this
+service desk agent's activity list. Mention ticket keys exactly as given and
+do not add work that isn't listed. Reply with only the note.
+
+{}"#,
+            activity
+        );
+
+        retry_with_backoff(|| self.generate_impl("narrate_day", &prompt)).await
+    }
+
+    async fn generate_impl(&self, operation: &str, prompt: &str) -> AppResult<String> {
+        self.logged(operation, prompt.chars().count(), self.send_generate(prompt)).await
     }
 
-    async fn summarize_impl(&self, checklist: &[ChecklistItem], problem: &str) -> AppResult<LLMSummaryResult> {
-        // Build the prompt
-        let prompt = self.build_prompt(checklist, problem);
+    /// Run one request, recording it in the usage ledger (each retry is its own entry)
+    async fn logged<T>(&self, operation: &str, prompt_chars: usize, request: impl Future<Output = AppResult<T>>) -> AppResult<T> {
+        let started = Instant::now();
+        let result = request.await;
+        llm_usage::log_call(&LlmCall {
+            provider: PROVIDER,
+            model: &self.model,
+            operation,
+            prompt_chars,
+            duration: started.elapsed(),
+            outcome: llm_usage::outcome(&result),
+        });
+        result
+    }
 
-        // Call Ollama API
+    async fn send_generate(&self, prompt: &str) -> AppResult<String> {
         let url = format!("{}/api/generate", self.endpoint);
 
         let request_body = OllamaGenerateRequest {
             model: self.model.clone(),
-            prompt,
+            prompt: prompt.to_string(),
             stream: false,
         };
 
         let response = self
             .client
             .post(&url)
+            .timeout(self.timeout)
             .header(CONTENT_TYPE, "application/json")
             .json(&request_body)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            return Err(AppError::Ollama(format!(
-                "Ollama API error: {}",
-                response.status()
-            )));
+            return Err(api_error("Ollama API error", response).await);
         }
 
         let ollama_response: OllamaGenerateResponse = response.json().await?;
+        Ok(ollama_response.response.trim().to_string())
+    }
+
+    async fn translate_impl(&self, text: &str, language: &str) -> AppResult<String> {
+        let prompt = format!(
+            r#"Translate the following {} support ticket text into English.
+Keep product names, error messages, file paths and ticket keys unchanged.
+Reply with only the translation, no preamble.
+
+{}"#,
+            language, text
+        );
+
+        self.generate_impl("translate", &prompt).await
+    }
+
+    async fn summarize_impl(
+        &self,
+        checklist: &[ChecklistItem],
+        problem: &str,
+        related: &[JiraLinkedIssue],
+    ) -> AppResult<LLMSummaryResult> {
+        let prompt = self.build_prompt(checklist, problem, related);
+        let summary = self.generate_impl("summarize", &prompt).await?;
+
+        // Models sometimes invent steps; drop any that aren't on the checklist
+        let (summary, warnings) = guardrails::check_summary(&summary, checklist);
+        for warning in &warnings {
+            log::warn!("LLM summary guardrail: {}", warning);
+        }
 
         // Calculate confidence based on checklist
         let (confidence, confidence_reason) = self.calculate_confidence(checklist);
 
         Ok(LLMSummaryResult {
-            summary: ollama_response.response,
+            summary,
             confidence,
             confidence_reason,
+            warnings,
         })
     }
 
-    fn build_prompt(&self, checklist: &[ChecklistItem], problem: &str) -> String {
+    fn build_prompt(&self, checklist: &[ChecklistItem], problem: &str, related: &[JiraLinkedIssue]) -> String {
         let mut checklist_text = String::new();
         for item in checklist {
             let checkbox = if item.checked { "[x]" } else { "[ ]" };
-            checklist_text.push_str(&format!("- {} {}\n", checkbox, item.text));
+            match &item.link {
+                Some(link) => checklist_text.push_str(&format!("- {} {} (procedure: {})\n", checkbox, item.text, link)),
+                None => checklist_text.push_str(&format!("- {} {}\n", checkbox, item.text)),
+            }
+        }
+
+        let mut related_text = String::new();
+        if !related.is_empty() {
+            related_text.push_str("\nRelated Jira issues:\n");
+            for issue in related {
+                related_text.push_str(&format!(
+                    "- {} {}: {} ({})\n",
+                    issue.relation, issue.key, issue.summary, issue.status
+                ));
+            }
         }
 
         format!(
@@ -100,8 +263,7 @@ Given the following problem and checklist of troubleshooting steps, generate a s
 Problem: {}
 
 Troubleshooting checklist:
-{}
-
+{}{}
 Generate output in exactly this format:
 
 ✓ Completed steps:
@@ -113,8 +275,10 @@ Generate output in exactly this format:
 ? Recommendations for L2:
 - [what L2 should investigate next]
 
-Keep it concise. Only include steps from the checklist above. Do not invent steps."#,
-            problem, checklist_text
+Keep it concise. Only include steps from the checklist above. Do not invent steps.
+When a step lists a procedure link, keep the link next to that step so L2 can follow it.
+If a related issue looks like a known problem record, say so in the recommendations."#,
+            problem, checklist_text, related_text
         )
     }
 
@@ -156,6 +320,21 @@ Keep it concise. Only include steps from the checklist above. Do not invent step
     }
 }
 
+/// Keep Ollama's error body (e.g. "model is loading") so retries can classify it
+async fn api_error(context: &str, response: reqwest::Response) -> AppError {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let detail = serde_json::from_str::<OllamaErrorResponse>(&body)
+        .map(|e| e.error)
+        .unwrap_or(body);
+
+    if detail.trim().is_empty() {
+        AppError::Ollama(format!("{}: {}", context, status))
+    } else {
+        AppError::Ollama(format!("{}: {}: {}", context, status, detail.trim()))
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct OllamaGenerateRequest {
     model: String,
@@ -168,6 +347,22 @@ struct OllamaGenerateResponse {
     response: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct OllamaErrorResponse {
+    error: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbedRequest {
+    model: String,
+    input: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,12 +371,12 @@ mod tests {
     fn test_confidence_high() {
         let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
         let checklist = vec![
-            ChecklistItem { text: "Step 1".to_string(), checked: true },
-            ChecklistItem { text: "Step 2".to_string(), checked: true },
-            ChecklistItem { text: "Step 3".to_string(), checked: true },
-            ChecklistItem { text: "Step 4".to_string(), checked: true },
-            ChecklistItem { text: "Step 5".to_string(), checked: false },
-            ChecklistItem { text: "Step 6".to_string(), checked: false },
+            ChecklistItem { text: "Step 1".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Step 2".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Step 3".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Step 4".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Step 5".to_string(), checked: false, link: None },
+            ChecklistItem { text: "Step 6".to_string(), checked: false, link: None },
         ];
         let (confidence, _) = client.calculate_confidence(&checklist);
         assert_eq!(confidence, "High");
@@ -191,9 +386,9 @@ mod tests {
     fn test_confidence_medium() {
         let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
         let checklist = vec![
-            ChecklistItem { text: "Step 1".to_string(), checked: true },
-            ChecklistItem { text: "Step 2".to_string(), checked: false },
-            ChecklistItem { text: "Step 3".to_string(), checked: false },
+            ChecklistItem { text: "Step 1".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Step 2".to_string(), checked: false, link: None },
+            ChecklistItem { text: "Step 3".to_string(), checked: false, link: None },
         ];
         let (confidence, _) = client.calculate_confidence(&checklist);
         assert_eq!(confidence, "Medium");
@@ -203,7 +398,7 @@ mod tests {
     fn test_confidence_low() {
         let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
         let checklist = vec![
-            ChecklistItem { text: "Step 1".to_string(), checked: true },
+            ChecklistItem { text: "Step 1".to_string(), checked: true, link: None },
         ];
         let (confidence, _) = client.calculate_confidence(&checklist);
         assert_eq!(confidence, "Low");
@@ -213,12 +408,38 @@ mod tests {
     fn test_prompt_formatting() {
         let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
         let checklist = vec![
-            ChecklistItem { text: "Restarted VPN".to_string(), checked: true },
-            ChecklistItem { text: "Checked logs".to_string(), checked: false },
+            ChecklistItem { text: "Restarted VPN".to_string(), checked: true, link: None },
+            ChecklistItem { text: "Checked logs".to_string(), checked: false, link: None },
         ];
-        let prompt = client.build_prompt(&checklist, "VPN connection fails");
+        let prompt = client.build_prompt(&checklist, "VPN connection fails", &[]);
         assert!(prompt.contains("VPN connection fails"));
         assert!(prompt.contains("[x] Restarted VPN"));
         assert!(prompt.contains("[ ] Checked logs"));
     }
+
+    #[test]
+    fn test_prompt_includes_links() {
+        let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
+        let checklist = vec![ChecklistItem {
+            text: "Flushed DNS".to_string(),
+            checked: true,
+            link: Some("https://kb.example.com/dns".to_string()),
+        }];
+        let prompt = client.build_prompt(&checklist, "Sites not resolving", &[]);
+        assert!(prompt.contains("[x] Flushed DNS (procedure: https://kb.example.com/dns)"));
+        assert!(!prompt.contains("Related Jira issues"));
+    }
+
+    #[test]
+    fn test_prompt_includes_related_issues() {
+        let client = OllamaClient::new("http://localhost:11434".to_string(), "llama3".to_string()).unwrap();
+        let related = vec![JiraLinkedIssue {
+            key: "PRB-12".to_string(),
+            summary: "VPN concentrator firmware".to_string(),
+            status: "Known Error".to_string(),
+            relation: "is caused by".to_string(),
+        }];
+        let prompt = client.build_prompt(&[], "VPN drops", &related);
+        assert!(prompt.contains("- is caused by PRB-12: VPN concentrator firmware (Known Error)"));
+    }
 }
@@ -0,0 +1,137 @@
+/// First-run onboarding
+///
+/// Steps are done in order: Jira credentials, a successful connection test,
+/// Ollama (optional, so it can be skipped) and picking a first template.
+/// Whether a step's prerequisite actually holds is checked by the command
+/// layer; this module records progress and keeps posting off until every
+/// required step is done.
+use crate::error::{AppError, AppResult};
+use crate::models::{OnboardingState, OnboardingStep, OnboardingStepStatus};
+use rusqlite::{params, Connection};
+
+pub fn state(conn: &Connection) -> AppResult<OnboardingState> {
+    let mut stmt = conn.prepare("SELECT step, skipped, completed_at FROM onboarding_steps")?;
+    let done = stmt
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let steps: Vec<OnboardingStepStatus> = OnboardingStep::ALL
+        .into_iter()
+        .map(|step| {
+            let record = done.iter().find(|(name, _, _)| name == step.as_str());
+            OnboardingStepStatus {
+                step,
+                required: step.is_required(),
+                completed: record.is_some_and(|(_, skipped, _)| !skipped),
+                skipped: record.is_some_and(|(_, skipped, _)| *skipped),
+                completed_at: record.map(|(_, _, at)| at.clone()),
+            }
+        })
+        .collect();
+
+    Ok(OnboardingState {
+        current_step: steps.iter().find(|s| !s.completed && !s.skipped).map(|s| s.step),
+        posting_enabled: steps.iter().all(|s| s.completed || !s.required),
+        steps,
+    })
+}
+
+/// Record a step as done (or skipped) once every earlier step is
+pub fn complete(conn: &Connection, step: OnboardingStep, skip: bool) -> AppResult<OnboardingState> {
+    if skip && step.is_required() {
+        return Err(AppError::Validation(format!(
+            "The {} step is required and can't be skipped",
+            step.as_str()
+        )));
+    }
+
+    let current = state(conn)?;
+    if let Some(pending) = current
+        .steps
+        .iter()
+        .take_while(|s| s.step != step)
+        .find(|s| !s.completed && !s.skipped)
+    {
+        return Err(AppError::Validation(format!(
+            "Finish the {} step before {}",
+            pending.step.as_str(),
+            step.as_str()
+        )));
+    }
+
+    conn.execute(
+        "INSERT INTO onboarding_steps (step, skipped) VALUES (?, ?)
+        ON CONFLICT(step) DO UPDATE SET skipped = excluded.skipped, completed_at = datetime('now')",
+        params![step.as_str(), skip],
+    )?;
+    state(conn)
+}
+
+/// Refuse to post until onboarding's required steps are done
+pub fn ensure_posting_enabled(conn: &Connection) -> AppResult<()> {
+    let state = state(conn)?;
+    if state.posting_enabled {
+        return Ok(());
+    }
+
+    let missing: Vec<&str> = state
+        .steps
+        .iter()
+        .filter(|s| s.required && !s.completed)
+        .map(|s| s.step.as_str())
+        .collect();
+    Err(AppError::Validation(format!(
+        "Finish setup before posting (remaining: {})",
+        missing.join(", ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute_batch(include_str!("../../migrations/029_onboarding.sql")).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_steps_in_order() {
+        let conn = conn();
+        let fresh = state(&conn).unwrap();
+        assert_eq!(fresh.current_step, Some(OnboardingStep::JiraConfig));
+        assert!(!fresh.posting_enabled);
+
+        assert!(complete(&conn, OnboardingStep::TestConnection, false).is_err());
+        complete(&conn, OnboardingStep::JiraConfig, false).unwrap();
+        let after = complete(&conn, OnboardingStep::TestConnection, false).unwrap();
+        assert_eq!(after.current_step, Some(OnboardingStep::Ollama));
+        assert!(ensure_posting_enabled(&conn).is_err());
+
+        // Ollama is optional; the template step is not
+        assert!(complete(&conn, OnboardingStep::FirstTemplate, true).is_err());
+        complete(&conn, OnboardingStep::Ollama, true).unwrap();
+        let done = complete(&conn, OnboardingStep::FirstTemplate, false).unwrap();
+        assert_eq!(done.current_step, None);
+        assert!(done.posting_enabled);
+        assert!(done.steps[2].skipped && !done.steps[2].completed);
+        assert!(ensure_posting_enabled(&conn).is_ok());
+    }
+
+    #[test]
+    fn test_existing_installs_start_onboarded() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/001_init.sql")).unwrap();
+        conn.execute(
+            "INSERT INTO escalations (ticket_id, problem_summary, checklist, current_status, next_steps, status, posted_at)
+            VALUES ('IT-1', 'VPN drops', '[]', '', '', 'posted', datetime('now'))",
+            [],
+        )
+        .unwrap();
+        conn.execute_batch(include_str!("../../migrations/029_onboarding.sql")).unwrap();
+
+        assert!(state(&conn).unwrap().posting_enabled);
+    }
+}
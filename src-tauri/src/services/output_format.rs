@@ -0,0 +1,235 @@
+/// Renders the markdown produced by the template engine in other flavors:
+/// Jira wiki markup (Server/Data Center), plain text (email) and ADF (Cloud)
+use crate::models::OutputFormat;
+use crate::services::adf;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+pub fn render(markdown: &str, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Markdown => markdown.to_string(),
+        OutputFormat::Adf => serde_json::to_string_pretty(&adf::markdown_to_adf(markdown)).unwrap_or_default(),
+        OutputFormat::JiraWiki => markdown_to_wiki(markdown),
+        OutputFormat::PlainText => markdown_to_plain(markdown),
+    }
+}
+
+fn parser(markdown: &str) -> Parser<'_> {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    Parser::new_ext(markdown, options)
+}
+
+/// Jira wiki markup, as accepted by the v2 comment API
+pub fn markdown_to_wiki(markdown: &str) -> String {
+    let mut out = String::new();
+    // One entry per open list: Some(_) for ordered
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    let mut links: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+    let mut in_table_head = false;
+
+    for event in parser(markdown) {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => out.push_str(&format!("h{}. ", level as u8)),
+            Event::End(TagEnd::Heading(_)) => out.push_str("\n\n"),
+            Event::Start(Tag::Paragraph) => {}
+            Event::End(TagEnd::Paragraph) => {
+                out.push('\n');
+                if lists.is_empty() {
+                    out.push('\n');
+                }
+            }
+            Event::Start(Tag::Strong) | Event::End(TagEnd::Strong) => out.push('*'),
+            Event::Start(Tag::Emphasis) | Event::End(TagEnd::Emphasis) => out.push('_'),
+            Event::Start(Tag::Strikethrough) | Event::End(TagEnd::Strikethrough) => out.push('-'),
+            Event::Start(Tag::BlockQuote(_)) => out.push_str("{quote}\n"),
+            Event::End(TagEnd::BlockQuote(_)) => out.push_str("{quote}\n\n"),
+            Event::Start(Tag::CodeBlock(kind)) => {
+                in_code_block = true;
+                match kind {
+                    CodeBlockKind::Fenced(lang) if !lang.is_empty() => out.push_str(&format!("{{code:{}}}\n", lang)),
+                    _ => out.push_str("{code}\n"),
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                out.push_str("{code}\n\n");
+            }
+            Event::Start(Tag::List(start)) => {
+                if !lists.is_empty() && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                lists.push(start);
+            }
+            Event::End(TagEnd::List(_)) => {
+                lists.pop();
+                if lists.is_empty() {
+                    out.push('\n');
+                }
+            }
+            Event::Start(Tag::Item) => {
+                let bullets: String = lists.iter().map(|l| if l.is_some() { '#' } else { '*' }).collect();
+                out.push_str(&bullets);
+                out.push(' ');
+            }
+            Event::End(TagEnd::Item) if !out.ends_with('\n') => out.push('\n'),
+            Event::TaskListMarker(checked) => out.push_str(if checked { "(/) " } else { "(x) " }),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                links.push(dest_url.to_string());
+                out.push('[');
+            }
+            Event::End(TagEnd::Link) => {
+                let url = links.pop().unwrap_or_default();
+                out.push_str(&format!("|{}]", url));
+            }
+            Event::Start(Tag::TableHead) => in_table_head = true,
+            Event::End(TagEnd::TableHead) => {
+                in_table_head = false;
+                out.push_str("||\n");
+            }
+            Event::Start(Tag::TableCell) => out.push_str(if in_table_head { "||" } else { "|" }),
+            Event::End(TagEnd::TableRow) => out.push_str("|\n"),
+            Event::End(TagEnd::Table) => out.push('\n'),
+            Event::Code(code) => out.push_str(&format!("{{{{{}}}}}", code)),
+            Event::Text(text) if in_code_block => out.push_str(&text),
+            Event::Text(text) => out.push_str(&escape_wiki(&text)),
+            Event::SoftBreak | Event::HardBreak => out.push('\n'),
+            Event::Rule => out.push_str("----\n\n"),
+            _ => {}
+        }
+    }
+
+    // Mentions are already wiki syntax; the bracket can arrive as its own text event
+    out.trim_end().replace("\\[~accountid:", "[~accountid:") + "\n"
+}
+
+/// Plain text for email: no markup, links spelled out, checklists as [x]/[ ]
+pub fn markdown_to_plain(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut list_depth = 0usize;
+    let mut links: Vec<String> = Vec::new();
+    let mut link_text = String::new();
+    let mut in_code_block = false;
+
+    for event in parser(markdown) {
+        match event {
+            Event::End(TagEnd::Heading(_)) => out.push_str("\n\n"),
+            Event::End(TagEnd::Paragraph) => {
+                out.push('\n');
+                if list_depth == 0 {
+                    out.push('\n');
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => {
+                in_code_block = false;
+                out.push('\n');
+            }
+            Event::Start(Tag::List(_)) => {
+                if list_depth > 0 && !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                list_depth += 1;
+            }
+            Event::End(TagEnd::List(_)) => {
+                list_depth -= 1;
+                if list_depth == 0 {
+                    out.push('\n');
+                }
+            }
+            Event::Start(Tag::Item) => {
+                out.push_str(&"  ".repeat(list_depth.saturating_sub(1)));
+                out.push_str("- ");
+            }
+            Event::End(TagEnd::Item) if !out.ends_with('\n') => out.push('\n'),
+            Event::TaskListMarker(checked) => out.push_str(if checked { "[x] " } else { "[ ] " }),
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                links.push(dest_url.to_string());
+                link_text.clear();
+            }
+            Event::End(TagEnd::Link) => {
+                let url = links.pop().unwrap_or_default();
+                if link_text == url {
+                    out.push_str(&url);
+                } else {
+                    out.push_str(&format!("{} ({})", link_text, url));
+                }
+            }
+            Event::End(TagEnd::TableCell) => out.push_str("  "),
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => out.push('\n'),
+            Event::End(TagEnd::Table) => out.push('\n'),
+            Event::Text(text) if in_code_block => {
+                for line in text.lines() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+            Event::Text(text) | Event::Code(text) if !links.is_empty() => link_text.push_str(&text),
+            Event::Text(text) | Event::Code(text) => out.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => out.push('\n'),
+            Event::Rule => out.push_str("----------\n\n"),
+            _ => {}
+        }
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Brackets and braces start links and macros in wiki markup
+fn escape_wiki(text: &str) -> String {
+    text.replace('[', "\\[").replace('{', "\\{")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "## Escalation: IT-42\n\n### Problem Summary\nVPN **drops** every `10` minutes.\n\n### Troubleshooting Steps\n- [x] Restarted client\n- [ ] Checked [logs](https://kb.example.com/logs)\n\n1. First\n2. Second\n";
+
+    #[test]
+    fn test_wiki() {
+        let wiki = markdown_to_wiki(SAMPLE);
+        assert!(wiki.contains("h2. Escalation: IT-42\n"));
+        assert!(wiki.contains("VPN *drops* every {{10}} minutes."));
+        assert!(wiki.contains("* (/) Restarted client\n"));
+        assert!(wiki.contains("* (x) Checked [logs|https://kb.example.com/logs]\n"));
+        assert!(wiki.contains("# First\n# Second"));
+    }
+
+    #[test]
+    fn test_wiki_nested_list_and_code() {
+        let wiki = markdown_to_wiki("- outer\n  - inner\n\n```sh\nping [host]\n```\n");
+        assert!(wiki.contains("* outer\n** inner\n"));
+        assert!(wiki.contains("{code:sh}\nping [host]\n{code}"));
+    }
+
+    #[test]
+    fn test_wiki_table() {
+        let wiki = markdown_to_wiki("| Host | Result |\n|---|---|\n| vpn1 | down |\n");
+        assert_eq!(wiki, "||Host||Result||\n|vpn1|down|\n");
+    }
+
+    #[test]
+    fn test_plain() {
+        let plain = markdown_to_plain(SAMPLE);
+        assert!(plain.starts_with("Escalation: IT-42\n\nProblem Summary\n\nVPN drops every 10 minutes."));
+        assert!(plain.contains("- [x] Restarted client\n"));
+        assert!(plain.contains("- [ ] Checked logs (https://kb.example.com/logs)\n"));
+        assert!(!plain.contains("**"));
+    }
+
+    #[test]
+    fn test_wiki_keeps_mentions() {
+        let wiki = markdown_to_wiki("[~accountid:5b10a2844c20165700ede21g] check [this] out");
+        assert_eq!(wiki, "[~accountid:5b10a2844c20165700ede21g] check \\[this] out\n");
+    }
+
+    #[test]
+    fn test_render_markdown_passthrough() {
+        assert_eq!(render(SAMPLE, OutputFormat::Markdown), SAMPLE);
+        assert!(render(SAMPLE, OutputFormat::Adf).contains("\"type\": \"doc\""));
+    }
+}
@@ -0,0 +1,151 @@
+/// Length budgets for the rendered escalation, per `###` section and overall
+///
+/// Jira rejects comments over 32,767 characters, and some L2 intake forms cap
+/// individual fields far lower. Over-budget output is trimmed at word
+/// boundaries and the full text travels as an attachment instead.
+use crate::models::{OutputSizeReport, SectionSize};
+use std::collections::HashMap;
+
+const TRUNCATED_MARK: &str = "… _(truncated)_";
+
+pub struct Budget {
+    pub max_chars: usize,
+    /// Keyed by section heading, compared case-insensitively
+    pub sections: HashMap<String, usize>,
+}
+
+impl Budget {
+    fn section_limit(&self, title: &str) -> Option<usize> {
+        self.sections
+            .iter()
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case(title.trim()))
+            .map(|(_, limit)| *limit)
+    }
+}
+
+/// Split on `###` headings; the part before the first heading has an empty title
+fn sections(markdown: &str) -> Vec<(String, String)> {
+    let mut sections = vec![(String::new(), String::new())];
+    for line in markdown.lines() {
+        if let Some(title) = line.strip_prefix("### ") {
+            sections.push((title.trim().to_string(), String::new()));
+        } else {
+            let body = &mut sections.last_mut().expect("at least one section").1;
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    sections
+}
+
+pub fn measure(markdown: &str, budget: &Budget) -> OutputSizeReport {
+    let sections: Vec<SectionSize> = sections(markdown)
+        .into_iter()
+        .filter(|(title, _)| !title.is_empty())
+        .map(|(title, body)| {
+            let chars = body.trim().chars().count();
+            let limit = budget.section_limit(&title);
+            SectionSize {
+                words: body.split_whitespace().count(),
+                over_budget: limit.is_some_and(|limit| chars > limit),
+                name: title,
+                chars,
+                limit,
+            }
+        })
+        .collect();
+
+    let total_chars = markdown.chars().count();
+    OutputSizeReport {
+        total_chars,
+        total_words: markdown.split_whitespace().count(),
+        max_chars: budget.max_chars,
+        over_budget: total_chars > budget.max_chars || sections.iter().any(|s| s.over_budget),
+        sections,
+    }
+}
+
+/// Trim over-budget sections, then the whole text, and end with `note`
+pub fn truncate(markdown: &str, budget: &Budget, note: &str) -> String {
+    let mut out = String::new();
+    for (title, body) in sections(markdown) {
+        if !title.is_empty() {
+            out.push_str(&format!("### {}\n", title));
+        }
+        match budget.section_limit(&title) {
+            Some(limit) if body.trim().chars().count() > limit => {
+                out.push_str(&cut(body.trim(), limit));
+                out.push_str(TRUNCATED_MARK);
+                out.push_str("\n\n");
+            }
+            _ => out.push_str(&body),
+        }
+    }
+
+    let note = format!("\n\n---\n{}\n", note);
+    let room = budget.max_chars.saturating_sub(note.chars().count() + TRUNCATED_MARK.chars().count());
+    let mut out = if out.chars().count() > room {
+        cut(&out, room) + TRUNCATED_MARK
+    } else {
+        out.trim_end().to_string()
+    };
+    out.push_str(&note);
+    out
+}
+
+/// First `limit` characters, backed off to the last whitespace when there is one
+fn cut(text: &str, limit: usize) -> String {
+    let end = text.char_indices().nth(limit).map(|(i, _)| i).unwrap_or(text.len());
+    let head = &text[..end];
+    match head.rfind(char::is_whitespace) {
+        Some(i) if i > 0 && end < text.len() => head[..i].trim_end().to_string(),
+        _ => head.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "## Escalation: IT-1\n\n### Problem Summary\nThe VPN client disconnects every ten minutes for remote staff.\n\n### Next Steps\nCheck RADIUS logs.\n";
+
+    fn budget(max_chars: usize, sections: &[(&str, usize)]) -> Budget {
+        Budget {
+            max_chars,
+            sections: sections.iter().map(|(n, l)| (n.to_string(), *l)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_measure_sections() {
+        let report = measure(SAMPLE, &budget(1000, &[("problem summary", 20)]));
+        assert!(report.over_budget);
+        assert_eq!(report.sections.len(), 2);
+        assert_eq!(report.sections[0].name, "Problem Summary");
+        assert_eq!(report.sections[0].limit, Some(20));
+        assert!(report.sections[0].over_budget);
+        assert!(!report.sections[1].over_budget);
+        assert_eq!(report.sections[1].words, 3);
+    }
+
+    #[test]
+    fn test_within_budget() {
+        assert!(!measure(SAMPLE, &budget(1000, &[])).over_budget);
+    }
+
+    #[test]
+    fn test_truncate_section_at_word_boundary() {
+        let out = truncate(SAMPLE, &budget(1000, &[("Problem Summary", 20)]), "Full text attached");
+        assert!(out.contains("### Problem Summary\nThe VPN client… _(truncated)_"));
+        assert!(out.contains("### Next Steps\nCheck RADIUS logs."));
+        assert!(out.ends_with("---\nFull text attached\n"));
+    }
+
+    #[test]
+    fn test_truncate_total() {
+        let out = truncate(SAMPLE, &budget(80, &[]), "Full text attached");
+        assert!(out.chars().count() <= 80);
+        assert!(out.starts_with("## Escalation: IT-1"));
+        assert!(out.ends_with("Full text attached\n"));
+    }
+}
@@ -0,0 +1,144 @@
+/// Keyword and checklist heuristics for suggesting a Jira priority
+///
+/// Levels use Jira's default priority scheme names. Keywords set the
+/// baseline; a fully worked checklist bumps it one level (never to Highest,
+/// which only impact keywords can justify).
+use crate::models::{Escalation, PrioritySuggestion};
+use regex::Regex;
+
+pub const PRIORITIES: [&str; 4] = ["Low", "Medium", "High", "Highest"];
+
+const HIGHEST_KEYWORDS: &[&str] = &[
+    "outage",
+    "all users",
+    "everyone",
+    "data loss",
+    "lost data",
+    "security breach",
+    "ransomware",
+    "site down",
+];
+
+const HIGH_KEYWORDS: &[&str] = &[
+    "production",
+    "multiple users",
+    "several users",
+    "whole team",
+    "cannot work",
+    "can't work",
+    "blocked",
+    "deadline",
+    "vip",
+];
+
+const LOW_KEYWORDS: &[&str] = &["cosmetic", "typo", "question", "how do i", "feature request", "when convenient"];
+
+pub fn suggest(escalation: &Escalation) -> PrioritySuggestion {
+    let text = format!(
+        "{}\n{}\n{}",
+        escalation.problem_summary, escalation.current_status, escalation.next_steps
+    )
+    .to_lowercase();
+
+    let mut reasons = Vec::new();
+    let mut level = 1;
+
+    if let Some(hits) = matches(&text, HIGHEST_KEYWORDS) {
+        level = 3;
+        reasons.push(format!("Mentions {}", hits));
+    } else if let Some(hits) = matches(&text, HIGH_KEYWORDS) {
+        level = 2;
+        reasons.push(format!("Mentions {}", hits));
+    } else if let Some(hits) = matches(&text, LOW_KEYWORDS) {
+        level = 0;
+        reasons.push(format!("Mentions {}", hits));
+    }
+
+    let total = escalation.checklist.len();
+    let checked = escalation.checklist.iter().filter(|item| item.checked).count();
+    if total > 0 && checked == total && level < 2 {
+        level += 1;
+        reasons.push(format!("All {} checklist steps tried without a fix", total));
+    }
+
+    if reasons.is_empty() {
+        reasons.push("No impact keywords found".to_string());
+    }
+
+    PrioritySuggestion {
+        priority: PRIORITIES[level].to_string(),
+        reasons,
+        source: "heuristic".to_string(),
+    }
+}
+
+/// Match a model reply against the known priority names
+pub fn parse_priority(reply: &str) -> Option<&'static str> {
+    let first = reply.split_whitespace().next()?;
+    let word = first.trim_matches(|c: char| !c.is_alphanumeric());
+    PRIORITIES.iter().copied().find(|p| p.eq_ignore_ascii_case(word))
+}
+
+fn matches(text: &str, keywords: &[&str]) -> Option<String> {
+    let hits: Vec<String> = keywords
+        .iter()
+        .filter(|k| contains_phrase(text, k))
+        .map(|k| format!("\"{}\"", k))
+        .collect();
+
+    (!hits.is_empty()).then(|| hits.join(", "))
+}
+
+/// Whole-word match, so "reproduction" isn't "production" and "unblocked" isn't "blocked"
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    Regex::new(&format!(r"\b{}\b", regex::escape(phrase)))
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChecklistItem;
+
+    fn escalation(summary: &str, checked: &[bool]) -> Escalation {
+        Escalation {
+            problem_summary: summary.to_string(),
+            checklist: checked
+                .iter()
+                .map(|&c| ChecklistItem { text: "Step".to_string(), checked: c, link: None })
+                .collect(),
+            ..Escalation::fixture()
+        }
+    }
+
+    #[test]
+    fn test_outage_is_highest() {
+        let result = suggest(&escalation("Email outage affecting all users", &[false]));
+        assert_eq!(result.priority, "Highest");
+        assert!(result.reasons[0].contains("outage"));
+    }
+
+    #[test]
+    fn test_completed_checklist_bumps_level() {
+        assert_eq!(suggest(&escalation("Printer jams", &[true, false])).priority, "Medium");
+        assert_eq!(suggest(&escalation("Printer jams", &[true, true])).priority, "High");
+        assert_eq!(suggest(&escalation("Cosmetic glitch in footer", &[true])).priority, "Medium");
+    }
+
+    #[test]
+    fn test_keywords_match_whole_words() {
+        let result = suggest(&escalation("Reproduction steps attached; user is unblocked now", &[false]));
+        assert_eq!(result.priority, "Medium");
+        assert_eq!(result.reasons, vec!["No impact keywords found".to_string()]);
+
+        assert_eq!(suggest(&escalation("Blocked on the production VPN", &[false])).priority, "High");
+    }
+
+    #[test]
+    fn test_parse_priority() {
+        assert_eq!(parse_priority("high\n\nBecause several users..."), Some("High"));
+        assert_eq!(parse_priority("**Highest**"), Some("Highest"));
+        assert_eq!(parse_priority("Urgent"), None);
+    }
+}
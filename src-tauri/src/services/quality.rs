@@ -0,0 +1,163 @@
+/// Heuristic quality score for an escalation draft (0-100)
+///
+/// Weights: checklist completion 40, problem summary length 25,
+/// next steps present 20, attachments 15.
+use crate::models::{Attachment, ChecklistItem, Escalation, QualityFactor, QualityScore};
+
+const CHECKLIST_WEIGHT: u32 = 40;
+const SUMMARY_WEIGHT: u32 = 25;
+const NEXT_STEPS_WEIGHT: u32 = 20;
+const ATTACHMENTS_WEIGHT: u32 = 15;
+
+/// Problem summaries this long (in characters) get full marks
+const SUMMARY_TARGET_CHARS: usize = 200;
+/// Next steps shorter than this are treated as placeholders ("TBD", "see above")
+const NEXT_STEPS_MIN_CHARS: usize = 20;
+/// Attachments needed for full marks
+const ATTACHMENTS_TARGET: usize = 2;
+
+pub fn score(escalation: &Escalation) -> QualityScore {
+    score_fields(
+        &escalation.checklist,
+        &escalation.problem_summary,
+        &escalation.next_steps,
+        escalation.attachments.len(),
+    )
+}
+
+/// Score from just the fields the heuristic looks at, for reports that don't load whole escalations
+pub fn score_fields(checklist: &[ChecklistItem], problem_summary: &str, next_steps: &str, attachment_count: usize) -> QualityScore {
+    let factors = vec![
+        checklist_factor(checklist),
+        summary_factor(problem_summary),
+        next_steps_factor(next_steps),
+        attachments_factor(attachment_count),
+    ];
+
+    let score = factors.iter().map(|f| f.points).sum();
+    QualityScore { score, factors }
+}
+
+fn checklist_factor(checklist: &[ChecklistItem]) -> QualityFactor {
+    let total = checklist.len();
+    let checked = checklist.iter().filter(|item| item.checked).count();
+
+    let points = (CHECKLIST_WEIGHT as usize * checked)
+        .checked_div(total)
+        .unwrap_or(0) as u32;
+
+    QualityFactor {
+        name: "checklist_completion".to_string(),
+        points,
+        max_points: CHECKLIST_WEIGHT,
+        note: format!("{} of {} checklist items completed", checked, total),
+    }
+}
+
+fn summary_factor(problem_summary: &str) -> QualityFactor {
+    let chars = problem_summary.trim().chars().count();
+    let points = (SUMMARY_WEIGHT as usize * chars.min(SUMMARY_TARGET_CHARS) / SUMMARY_TARGET_CHARS) as u32;
+
+    QualityFactor {
+        name: "summary_length".to_string(),
+        points,
+        max_points: SUMMARY_WEIGHT,
+        note: format!("Problem summary is {} characters (target {})", chars, SUMMARY_TARGET_CHARS),
+    }
+}
+
+fn next_steps_factor(next_steps: &str) -> QualityFactor {
+    let present = next_steps.trim().chars().count() >= NEXT_STEPS_MIN_CHARS;
+
+    QualityFactor {
+        name: "next_steps".to_string(),
+        points: if present { NEXT_STEPS_WEIGHT } else { 0 },
+        max_points: NEXT_STEPS_WEIGHT,
+        note: if present {
+            "Next steps provided".to_string()
+        } else {
+            "Next steps missing or too short".to_string()
+        },
+    }
+}
+
+fn attachments_factor(count: usize) -> QualityFactor {
+    let points = (ATTACHMENTS_WEIGHT as usize * count.min(ATTACHMENTS_TARGET) / ATTACHMENTS_TARGET) as u32;
+
+    QualityFactor {
+        name: "attachments".to_string(),
+        points,
+        max_points: ATTACHMENTS_WEIGHT,
+        note: format!("{} attachment(s)", count),
+    }
+}
+
+/// Expected artifacts that no attachment has been mapped to
+pub fn missing_artifacts(expected: &[String], attachments: &[Attachment]) -> Vec<String> {
+    expected
+        .iter()
+        .filter(|artifact| {
+            !attachments.iter().any(|a| {
+                a.artifact
+                    .as_deref()
+                    .is_some_and(|mapped| mapped.trim().eq_ignore_ascii_case(artifact.trim()))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escalation(checked: &[bool], summary: &str, next_steps: &str) -> Escalation {
+        Escalation {
+            problem_summary: summary.to_string(),
+            checklist: checked
+                .iter()
+                .map(|&c| ChecklistItem { text: "Step".to_string(), checked: c, link: None })
+                .collect(),
+            next_steps: next_steps.to_string(),
+            ..Escalation::fixture()
+        }
+    }
+
+    #[test]
+    fn test_thin_escalation_scores_low() {
+        let result = score(&escalation(&[false, false], "broken", ""));
+        assert!(result.score < 10);
+        assert_eq!(result.factors.len(), 4);
+    }
+
+    #[test]
+    fn test_complete_escalation_scores_high() {
+        let summary = "x".repeat(SUMMARY_TARGET_CHARS);
+        let result = score(&escalation(
+            &[true, true, true, true],
+            &summary,
+            "Check RADIUS server logs for this user",
+        ));
+        // Everything except attachments
+        assert_eq!(result.score, 100 - ATTACHMENTS_WEIGHT);
+    }
+
+    #[test]
+    fn test_missing_artifacts() {
+        let attachment = |artifact: Option<&str>| Attachment {
+            id: 1,
+            escalation_id: 1,
+            file_path: "/tmp/file".to_string(),
+            description: None,
+            created_at: String::new(),
+            purged_at: None,
+            artifact: artifact.map(str::to_string),
+            annotations: None,
+        };
+        let expected = vec!["Client log".to_string(), "Network trace".to_string()];
+
+        let missing = missing_artifacts(&expected, &[attachment(Some("client log")), attachment(None)]);
+        assert_eq!(missing, vec!["Network trace".to_string()]);
+        assert!(missing_artifacts(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,323 @@
+/// Burn redaction rectangles into screenshots before they are uploaded, and
+/// strip personal details from free text that leaves the team
+///
+/// The original file is never modified: a redacted copy is written to a temp
+/// directory, uploaded in its place and then removed. Only PNG can be
+/// re-encoded here, so redacting any other format is refused rather than
+/// uploading the image as-is.
+use crate::error::{AppError, AppResult};
+use crate::models::{Attachment, ImageRect};
+use crate::services::audit::{scrub_secrets, EMAIL_RE};
+use crate::services::ticket_keys::JIRA_SERVER_PATTERN;
+use once_cell::sync::Lazy;
+use png::{BitDepth, ColorType, Decoder, Encoder, Transformations};
+use regex::Regex;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Redactions to apply to an upload path, matched against the escalation's attachments
+///
+/// Uploads may come from the original path or the staged copy, so a path matches
+/// an attachment with the same path, or else the single attachment with the same
+/// file name. An ambiguous match on a redacted file is an error.
+pub fn redactions_for<'a>(path: &str, attachments: &'a [Attachment]) -> AppResult<&'a [ImageRect]> {
+    let redactions = |a: &'a Attachment| a.annotations.as_ref().map_or(&[][..], |n| n.redactions.as_slice());
+
+    if let Some(exact) = attachments.iter().find(|a| a.file_path == path) {
+        return Ok(redactions(exact));
+    }
+
+    let file_name = Path::new(path).file_name();
+    let same_name: Vec<_> = attachments
+        .iter()
+        .filter(|a| Path::new(&a.file_path).file_name() == file_name)
+        .collect();
+
+    match same_name.as_slice() {
+        [only] => Ok(redactions(only)),
+        several if several.iter().any(|a| !redactions(a).is_empty()) => Err(AppError::Validation(format!(
+            "Can't tell which attachment {} is, and one of them has redactions. Re-attach it from the saved escalation.",
+            path
+        ))),
+        _ => Ok(&[]),
+    }
+}
+
+/// Write a redacted copy of `path` for upload, keeping the file name
+pub fn redacted_copy(path: &Path, redactions: &[ImageRect]) -> AppResult<PathBuf> {
+    let is_png = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    if !is_png {
+        return Err(AppError::Validation(format!(
+            "{} has redactions, but only PNG screenshots can be redacted",
+            path.display()
+        )));
+    }
+
+    let redacted = redact_png(&fs::read(path)?, redactions)?;
+
+    let dir = std::env::temp_dir()
+        .join("ticket-handoff-redacted")
+        .join(format!("{:016x}", rand::random::<u64>()));
+    fs::create_dir_all(&dir)?;
+    let target = dir.join(path.file_name().unwrap_or_default());
+    fs::write(&target, redacted)?;
+    Ok(target)
+}
+
+/// Remove a copy made by `redacted_copy` along with its directory
+pub fn remove_copy(path: &Path) {
+    let _ = fs::remove_file(path);
+    if let Some(dir) = path.parent() {
+        let _ = fs::remove_dir(dir);
+    }
+}
+
+/// Fill each rectangle with opaque black; rectangles are clipped to the image
+///
+/// The image is re-encoded as 8-bit, which also drops text chunks and other
+/// metadata that could carry the same details.
+pub fn redact_png(bytes: &[u8], redactions: &[ImageRect]) -> AppResult<Vec<u8>> {
+    let mut decoder = Decoder::new(Cursor::new(bytes));
+    decoder.set_transformations(Transformations::normalize_to_color8());
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| AppError::File(format!("Cannot read PNG: {}", e)))?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut pixels)
+        .map_err(|e| AppError::File(format!("Cannot read PNG: {}", e)))?;
+    pixels.truncate(info.buffer_size());
+
+    let channels = info.color_type.samples();
+    for rect in redactions {
+        let x_end = rect.x.saturating_add(rect.width).min(info.width) as usize;
+        let y_end = rect.y.saturating_add(rect.height).min(info.height) as usize;
+        for y in (rect.y as usize)..y_end {
+            for x in (rect.x as usize)..x_end {
+                let pixel = y * info.line_size + x * channels;
+                fill_black(&mut pixels[pixel..pixel + channels], info.color_type);
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = Encoder::new(&mut out, info.width, info.height);
+    encoder.set_color(info.color_type);
+    encoder.set_depth(BitDepth::Eight);
+    let writer = encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels).map(|_| writer))
+        .map_err(|e| AppError::File(format!("Cannot write redacted PNG: {}", e)))?;
+    writer
+        .finish()
+        .map_err(|e| AppError::File(format!("Cannot write redacted PNG: {}", e)))?;
+    Ok(out)
+}
+
+/// Encode 8-bit RGBA pixels, as the clipboard provides them, as a PNG
+pub fn encode_rgba_png(width: u32, height: u32, rgba: &[u8]) -> AppResult<Vec<u8>> {
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(AppError::File(format!(
+            "Image data doesn't match its {}x{} size",
+            width, height
+        )));
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = Encoder::new(&mut out, width, height);
+    encoder.set_color(ColorType::Rgba);
+    encoder.set_depth(BitDepth::Eight);
+    let writer = encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(rgba).map(|_| writer))
+        .map_err(|e| AppError::File(format!("Cannot write PNG: {}", e)))?;
+    writer
+        .finish()
+        .map_err(|e| AppError::File(format!("Cannot write PNG: {}", e)))?;
+    Ok(out)
+}
+
+static MENTION_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\[~(?:accountid:)?[^\]]+\]").expect("valid mention regex"));
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\b(?:https?|ftp)://[^\s<>()\[\]]+").expect("valid URL regex"));
+static IP_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b\d{1,3}(?:\.\d{1,3}){3}\b").expect("valid IP address regex"));
+// International numbers with a leading +, or the 3-3-4 grouping; plain dates don't match either
+static PHONE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\+\d[\d .-]{6,}\d|\(?\b\d{3}\)?[ .-]\d{3}[ .-]\d{4}\b").expect("valid phone number regex")
+});
+
+/// Replaces personal details in free text with placeholders like `[email]`
+///
+/// Covers credentials, @mentions, URLs, email and IP addresses, phone numbers,
+/// ticket keys and the given people's names. Names are also matched by each
+/// word of three or more letters, so "Dana" goes when "Dana Agent" is known;
+/// that over-redacts common words in some names, which is the safe direction.
+pub struct TextRedactor {
+    ticket_keys: Regex,
+    names: Option<Regex>,
+}
+
+impl TextRedactor {
+    pub fn new(ticket_key_pattern: &str, names: &[String]) -> AppResult<Self> {
+        let ticket_keys = Regex::new(&format!(r"\b(?:{}|{})\b", ticket_key_pattern, JIRA_SERVER_PATTERN))
+            .map_err(|e| AppError::Validation(format!("Invalid ticket key pattern: {}", e)))?;
+
+        let mut words: Vec<&str> = names
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .flat_map(|name| std::iter::once(name).chain(name.split_whitespace().filter(|w| w.chars().count() >= 3)))
+            .collect();
+        // Longest first, so a full name is replaced before its parts
+        words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+        words.dedup();
+        let names = if words.is_empty() {
+            None
+        } else {
+            let alternatives: Vec<String> = words.iter().map(|w| regex::escape(w)).collect();
+            Some(
+                Regex::new(&format!(r"(?i)\b(?:{})\b", alternatives.join("|")))
+                    .map_err(|e| AppError::Validation(format!("Cannot match names: {}", e)))?,
+            )
+        };
+
+        Ok(Self { ticket_keys, names })
+    }
+
+    pub fn redact(&self, text: &str) -> String {
+        let text = URL_RE.replace_all(text, "[url]");
+        let text = scrub_secrets(&text);
+        let text = MENTION_RE.replace_all(&text, "[person]");
+        let text = EMAIL_RE.replace_all(&text, "[email]");
+        let text = IP_RE.replace_all(&text, "[ip]");
+        let text = PHONE_RE.replace_all(&text, "[phone]");
+        let text = self.ticket_keys.replace_all(&text, "[ticket]");
+        match &self.names {
+            Some(names) => names.replace_all(&text, "[name]").into_owned(),
+            None => text.into_owned(),
+        }
+    }
+}
+
+fn fill_black(pixel: &mut [u8], color_type: ColorType) {
+    match color_type {
+        // Force opaque so the redaction can't be undone by dropping the alpha channel
+        ColorType::GrayscaleAlpha | ColorType::Rgba => {
+            let (color, alpha) = pixel.split_at_mut(pixel.len() - 1);
+            color.fill(0);
+            alpha[0] = 255;
+        }
+        _ => pixel.fill(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ImageAnnotations;
+
+    fn white_png(width: u32, height: u32) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut encoder = Encoder::new(&mut out, width, height);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&vec![255; (width * height * 3) as usize]).unwrap();
+        writer.finish().unwrap();
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Vec<u8> {
+        let mut reader = Decoder::new(Cursor::new(bytes)).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        pixels
+    }
+
+    fn attachment(file_path: &str, redactions: Vec<ImageRect>) -> Attachment {
+        Attachment {
+            id: 1,
+            escalation_id: 1,
+            file_path: file_path.to_string(),
+            description: None,
+            created_at: String::new(),
+            purged_at: None,
+            artifact: None,
+            annotations: Some(ImageAnnotations { redactions, ..Default::default() }),
+        }
+    }
+
+    #[test]
+    fn test_redact_png_blacks_out_clipped_rectangles() {
+        let redactions = [
+            ImageRect { x: 1, y: 1, width: 2, height: 1 },
+            ImageRect { x: 3, y: 3, width: 100, height: 100 },
+        ];
+        let pixels = decode(&redact_png(&white_png(4, 4), &redactions).unwrap());
+
+        let black: Vec<(usize, usize)> = (0..16)
+            .filter(|i| pixels[i * 3] == 0)
+            .map(|i| (i % 4, i / 4))
+            .collect();
+        assert_eq!(black, vec![(1, 1), (2, 1), (3, 3)]);
+    }
+
+    #[test]
+    fn test_redactions_for_matches_original_or_staged_path() {
+        let rect = ImageRect { x: 0, y: 0, width: 1, height: 1 };
+        let attachments = vec![
+            attachment("/data/attachments/1/0/screen.png", vec![rect]),
+            attachment("/data/attachments/1/1/log.txt", vec![]),
+        ];
+
+        assert_eq!(redactions_for("/data/attachments/1/0/screen.png", &attachments).unwrap(), &[rect]);
+        assert_eq!(redactions_for("/Users/me/Desktop/screen.png", &attachments).unwrap(), &[rect]);
+        assert!(redactions_for("/Users/me/other.png", &attachments).unwrap().is_empty());
+
+        let ambiguous = vec![
+            attachment("/data/attachments/1/0/screen.png", vec![rect]),
+            attachment("/data/attachments/1/1/screen.png", vec![]),
+        ];
+        assert!(redactions_for("/Users/me/screen.png", &ambiguous).is_err());
+    }
+
+    #[test]
+    fn test_encode_rgba_png() {
+        let rgba = [255u8, 0, 0, 255].repeat(6);
+        let png = encode_rgba_png(3, 2, &rgba).unwrap();
+        assert_eq!(decode(&png), rgba);
+        assert!(encode_rgba_png(3, 3, &rgba).is_err());
+    }
+
+    #[test]
+    fn test_only_png_can_be_redacted() {
+        let rect = ImageRect { x: 0, y: 0, width: 1, height: 1 };
+        assert!(redacted_copy(Path::new("/tmp/photo.jpg"), &[rect]).is_err());
+    }
+
+    #[test]
+    fn test_redact_text() {
+        let redactor = TextRedactor::new(r"INC\d{7}", &["Dana Agent".to_string()]).unwrap();
+        let text = "Dana Agent (dana@example.com) saw IT-4521 and INC0012345 fail from 10.2.3.4, see \
+            https://intranet/vpn?token=abc and call +44 20 7946 0958 or 415-555-0134. password=hunter2 \
+            [~accountid:5b10a] agreed; Dana retried.";
+        assert_eq!(
+            redactor.redact(text),
+            "[name] ([email]) saw [ticket] and [ticket] fail from [ip], see [url] and call [phone] or [phone]. \
+            password=[REDACTED] [person] agreed; [name] retried."
+        );
+    }
+
+    #[test]
+    fn test_redact_text_keeps_structure() {
+        let redactor = TextRedactor::new(r"[A-Z][A-Z0-9]+-\d+", &[]).unwrap();
+        let text = "Restarted the VPN client on 2024-01-15, cleared 3 cached profiles";
+        assert_eq!(redactor.redact(text), text);
+    }
+
+}
@@ -0,0 +1,110 @@
+/// Collects external resources (dashboards, incident docs, runbooks) referenced
+/// by an escalation so they can be added to the ticket as Jira remote links.
+use crate::models::Escalation;
+use crate::services::template_engine;
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static URL_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"https?://[^\s<>()\[\]"'`]+"#).expect("valid URL regex"));
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteLink {
+    pub url: String,
+    pub title: String,
+}
+
+/// Extract http(s) URLs from free text, dropping trailing punctuation
+pub fn extract_urls(text: &str) -> Vec<String> {
+    URL_RE
+        .find_iter(text)
+        .map(|m| m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']).to_string())
+        .collect()
+}
+
+/// Gather unique links from checklist items and the free-text fields
+pub fn collect_links(escalation: &Escalation) -> Vec<RemoteLink> {
+    let mut links: Vec<RemoteLink> = Vec::new();
+
+    let mut push = |url: String, title: String| {
+        if !links.iter().any(|l| l.url == url) {
+            links.push(RemoteLink { url, title });
+        }
+    };
+
+    // Links saved before they were validated may not be http(s)
+    for item in &escalation.checklist {
+        if let Some(link) = item.link.as_deref().filter(|link| template_engine::is_web_link(link)) {
+            push(link.to_string(), item.text.clone());
+        }
+    }
+
+    for text in [
+        &escalation.problem_summary,
+        &escalation.current_status,
+        &escalation.next_steps,
+    ] {
+        for url in extract_urls(text) {
+            push(url.clone(), url);
+        }
+    }
+
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChecklistItem;
+
+    #[test]
+    fn test_extract_urls() {
+        let text = "See dashboard https://grafana.example.com/d/vpn?from=now-1h. Incident doc: (https://docs.example.com/inc-42)";
+        assert_eq!(
+            extract_urls(text),
+            vec![
+                "https://grafana.example.com/d/vpn?from=now-1h".to_string(),
+                "https://docs.example.com/inc-42".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_none() {
+        assert!(extract_urls("No links here").is_empty());
+    }
+
+    #[test]
+    fn test_collect_links() {
+        let item = |text: &str, link: Option<&str>| ChecklistItem {
+            text: text.to_string(),
+            checked: true,
+            link: link.map(str::to_string),
+        };
+        let escalation = Escalation {
+            checklist: vec![
+                item("Followed the VPN runbook", Some("https://kb.example.com/vpn")),
+                item("Clicked a bad link", Some("javascript:alert(1)")),
+                item("No link", None),
+            ],
+            problem_summary: "Dashboard https://grafana.example.com/d/vpn shows drops".to_string(),
+            next_steps: "Compare with https://kb.example.com/vpn and https://docs.example.com/inc-42.".to_string(),
+            ..Escalation::fixture()
+        };
+
+        assert_eq!(
+            collect_links(&escalation),
+            vec![
+                RemoteLink { url: "https://kb.example.com/vpn".to_string(), title: "Followed the VPN runbook".to_string() },
+                RemoteLink {
+                    url: "https://grafana.example.com/d/vpn".to_string(),
+                    title: "https://grafana.example.com/d/vpn".to_string(),
+                },
+                RemoteLink {
+                    url: "https://docs.example.com/inc-42".to_string(),
+                    title: "https://docs.example.com/inc-42".to_string(),
+                },
+            ]
+        );
+    }
+}
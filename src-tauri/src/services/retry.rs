@@ -1,4 +1,5 @@
 use crate::error::AppError;
+use crate::services::metrics::METRICS;
 use std::future::Future;
 use std::time::Duration;
 use tokio::time::sleep;
@@ -29,6 +30,7 @@ where
                 return Err(e);
             }
             Err(e) => {
+                METRICS.record_retry();
                 let delay_ms = calculate_backoff(attempt);
                 log::warn!(
                     "Attempt {}/{} failed: {}. Retrying in {}ms",
@@ -63,6 +65,7 @@ fn is_retryable(error: &AppError) -> bool {
             // Retry on timeout, connection errors, or 5xx server errors
             e.is_timeout()
                 || e.is_connect()
+                || is_connection_dropped(e)
                 || e.status()
                     .map(|s| s.is_server_error() || s.as_u16() == 429)
                     .unwrap_or(false)
@@ -76,11 +79,16 @@ fn is_retryable(error: &AppError) -> bool {
                 || msg.contains("timeout")
                 || msg.contains("connection")
         }
-        // Ollama errors
+        // Ollama errors; 503 and "loading" mean the model is still being loaded into memory
         AppError::Ollama(msg) => {
+            let msg = msg.to_lowercase();
             msg.contains("connection")
                 || msg.contains("timeout")
                 || msg.contains("unavailable")
+                || msg.contains("reset")
+                || msg.contains("loading")
+                || msg.contains("server busy")
+                || msg.contains("503")
         }
         // Don't retry these
         AppError::Db(_)
@@ -94,6 +102,24 @@ fn is_retryable(error: &AppError) -> bool {
     }
 }
 
+/// Connection reset or closed mid-request, e.g. Ollama restarting while loading a model
+fn is_connection_dropped(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(err) = source {
+        if let Some(io) = err.downcast_ref::<std::io::Error>() {
+            return matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            );
+        }
+        source = err.source();
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,10 +149,17 @@ mod tests {
         assert!(is_retryable(&AppError::Jira("429 Too Many Requests".into())));
         assert!(is_retryable(&AppError::Jira("503 Service Unavailable".into())));
         assert!(is_retryable(&AppError::Ollama("connection refused".into())));
+        assert!(is_retryable(&AppError::Ollama(
+            "Ollama API error: 503 Service Unavailable: server busy, model is loading".into()
+        )));
+        assert!(is_retryable(&AppError::Ollama("Connection reset by peer".into())));
 
         // Not retryable
         assert!(!is_retryable(&AppError::Validation("bad input".into())));
         assert!(!is_retryable(&AppError::NotFound("not found".into())));
         assert!(!is_retryable(&AppError::Jira("401 Unauthorized".into())));
+        assert!(!is_retryable(&AppError::Ollama(
+            "Ollama API error: 404 Not Found: model \"llama3\" not found, try pulling it first".into()
+        )));
     }
 }
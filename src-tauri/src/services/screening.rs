@@ -0,0 +1,217 @@
+/// Pre-upload safety screening for attachments
+///
+/// Checks, in order: blocked extensions, disguised double extensions
+/// (`invoice.pdf.exe`), and an optional ClamAV scan over clamd's unix socket.
+/// Once a socket is configured the scan fails closed: a file is only allowed
+/// when clamd explicitly answers OK.
+use crate::models::{ScreeningIssue, ScreeningReport, ScreeningResult, ScreeningSeverity};
+use std::path::Path;
+
+/// Extensions attackers commonly use as the "visible" part of a double extension
+const DECOY_EXTENSIONS: &[&str] = &[
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "txt", "jpg", "jpeg", "png", "gif",
+];
+
+pub struct ScreeningConfig<'a> {
+    pub blocked_extensions: &'a [String],
+    pub clamav_socket: Option<&'a str>,
+}
+
+pub fn screen_files(file_paths: &[String], config: &ScreeningConfig) -> ScreeningReport {
+    let results: Vec<ScreeningResult> = file_paths.iter().map(|p| screen_file(p, config)).collect();
+    let blocked_count = results.iter().filter(|r| !r.allowed).count();
+
+    ScreeningReport { results, blocked_count }
+}
+
+pub fn screen_file(file_path: &str, config: &ScreeningConfig) -> ScreeningResult {
+    let mut issues = Vec::new();
+
+    let file_name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(file_path)
+        .to_lowercase();
+    let extensions: Vec<&str> = file_name.split('.').skip(1).collect();
+
+    if let Some(ext) = extensions.last() {
+        if config.blocked_extensions.iter().any(|b| b.trim_start_matches('.').eq_ignore_ascii_case(ext)) {
+            issues.push(issue(
+                ScreeningSeverity::Block,
+                "blocked_extension",
+                format!("Files of type .{} are not allowed", ext),
+            ));
+        }
+    }
+
+    if extensions.len() >= 2 {
+        let decoy = extensions[extensions.len() - 2];
+        let actual = extensions[extensions.len() - 1];
+        if DECOY_EXTENSIONS.contains(&decoy) && decoy != actual {
+            issues.push(issue(
+                ScreeningSeverity::Warning,
+                "double_extension",
+                format!("File looks like .{} but is actually .{}", decoy, actual),
+            ));
+        }
+    }
+
+    if let Some(socket) = config.clamav_socket.filter(|s| !s.trim().is_empty()) {
+        match clamav_scan(socket, Path::new(file_path)) {
+            Ok(None) => {}
+            Ok(Some(signature)) => issues.push(issue(
+                ScreeningSeverity::Block,
+                "malware_detected",
+                format!("ClamAV detected {}", signature),
+            )),
+            Err(e) => issues.push(issue(
+                ScreeningSeverity::Block,
+                "scan_unavailable",
+                format!("Virus scan failed: {}", e),
+            )),
+        }
+    }
+
+    let allowed = !issues.iter().any(|i| i.severity == ScreeningSeverity::Block);
+    ScreeningResult {
+        file_path: file_path.to_string(),
+        allowed,
+        issues,
+    }
+}
+
+fn issue(severity: ScreeningSeverity, code: &str, message: String) -> ScreeningIssue {
+    ScreeningIssue {
+        severity,
+        code: code.to_string(),
+        message,
+    }
+}
+
+/// Stream a file to clamd using the INSTREAM command.
+/// Returns the signature name when the file is infected.
+#[cfg(unix)]
+fn clamav_scan(socket: &str, file_path: &Path) -> std::io::Result<Option<String>> {
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let mut stream = UnixStream::connect(socket)?;
+    stream.set_read_timeout(Some(Duration::from_secs(60)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(60)))?;
+
+    stream.write_all(b"zINSTREAM\0")?;
+
+    let mut file = std::fs::File::open(file_path)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        stream.write_all(&(n as u32).to_be_bytes())?;
+        stream.write_all(&buf[..n])?;
+    }
+    stream.write_all(&0u32.to_be_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    parse_clamd_response(&response)
+}
+
+#[cfg(not(unix))]
+fn clamav_scan(_socket: &str, _file_path: &Path) -> std::io::Result<Option<String>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "ClamAV sockets are only supported on Unix",
+    ))
+}
+
+/// Parse `stream: OK` / `stream: <Signature> FOUND`; anything else is an error
+fn parse_clamd_response(response: &str) -> std::io::Result<Option<String>> {
+    let response = response.trim_end_matches('\0').trim();
+    match response.strip_prefix("stream: ") {
+        Some("OK") => Ok(None),
+        Some(rest) if rest.ends_with(" FOUND") => Ok(Some(rest.trim_end_matches(" FOUND").to_string())),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("unexpected clamd response '{}'", response),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DEFAULT_BLOCKED_EXTENSIONS;
+
+    fn config(blocked: &[String]) -> ScreeningConfig<'_> {
+        ScreeningConfig {
+            blocked_extensions: blocked,
+            clamav_socket: None,
+        }
+    }
+
+    fn default_blocked() -> Vec<String> {
+        DEFAULT_BLOCKED_EXTENSIONS.iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn test_allows_logs() {
+        let blocked = default_blocked();
+        let result = screen_file("/tmp/vpn-client.log", &config(&blocked));
+        assert!(result.allowed);
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_blocks_executables() {
+        let blocked = default_blocked();
+        let result = screen_file("/tmp/Setup.EXE", &config(&blocked));
+        assert!(!result.allowed);
+        assert_eq!(result.issues[0].code, "blocked_extension");
+    }
+
+    #[test]
+    fn test_double_extension() {
+        let blocked = default_blocked();
+        let result = screen_file("/tmp/invoice.pdf.exe", &config(&blocked));
+        assert!(!result.allowed);
+        assert!(result.issues.iter().any(|i| i.code == "double_extension"));
+
+        // Archives of logs are fine
+        let result = screen_file("/tmp/logs.tar.gz", &config(&blocked));
+        assert!(result.issues.is_empty());
+    }
+
+    #[test]
+    fn test_report_counts_blocked() {
+        let blocked = default_blocked();
+        let report = screen_files(&["a.txt".to_string(), "b.bat".to_string()], &config(&blocked));
+        assert_eq!(report.blocked_count, 1);
+    }
+
+    #[test]
+    fn test_parse_clamd_response() {
+        assert_eq!(parse_clamd_response("stream: OK\0").unwrap(), None);
+        assert_eq!(
+            parse_clamd_response("stream: Eicar-Test-Signature FOUND\0").unwrap(),
+            Some("Eicar-Test-Signature".to_string())
+        );
+        assert!(parse_clamd_response("INSTREAM size limit exceeded. ERROR\0").is_err());
+        assert!(parse_clamd_response("").is_err());
+    }
+
+    #[test]
+    fn test_scan_failure_blocks() {
+        let blocked = default_blocked();
+        let config = ScreeningConfig {
+            blocked_extensions: &blocked,
+            clamav_socket: Some("/nonexistent/clamd.sock"),
+        };
+        let result = screen_file("/tmp/vpn-client.log", &config);
+        assert!(!result.allowed);
+        assert_eq!(result.issues[0].code, "scan_unavailable");
+    }
+}
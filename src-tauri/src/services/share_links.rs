@@ -0,0 +1,100 @@
+/// Signed, expiring links to a read-only view of an escalation on the local API
+///
+/// Links are signed with a key generated when the app starts, so every link
+/// stops working when the app quits; nothing needs to be stored or revoked.
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use once_cell::sync::Lazy;
+use sha2::Sha256;
+
+pub const DEFAULT_TTL_MINUTES: i64 = 60;
+pub const MAX_TTL_MINUTES: i64 = 24 * 60;
+
+static SIGNING_KEY: Lazy<[u8; 32]> = Lazy::new(rand::random);
+
+/// `http://<bind>/share/<id>?expires=<unix>&sig=<hmac>`; wildcard binds use localhost
+pub fn url(bind: &str, escalation_id: i64, expires: i64) -> String {
+    let (host, port) = bind.rsplit_once(':').unwrap_or((bind, "80"));
+    let host = match host {
+        "0.0.0.0" | "[::]" | "" => "localhost",
+        host => host,
+    };
+    format!(
+        "http://{}:{}/share/{}?expires={}&sig={}",
+        host,
+        port,
+        escalation_id,
+        expires,
+        sign(&*SIGNING_KEY, escalation_id, expires)
+    )
+}
+
+/// Check a share request's path and query; returns the escalation id when valid
+pub fn verify(path: &str, query: &str, now: i64) -> Option<i64> {
+    verify_with_key(&*SIGNING_KEY, path, query, now)
+}
+
+fn verify_with_key(key: &[u8], path: &str, query: &str, now: i64) -> Option<i64> {
+    let escalation_id: i64 = path.strip_prefix("/share/")?.parse().ok()?;
+
+    let mut expires = None;
+    let mut signature = None;
+    for (name, value) in query.split('&').filter_map(|pair| pair.split_once('=')) {
+        match name {
+            "expires" => expires = value.parse::<i64>().ok(),
+            "sig" => signature = URL_SAFE_NO_PAD.decode(value).ok(),
+            _ => {}
+        }
+    }
+    let (expires, signature) = (expires?, signature?);
+    if expires < now {
+        return None;
+    }
+
+    mac(key, escalation_id, expires).verify_slice(&signature).ok()?;
+    Some(escalation_id)
+}
+
+fn sign(key: &[u8], escalation_id: i64, expires: i64) -> String {
+    URL_SAFE_NO_PAD.encode(mac(key, escalation_id, expires).finalize().into_bytes())
+}
+
+fn mac(key: &[u8], escalation_id: i64, expires: i64) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(format!("share:{}:{}", escalation_id, expires).as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test-key";
+    const NOW: i64 = 1_700_000_000;
+
+    fn query(escalation_id: i64, expires: i64) -> String {
+        format!("expires={}&sig={}", expires, sign(KEY, escalation_id, expires))
+    }
+
+    #[test]
+    fn test_url_uses_bind_address() {
+        assert!(url("127.0.0.1:8787", 7, NOW).starts_with("http://127.0.0.1:8787/share/7?expires=1700000000&sig="));
+        assert!(url("0.0.0.0:8787", 7, NOW).starts_with("http://localhost:8787/share/7?"));
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(verify_with_key(KEY, "/share/7", &query(7, NOW + 60), NOW), Some(7));
+
+        // Expired, signed for another escalation, or signed with another key
+        assert_eq!(verify_with_key(KEY, "/share/7", &query(7, NOW - 1), NOW), None);
+        assert_eq!(verify_with_key(KEY, "/share/8", &query(7, NOW + 60), NOW), None);
+        assert_eq!(verify_with_key(b"other", "/share/7", &query(7, NOW + 60), NOW), None);
+
+        // Tampered expiry or missing signature
+        let tampered = query(7, NOW + 60).replace(&(NOW + 60).to_string(), &(NOW + 9999).to_string());
+        assert_eq!(verify_with_key(KEY, "/share/7", &tampered, NOW), None);
+        assert_eq!(verify_with_key(KEY, "/share/7", &format!("expires={}", NOW + 60), NOW), None);
+    }
+}
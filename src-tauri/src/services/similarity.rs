@@ -0,0 +1,148 @@
+/// Ranks past escalations by how closely they match a Jira ticket
+///
+/// Matching is a plain word-overlap (Jaccard) score between the ticket summary
+/// and each escalation's problem summary, or cosine similarity of cached
+/// embeddings when an embedding model is configured; earlier escalations on
+/// the same ticket always rank first.
+use crate::models::{EscalationSummary, SimilarEscalation};
+use crate::services::embeddings;
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_LIMIT: usize = 5;
+
+/// Minimum overlap for an escalation on a different ticket to count as similar
+const MIN_SCORE: f64 = 0.2;
+/// Embedding cosine similarity is much denser than word overlap, so needs a higher bar
+const MIN_EMBEDDING_SCORE: f64 = 0.6;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "not", "with", "after", "when", "from", "this", "that", "user", "users",
+    "cannot", "can't", "does", "doesn't", "issue", "problem",
+];
+
+fn tokens(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .map(|w| w.trim_matches('\'').to_lowercase())
+        .filter(|w| w.len() >= 3 && !STOPWORDS.contains(&w.as_str()))
+        .collect()
+}
+
+/// Jaccard similarity of the significant words in two texts (0.0-1.0)
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a = tokens(a);
+    let b = tokens(b);
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+pub fn rank(
+    ticket_key: &str,
+    ticket_summary: &str,
+    candidates: Vec<EscalationSummary>,
+    limit: usize,
+) -> Vec<SimilarEscalation> {
+    rank_with(ticket_key, candidates, limit, MIN_SCORE, |escalation| {
+        similarity(ticket_summary, &escalation.problem_summary)
+    })
+}
+
+/// Rank by cosine similarity; escalations without a cached vector score 0
+pub fn rank_by_embedding(
+    ticket_key: &str,
+    ticket_vector: &[f32],
+    candidates: Vec<EscalationSummary>,
+    vectors: &HashMap<i64, Vec<f32>>,
+    limit: usize,
+) -> Vec<SimilarEscalation> {
+    rank_with(ticket_key, candidates, limit, MIN_EMBEDDING_SCORE, |escalation| {
+        vectors
+            .get(&escalation.id)
+            .map_or(0.0, |vector| embeddings::cosine(ticket_vector, vector))
+    })
+}
+
+fn rank_with(
+    ticket_key: &str,
+    candidates: Vec<EscalationSummary>,
+    limit: usize,
+    min_score: f64,
+    score_fn: impl Fn(&EscalationSummary) -> f64,
+) -> Vec<SimilarEscalation> {
+    let mut ranked: Vec<SimilarEscalation> = candidates
+        .into_iter()
+        .filter_map(|escalation| {
+            let score = if escalation.ticket_id.eq_ignore_ascii_case(ticket_key) {
+                1.0
+            } else {
+                score_fn(&escalation)
+            };
+
+            (score >= min_score).then_some(SimilarEscalation { escalation, score })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    ranked.truncate(limit);
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EscalationStatus;
+
+    fn summary(id: i64, ticket_id: &str, problem_summary: &str) -> EscalationSummary {
+        EscalationSummary {
+            id,
+            ticket_id: ticket_id.to_string(),
+            problem_summary: problem_summary.to_string(),
+            status: EscalationStatus::Posted,
+            created_at: "2024-01-01 00:00:00".to_string(),
+            incident_id: None,
+        }
+    }
+
+    #[test]
+    fn test_similarity_ignores_stopwords_and_case() {
+        assert_eq!(similarity("VPN disconnects", "the vpn DISCONNECTS"), 1.0);
+        assert_eq!(similarity("", "anything"), 0.0);
+    }
+
+    #[test]
+    fn test_rank_orders_by_score() {
+        let candidates = vec![
+            summary(1, "HELP-1", "Printer jams on tray two"),
+            summary(2, "HELP-2", "VPN client disconnects every hour"),
+            summary(3, "HELP-3", "VPN client disconnects after sleep"),
+            summary(4, "HELP-9", "Unrelated earlier escalation"),
+        ];
+
+        let ranked = rank("HELP-9", "VPN client disconnects after laptop sleep", candidates, DEFAULT_LIMIT);
+        let ids: Vec<i64> = ranked.iter().map(|s| s.escalation.id).collect();
+        assert_eq!(ids, vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn test_rank_by_embedding() {
+        let candidates = vec![
+            summary(1, "HELP-1", "Printer jams"),
+            summary(2, "HELP-2", "VPN drops"),
+            summary(3, "HELP-3", "Not indexed yet"),
+        ];
+        let vectors = HashMap::from([(1, vec![0.0, 1.0]), (2, vec![0.9, 0.1])]);
+
+        let ranked = rank_by_embedding("HELP-9", &[1.0, 0.0], candidates, &vectors, DEFAULT_LIMIT);
+        let ids: Vec<i64> = ranked.iter().map(|s| s.escalation.id).collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[test]
+    fn test_rank_respects_limit() {
+        let candidates = (0..10).map(|i| summary(i, "HELP-1", "Anything")).collect();
+        assert_eq!(rank("HELP-1", "", candidates, 3).len(), 3);
+    }
+}
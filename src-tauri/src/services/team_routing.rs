@@ -0,0 +1,122 @@
+/// Handing one escalation to several L2 teams
+///
+/// Teams are configured in Settings with the component, assignee and people to
+/// @mention for their escalations. An escalation names the teams it is for and
+/// what each is asked to do; at post time every selected team's component is
+/// added, and the ticket goes to the first selected team that has an assignee.
+use crate::error::{AppError, AppResult};
+use crate::models::{L2Team, TeamAsk};
+
+/// What posting does to the ticket for the selected teams
+#[derive(Debug, Default, PartialEq)]
+pub struct Routing {
+    pub components: Vec<String>,
+    pub assignee_account_id: Option<String>,
+}
+
+/// Every selected team must be configured, and selected once
+pub fn validate(selected: &[TeamAsk], teams: &[L2Team]) -> AppResult<()> {
+    for (i, ask) in selected.iter().enumerate() {
+        if find(teams, &ask.team).is_none() {
+            return Err(AppError::Validation(format!(
+                "Unknown L2 team \"{}\". Add it in Settings first.",
+                ask.team
+            )));
+        }
+        if selected[..i].iter().any(|earlier| earlier.team.eq_ignore_ascii_case(&ask.team)) {
+            return Err(AppError::Validation(format!("L2 team \"{}\" is selected twice", ask.team)));
+        }
+    }
+    Ok(())
+}
+
+/// The selected teams with each one's mentions filled in from its settings
+pub fn with_mentions(selected: &[TeamAsk], teams: &[L2Team]) -> Vec<TeamAsk> {
+    selected
+        .iter()
+        .map(|ask| TeamAsk {
+            mentions: find(teams, &ask.team)
+                .map(|team| team.mention_account_ids.clone())
+                .unwrap_or_default(),
+            ..ask.clone()
+        })
+        .collect()
+}
+
+/// Components of all selected teams (each once, in selection order) and the first assignee
+pub fn routing(selected: &[TeamAsk], teams: &[L2Team]) -> Routing {
+    let mut routing = Routing::default();
+    for team in selected.iter().filter_map(|ask| find(teams, &ask.team)) {
+        if let Some(component) = team.component.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            if !routing.components.iter().any(|c| c == component) {
+                routing.components.push(component.to_string());
+            }
+        }
+        if routing.assignee_account_id.is_none() {
+            routing.assignee_account_id = team.assignee_account_id.clone().filter(|a| !a.is_empty());
+        }
+    }
+    routing
+}
+
+fn find<'a>(teams: &'a [L2Team], name: &str) -> Option<&'a L2Team> {
+    teams.iter().find(|team| team.name.trim().eq_ignore_ascii_case(name.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(name: &str, component: Option<&str>, assignee: Option<&str>, mentions: &[&str]) -> L2Team {
+        L2Team {
+            name: name.to_string(),
+            component: component.map(str::to_string),
+            assignee_account_id: assignee.map(str::to_string),
+            mention_account_ids: mentions.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    fn ask(team: &str) -> TeamAsk {
+        TeamAsk {
+            team: team.to_string(),
+            asks: format!("{} asks", team),
+            mentions: Vec::new(),
+        }
+    }
+
+    fn teams() -> Vec<L2Team> {
+        vec![
+            team("Network", Some("Networking"), None, &["acc-net"]),
+            team("Identity", Some("IAM"), Some("acc-iam-queue"), &["acc-iam-1", "acc-iam-2"]),
+            team("Endpoint", Some("Networking"), Some("acc-endpoint"), &[]),
+        ]
+    }
+
+    #[test]
+    fn test_routing_across_teams() {
+        let selected = vec![ask("network"), ask("Identity"), ask("Endpoint")];
+        assert_eq!(
+            routing(&selected, &teams()),
+            Routing {
+                components: vec!["Networking".to_string(), "IAM".to_string()],
+                assignee_account_id: Some("acc-iam-queue".to_string()),
+            }
+        );
+        assert_eq!(routing(&[], &teams()), Routing::default());
+    }
+
+    #[test]
+    fn test_with_mentions() {
+        let filled = with_mentions(&[ask("Identity"), ask("Endpoint")], &teams());
+        assert_eq!(filled[0].mentions, vec!["acc-iam-1", "acc-iam-2"]);
+        assert!(filled[1].mentions.is_empty());
+        assert_eq!(filled[0].asks, "Identity asks");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&[ask("Network"), ask("Identity")], &teams()).is_ok());
+        assert!(validate(&[ask("Facilities")], &teams()).is_err());
+        assert!(validate(&[ask("Network"), ask("NETWORK")], &teams()).is_err());
+    }
+}
@@ -1,17 +1,35 @@
-use crate::error::AppResult;
-use crate::models::{EscalationInput, Template};
+use crate::error::{AppError, AppResult};
+use crate::models::{
+    AttachmentInput, ChecklistItem, EnvironmentInfo, EnvironmentItem, EscalationInput, NextStepBlock, PreviousEscalation, TeamAsk, Template,
+    TemplateRenderResult, TicketSnapshot,
+};
+use crate::services::language;
 use handlebars::Handlebars;
-use serde_json::json;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
 
-const TEMPLATE: &str = r#"## Escalation: {{ticket_id}}
+/// Version of the built-in layout new escalations are written with
+///
+/// Escalations keep the version they were posted with, so a layout change never
+/// alters how an old escalation re-renders on retry. Change the layout by adding
+/// a new version that revises the previous one, leaving the earlier ones (and their
+/// fixtures) as they are.
+pub const RENDER_VERSION: u32 = 5;
+
+const LAYOUT_V1: &str = r#"## Escalation: {{ticket_id}}
 {{#if template_name}}**Template:** {{template_name}}{{/if}}
 
 ### Problem Summary
 {{problem_summary}}
+{{#if summary_translation}}
+
+**English translation{{#if summary_language_name}} (from {{summary_language_name}}){{/if}}, machine-translated:**
+{{summary_translation}}
+{{/if}}
 
 ### Troubleshooting Steps
 {{#each checklist}}
-- [{{#if checked}}x{{else}} {{/if}}] {{text}}
+- [{{#if checked}}x{{else}} {{/if}}] {{#if link}}[{{text}}]({{{link}}}){{else}}{{text}}{{/if}}
 {{/each}}
 
 ### Current Status
@@ -20,6 +38,30 @@ const TEMPLATE: &str = r#"## Escalation: {{ticket_id}}
 ### Next Steps
 {{next_steps}}
 
+{{#if attachments}}
+### Attachments
+{{#each attachments}}
+- `{{file_name}}`{{#if artifact}} ({{artifact}}){{/if}}{{#if description}} — {{description}}{{/if}}
+{{/each}}
+{{/if}}
+
+{{#if ticket_snapshot}}
+### Ticket state at escalation time
+- **Summary:** {{ticket_snapshot.summary}}
+- **Status:** {{ticket_snapshot.status}}
+- **Assignee:** {{#if ticket_snapshot.assignee}}{{ticket_snapshot.assignee}}{{else}}Unassigned{{/if}}
+{{#if ticket_snapshot.captured_at}}- **Captured:** {{ticket_snapshot.captured_at}}
+{{/if}}
+{{/if}}
+
+{{#if previous_escalation}}
+### Previous escalation
+This issue was escalated before as #{{previous_escalation.id}}{{#if previous_escalation.posted_at}} (posted {{previous_escalation.posted_at}}){{/if}}.
+- **Problem:** {{previous_escalation.problem_summary}}
+- **Status then:** {{previous_escalation.current_status}}
+- **Next steps then:** {{previous_escalation.next_steps}}
+{{/if}}
+
 {{#if llm_summary}}
 ### AI Summary
 {{llm_summary}}
@@ -30,11 +72,268 @@ const TEMPLATE: &str = r#"## Escalation: {{ticket_id}}
 *Generated by Ticket Handoff Assistant*
 "#;
 
-pub fn render_markdown(template: Option<&Template>, input: &EscalationInput) -> AppResult<String> {
+/// Adds a per-team "Asks by team" section for escalations handed to several L2 teams
+static LAYOUT_V2: Lazy<String> = Lazy::new(|| {
+    revise(
+        LAYOUT_V1,
+        "{{next_steps}}\n",
+        r#"{{next_steps}}
+{{#if l2_teams}}
+
+### Asks by team
+{{#each l2_teams}}
+
+#### {{team}}
+{{#each mentions}}[~accountid:{{this}}] {{/each}}{{asks}}
+{{/each}}
+{{/if}}
+"#,
+    )
+});
+
+/// Adds an "Environment" section with the captured machine details
+static LAYOUT_V3: Lazy<String> = Lazy::new(|| {
+    revise(
+        &LAYOUT_V2,
+        "{{#if attachments}}\n",
+        r#"{{#if environment_info}}
+### Environment
+{{#each environment_info.items}}
+- **{{label}}:** {{#if value}}{{value}}{{else}}unavailable{{/if}}
+{{/each}}
+{{/if}}
+
+{{#if attachments}}
+"#,
+    )
+});
+
+/// Adds the linked incident under the heading
+static LAYOUT_V4: Lazy<String> = Lazy::new(|| {
+    revise(
+        &LAYOUT_V3,
+        "{{#if template_name}}**Template:** {{template_name}}{{/if}}\n",
+        r#"{{#if template_name}}**Template:** {{template_name}}{{/if}}
+{{#if incident_id}}**Incident:** {{incident_id}}
+{{/if}}
+"#,
+    )
+});
+
+/// Names the previous escalation by its Jira ticket rather than the local escalation id
+static LAYOUT_V5: Lazy<String> = Lazy::new(|| {
+    revise(
+        &LAYOUT_V4,
+        "escalated before as #{{previous_escalation.id}}",
+        "escalated before on {{previous_escalation.ticket_id}}",
+    )
+});
+
+/// A layout with one passage of the previous version replaced; the passage must occur exactly once
+fn revise(previous: &str, passage: &str, replacement: &str) -> String {
+    assert_eq!(previous.matches(passage).count(), 1, "layout passage {:?} is not unique", passage);
+    previous.replacen(passage, replacement, 1)
+}
+
+/// Next-steps blocks are available as `{{> name}}` partials; references in the next steps
+/// themselves were already expanded when the draft was saved
+pub fn render_markdown(
+    template: Option<&Template>,
+    input: &EscalationInput,
+    blocks: &[NextStepBlock],
+) -> AppResult<String> {
+    let body = match template.and_then(|t| t.body.as_deref()) {
+        Some(body) => body,
+        None => layout(input.render_version.unwrap_or(RENDER_VERSION))?,
+    };
+
     let mut handlebars = Handlebars::new();
-    handlebars.register_template_string("escalation", TEMPLATE)?;
+    register_blocks(&mut handlebars, blocks)?;
+    handlebars.register_template_string("escalation", body)?;
+
+    let rendered = handlebars.render("escalation", &build_data(template, input))?;
+    Ok(rendered)
+}
+
+/// Render a template for authoring feedback, collecting errors instead of failing
+///
+/// Rendering runs in strict mode first so references to unknown fields are
+/// reported; if only strict mode fails the lenient output is still returned.
+pub fn test_render(template: &Template, input: &EscalationInput, blocks: &[NextStepBlock]) -> TemplateRenderResult {
+    let mut errors = Vec::new();
+    let body = match template.body.as_deref() {
+        Some(body) => body,
+        None => match layout(input.render_version.unwrap_or(RENDER_VERSION)) {
+            Ok(layout) => layout,
+            Err(e) => {
+                errors.push(e.to_string());
+                return TemplateRenderResult { markdown: None, errors };
+            }
+        },
+    };
+    let data = build_data(Some(template), input);
+
+    let mut handlebars = Handlebars::new();
+    if let Err(e) = register_blocks(&mut handlebars, blocks) {
+        errors.push(e.to_string());
+    }
+    if let Err(e) = handlebars.register_template_string("escalation", body) {
+        errors.push(e.to_string());
+        return TemplateRenderResult { markdown: None, errors };
+    }
 
-    let data = json!({
+    handlebars.set_strict_mode(true);
+    if let Err(e) = handlebars.render("escalation", &data) {
+        errors.push(e.to_string());
+    }
+
+    handlebars.set_strict_mode(false);
+    let markdown = match handlebars.render("escalation", &data) {
+        Ok(markdown) => Some(markdown),
+        Err(e) => {
+            errors.push(e.to_string());
+            None
+        }
+    };
+
+    TemplateRenderResult { markdown, errors }
+}
+
+/// Synthetic escalation exercising every section of a template
+pub fn sample_input(template: &Template) -> EscalationInput {
+    let checklist = template
+        .checklist_items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| ChecklistItem {
+            checked: i % 2 == 0,
+            ..item.clone()
+        })
+        .collect();
+
+    EscalationInput {
+        ticket_id: "SAMPLE-123".to_string(),
+        template_id: Some(template.id),
+        problem_summary: "User reports the issue started this morning after a restart.".to_string(),
+        checklist,
+        current_status: "Issue persists after the completed steps.".to_string(),
+        next_steps: "Please review server-side logs for the affected user.".to_string(),
+        llm_summary: Some("✓ Completed steps:\n- Sample step".to_string()),
+        llm_confidence: Some("Medium".to_string()),
+        attachments: vec![AttachmentInput {
+            file_path: "/tmp/sample-log.txt".to_string(),
+            description: Some("client log during failure window".to_string()),
+            artifact: template.expected_artifacts.first().cloned(),
+            annotations: None,
+        }],
+        private_notes: Some("Caller sounded frustrated; possible churn risk.".to_string()),
+        ticket_snapshot: Some(TicketSnapshot {
+            summary: "Cannot connect to VPN".to_string(),
+            status: "In Progress".to_string(),
+            assignee: Some("Service Desk Agent".to_string()),
+            captured_at: Some("2024-01-15T09:30:00Z".to_string()),
+        }),
+        summary_language: None,
+        summary_translation: None,
+        parent_escalation_id: Some(41),
+        previous_escalation: Some(PreviousEscalation {
+            id: 41,
+            ticket_id: "SAMPLE-123".to_string(),
+            problem_summary: "User could not sign in after a password reset.".to_string(),
+            current_status: "Sign-in restored after clearing cached credentials.".to_string(),
+            next_steps: "Confirm the reset flow no longer leaves stale tokens.".to_string(),
+            posted_at: Some("2024-01-08T14:00:00Z".to_string()),
+        }),
+        render_version: None,
+        l2_teams: vec![
+            TeamAsk {
+                team: "Network".to_string(),
+                asks: "Check the VPN concentrator for dropped sessions.".to_string(),
+                mentions: vec!["5b10a2844c20165700ede21g".to_string()],
+            },
+            TeamAsk {
+                team: "Identity".to_string(),
+                asks: "Confirm MFA enrollment is intact for the user.".to_string(),
+                mentions: Vec::new(),
+            },
+        ],
+        environment_info: Some(EnvironmentInfo {
+            captured_at: "2024-01-15T09:31:00Z".to_string(),
+            items: vec![
+                EnvironmentItem {
+                    label: "OS".to_string(),
+                    value: Some("macOS 14.2.1".to_string()),
+                    error: None,
+                },
+                EnvironmentItem {
+                    label: "VPN client".to_string(),
+                    value: None,
+                    error: Some("vpn timed out after 5s".to_string()),
+                },
+            ],
+        }),
+        incident_id: Some("INC-2041".to_string()),
+    }
+}
+
+/// Reject checklist links that aren't plain http(s) URLs
+///
+/// Links are written into a Markdown link target, so `javascript:` and other
+/// schemes, and characters that would end the target early, are refused.
+pub fn validate_checklist_links(checklist: &[ChecklistItem]) -> AppResult<()> {
+    for item in checklist {
+        if let Some(link) = item.link.as_deref().filter(|link| !is_web_link(link)) {
+            return Err(AppError::Validation(format!(
+                "Link for \"{}\" must be an http(s) URL without spaces or brackets: {}",
+                item.text, link
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// An absolute http(s) URL that is safe to place in a Markdown link target
+///
+/// The layouts write links raw with `{{{link}}}` so URLs stay usable in plain-text output;
+/// this check is what keeps them safe there.
+pub fn is_web_link(link: &str) -> bool {
+    let lower = link.to_ascii_lowercase();
+    let has_scheme = ["http://", "https://"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme) && lower.len() > scheme.len());
+    has_scheme && !link.chars().any(|c| c.is_whitespace() || c.is_control() || "<>\"'()".contains(c))
+}
+
+/// The built-in layout for a render version
+pub fn layout(version: u32) -> AppResult<&'static str> {
+    match version {
+        1 => Ok(LAYOUT_V1),
+        2 => Ok(&LAYOUT_V2),
+        3 => Ok(&LAYOUT_V3),
+        4 => Ok(&LAYOUT_V4),
+        5 => Ok(&LAYOUT_V5),
+        _ => Err(AppError::Validation(format!(
+            "Unknown render version {} (latest is {})",
+            version, RENDER_VERSION
+        ))),
+    }
+}
+
+fn register_blocks(handlebars: &mut Handlebars, blocks: &[NextStepBlock]) -> AppResult<()> {
+    for block in blocks {
+        handlebars.register_partial(&block.name, &block.body)?;
+    }
+    Ok(())
+}
+
+fn build_data(template: Option<&Template>, input: &EscalationInput) -> Value {
+    let attachments: Vec<_> = input
+        .attachments
+        .iter()
+        .map(|a| json!({ "file_name": a.file_name(), "description": a.description, "artifact": a.artifact }))
+        .collect();
+
+    json!({
         "ticket_id": input.ticket_id,
         "template_name": template.map(|t| &t.name),
         "problem_summary": input.problem_summary,
@@ -43,16 +342,34 @@ pub fn render_markdown(template: Option<&Template>, input: &EscalationInput) ->
         "next_steps": input.next_steps,
         "llm_summary": input.llm_summary,
         "llm_confidence": input.llm_confidence,
-    });
-
-    let rendered = handlebars.render("escalation", &data)?;
-    Ok(rendered)
+        "attachments": attachments,
+        "ticket_snapshot": input.ticket_snapshot,
+        "summary_translation": input.summary_translation,
+        "previous_escalation": input.previous_escalation,
+        "summary_language_name": input.summary_language.as_deref().and_then(language::name),
+        "l2_teams": input.l2_teams,
+        "environment_info": input.environment_info,
+        "incident_id": input.incident_id.as_deref().map(str::trim).filter(|id| !id.is_empty()),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ChecklistItem;
+
+    fn custom_template(body: &str) -> Template {
+        Template {
+            id: 1,
+            name: "Custom".to_string(),
+            description: String::new(),
+            category: "Test".to_string(),
+            checklist_items: vec![ChecklistItem { text: "Step".to_string(), checked: false, link: None }],
+            l2_team: None,
+            body: Some(body.to_string()),
+            expected_artifacts: vec![],
+            pinned: false,
+        }
+    }
 
     #[test]
     fn test_render_markdown() {
@@ -61,16 +378,27 @@ mod tests {
             template_id: None,
             problem_summary: "User cannot access VPN".to_string(),
             checklist: vec![
-                ChecklistItem { text: "Restarted VPN client".to_string(), checked: true },
-                ChecklistItem { text: "Verified credentials".to_string(), checked: false },
+                ChecklistItem { text: "Restarted VPN client".to_string(), checked: true, link: None },
+                ChecklistItem { text: "Verified credentials".to_string(), checked: false, link: None },
             ],
             current_status: "VPN still not connecting".to_string(),
             next_steps: "Check firewall settings".to_string(),
             llm_summary: None,
             llm_confidence: None,
+            attachments: vec![],
+            private_notes: None,
+            ticket_snapshot: None,
+            summary_language: None,
+            summary_translation: None,
+            parent_escalation_id: None,
+            previous_escalation: None,
+            render_version: None,
+            l2_teams: Vec::new(),
+            environment_info: None,
+            incident_id: None,
         };
 
-        let result = render_markdown(None, &input);
+        let result = render_markdown(None, &input, &[]);
         assert!(result.is_ok());
 
         let markdown = result.unwrap();
@@ -79,4 +407,236 @@ mod tests {
         assert!(markdown.contains("- [x] Restarted VPN client"));
         assert!(markdown.contains("- [ ] Verified credentials"));
     }
+
+    #[test]
+    fn test_render_checklist_links() {
+        let input = EscalationInput {
+            ticket_id: "TEST-124".to_string(),
+            template_id: None,
+            problem_summary: "VPN drops".to_string(),
+            checklist: vec![ChecklistItem {
+                text: "Reset network stack".to_string(),
+                checked: true,
+                link: Some("https://kb.example.com/article?id=vpn-reset".to_string()),
+            }],
+            current_status: String::new(),
+            next_steps: String::new(),
+            llm_summary: None,
+            llm_confidence: None,
+            attachments: vec![],
+            private_notes: None,
+            ticket_snapshot: None,
+            summary_language: None,
+            summary_translation: None,
+            parent_escalation_id: None,
+            previous_escalation: None,
+            render_version: None,
+            l2_teams: Vec::new(),
+            environment_info: None,
+            incident_id: None,
+        };
+
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(markdown.contains("- [x] [Reset network stack](https://kb.example.com/article?id=vpn-reset)"));
+    }
+
+    #[test]
+    fn test_validate_checklist_links() {
+        let item = |link: &str| ChecklistItem { text: "Step".to_string(), checked: false, link: Some(link.to_string()) };
+
+        assert!(validate_checklist_links(&[item("https://kb.example.com/a?id=1")]).is_ok());
+        assert!(validate_checklist_links(&[ChecklistItem { link: None, ..item("") }]).is_ok());
+        for bad in ["javascript:alert(1)", "JAVASCRIPT:alert(1)", "data:text/html,<b>x</b>", "https://", "https://x.com/<script>", "https://x.com/a) [b](c"] {
+            assert!(validate_checklist_links(&[item(bad)]).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_render_attachments_section() {
+        let input = EscalationInput {
+            ticket_id: "TEST-125".to_string(),
+            template_id: None,
+            problem_summary: "App crashes on launch".to_string(),
+            checklist: vec![],
+            current_status: String::new(),
+            next_steps: String::new(),
+            llm_summary: None,
+            llm_confidence: None,
+            attachments: vec![
+                AttachmentInput {
+                    file_path: "/tmp/logs/log_final_v2.txt".to_string(),
+                    description: Some("client log during failure window".to_string()),
+                    artifact: None,
+                    annotations: None,
+                },
+                AttachmentInput {
+                    file_path: "/tmp/screenshot.png".to_string(),
+                    description: None,
+                    artifact: None,
+                    annotations: None,
+                },
+            ],
+            private_notes: None,
+            ticket_snapshot: None,
+            summary_language: None,
+            summary_translation: None,
+            parent_escalation_id: None,
+            previous_escalation: None,
+            render_version: None,
+            l2_teams: Vec::new(),
+            environment_info: None,
+            incident_id: None,
+        };
+
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(markdown.contains("### Attachments"));
+        assert!(markdown.contains("- `log_final_v2.txt` — client log during failure window"));
+        assert!(markdown.contains("- `screenshot.png`\n"));
+    }
+
+    #[test]
+    fn test_custom_body_overrides_layout() {
+        let template = custom_template("Ticket {{ticket_id}}: {{problem_summary}}");
+        let input = sample_input(&template);
+
+        let markdown = render_markdown(Some(&template), &input, &[]).unwrap();
+        assert_eq!(markdown, "Ticket SAMPLE-123: User reports the issue started this morning after a restart.");
+    }
+
+    #[test]
+    fn test_render_ticket_snapshot_section() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(markdown.contains("### Ticket state at escalation time"));
+        assert!(markdown.contains("- **Status:** In Progress"));
+        assert!(markdown.contains("- **Assignee:** Service Desk Agent"));
+
+        input.ticket_snapshot = None;
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(!markdown.contains("Ticket state at escalation time"));
+    }
+
+    #[test]
+    fn test_render_previous_escalation_section() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(markdown.contains("### Previous escalation"));
+        assert!(markdown.contains("escalated before on SAMPLE-123 (posted 2024-01-08T14:00:00Z)"));
+        assert!(markdown.contains("- **Next steps then:** Confirm the reset flow"));
+
+        input.previous_escalation = None;
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(!markdown.contains("Previous escalation"));
+    }
+
+    #[test]
+    fn test_render_with_next_step_blocks() {
+        let block = NextStepBlock {
+            id: 1,
+            name: "vpn-logs".to_string(),
+            body: "Collect VPN gateway logs".to_string(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        };
+        let template = custom_template("{{next_steps}} / {{> vpn-logs}}");
+        let mut input = sample_input(&template);
+        input.next_steps = "Escalate to network".to_string();
+
+        let markdown = render_markdown(Some(&template), &input, &[block]).unwrap();
+        assert_eq!(markdown, "Escalate to network / Collect VPN gateway logs");
+    }
+
+    /// Each layout version's output is frozen; a diff here means historical escalations would re-render differently
+    #[test]
+    fn test_golden_output_v1() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(1);
+        assert_eq!(render_markdown(None, &input, &[]).unwrap(), include_str!("fixtures/render_v1.md"));
+    }
+
+    #[test]
+    fn test_golden_output_v2() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(2);
+        assert_eq!(render_markdown(None, &input, &[]).unwrap(), include_str!("fixtures/render_v2.md"));
+    }
+
+    #[test]
+    fn test_golden_output_v3() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(3);
+        assert_eq!(render_markdown(None, &input, &[]).unwrap(), include_str!("fixtures/render_v3.md"));
+    }
+
+    #[test]
+    fn test_golden_output_v4() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(4);
+        assert_eq!(render_markdown(None, &input, &[]).unwrap(), include_str!("fixtures/render_v4.md"));
+    }
+
+    #[test]
+    fn test_golden_output_v5() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(5);
+        assert_eq!(render_markdown(None, &input, &[]).unwrap(), include_str!("fixtures/render_v5.md"));
+    }
+
+    #[test]
+    fn test_unknown_render_version() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.render_version = Some(RENDER_VERSION + 1);
+        assert!(render_markdown(None, &input, &[]).is_err());
+
+        // Custom bodies own their layout, so the version doesn't apply
+        assert!(render_markdown(Some(&custom_template("{{ticket_id}}")), &input, &[]).is_ok());
+    }
+
+    #[test]
+    fn test_render_translation_alongside_original() {
+        let template = custom_template("");
+        let mut input = sample_input(&template);
+        input.problem_summary = "Impossible de se connecter au VPN".to_string();
+        input.summary_language = Some("fr".to_string());
+        input.summary_translation = Some("The user cannot connect to the VPN".to_string());
+
+        let markdown = render_markdown(None, &input, &[]).unwrap();
+        assert!(markdown.contains("Impossible de se connecter au VPN\n\n**English translation (from French), machine-translated:**\nThe user cannot connect to the VPN"));
+    }
+
+    #[test]
+    fn test_private_notes_never_rendered() {
+        let template = custom_template("{{private_notes}}");
+        let input = sample_input(&template);
+
+        let result = test_render(&template, &input, &[]);
+        assert_eq!(result.markdown.as_deref(), Some(""));
+        assert!(!render_markdown(None, &input, &[]).unwrap().contains("churn"));
+    }
+
+    #[test]
+    fn test_render_reports_syntax_errors() {
+        let template = custom_template("{{#if ticket_id}}unclosed");
+        let result = test_render(&template, &sample_input(&template), &[]);
+
+        assert!(result.markdown.is_none());
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_render_reports_unknown_fields() {
+        let template = custom_template("{{ticket_id}} {{no_such_field}}");
+        let result = test_render(&template, &sample_input(&template), &[]);
+
+        assert_eq!(result.markdown.as_deref(), Some("SAMPLE-123 "));
+        assert_eq!(result.errors.len(), 1);
+    }
 }
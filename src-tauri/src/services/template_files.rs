@@ -0,0 +1,222 @@
+/// Template definitions stored as JSON files, in the schema of `assets/templates`
+///
+/// Used both to seed the built-in templates and to bulk-import a folder of
+/// team templates.
+use crate::error::{AppError, AppResult};
+use crate::models::{ChecklistItem, TemplateImportResult};
+use crate::services::template_engine;
+use handlebars::Handlebars;
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateFile {
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub checklist_items: Vec<ChecklistItem>,
+    pub l2_team: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub expected_artifacts: Vec<String>,
+}
+
+/// Parse and validate one template file
+pub fn parse(json: &str) -> AppResult<TemplateFile> {
+    let template: TemplateFile = serde_json::from_str(json)
+        .map_err(|e| AppError::Validation(format!("Failed to parse template: {}", e)))?;
+
+    if template.name.trim().is_empty() {
+        return Err(AppError::Validation("Template name is empty".to_string()));
+    }
+    if template.category.trim().is_empty() {
+        return Err(AppError::Validation("Template category is empty".to_string()));
+    }
+    if let Some(i) = template.checklist_items.iter().position(|item| item.text.trim().is_empty()) {
+        return Err(AppError::Validation(format!("Checklist item {} has no text", i + 1)));
+    }
+    template_engine::validate_checklist_links(&template.checklist_items)?;
+    if let Some(body) = &template.body {
+        Handlebars::new().register_template_string("escalation", body)?;
+    }
+
+    Ok(template)
+}
+
+pub fn insert(conn: &Connection, template: &TemplateFile) -> AppResult<i64> {
+    let checklist_json = serde_json::to_string(&template.checklist_items)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize checklist: {}", e)))?;
+
+    let artifacts_json = serde_json::to_string(&template.expected_artifacts)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize expected artifacts: {}", e)))?;
+
+    conn.execute(
+        "INSERT INTO templates (name, description, category, checklist_items, l2_team, body, expected_artifacts)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        params![
+            template.name,
+            template.description,
+            template.category,
+            checklist_json,
+            template.l2_team,
+            template.body,
+            artifacts_json,
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Validate every `*.json` file in `dir`, then import the valid ones
+///
+/// Names must be unique among existing templates and within the folder, so
+/// re-running an import after fixing a broken file skips what already landed.
+pub fn import_directory(conn: &mut Connection, dir: &Path) -> AppResult<Vec<TemplateImportResult>> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let files = paths
+        .into_iter()
+        .map(|path| {
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (file_name, std::fs::read_to_string(&path).map_err(AppError::from))
+        })
+        .collect();
+    import_files(conn, files)
+}
+
+/// Validate each named template JSON (or the error reading it), then import the valid ones together
+pub fn import_files(conn: &mut Connection, files: Vec<(String, AppResult<String>)>) -> AppResult<Vec<TemplateImportResult>> {
+    let mut names: HashSet<String> = {
+        let mut stmt = conn.prepare("SELECT name FROM templates")?;
+        let names = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        names.into_iter().map(|name| name.to_lowercase()).collect()
+    };
+
+    let mut results = Vec::new();
+    let mut valid = Vec::new();
+    for (file_name, json) in files {
+        let parsed = json
+            .and_then(|json| parse(&json))
+            .and_then(|template| {
+                if names.insert(template.name.to_lowercase()) {
+                    Ok(template)
+                } else {
+                    Err(AppError::Validation(format!("A template named \"{}\" already exists", template.name)))
+                }
+            });
+
+        match parsed {
+            Ok(template) => {
+                results.push(TemplateImportResult {
+                    file_name,
+                    template_name: Some(template.name.clone()),
+                    template_id: None,
+                    error: None,
+                });
+                valid.push((results.len() - 1, template));
+            }
+            Err(e) => results.push(TemplateImportResult {
+                file_name,
+                template_name: None,
+                template_id: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    let tx = conn.transaction()?;
+    for (index, template) in &valid {
+        results[*index].template_id = Some(insert(&tx, template)?);
+    }
+    tx.commit()?;
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+    use std::fs;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE templates (
+                id INTEGER PRIMARY KEY, name TEXT, description TEXT, category TEXT,
+                checklist_items TEXT, l2_team TEXT, body TEXT, expected_artifacts TEXT
+            );
+            INSERT INTO templates (name, category, checklist_items) VALUES ('Network/VPN', 'Network', '[]');",
+        )
+        .unwrap();
+        conn
+    }
+
+    fn template_json(name: &str) -> String {
+        serde_json::json!({
+            "name": name,
+            "description": "",
+            "category": "Email",
+            "checklist_items": [{ "text": "Checked mailbox quota", "checked": false }],
+            "l2_team": null,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_builtin_templates_are_valid() {
+        for json in [
+            include_str!("../../../assets/templates/network-vpn.json"),
+            include_str!("../../../assets/templates/app-crash.json"),
+            include_str!("../../../assets/templates/access-permissions.json"),
+        ] {
+            parse(json).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        assert!(parse("{").is_err());
+        assert!(parse(&template_json(" ")).is_err());
+
+        let mut value: serde_json::Value = serde_json::from_str(&template_json("Email")).unwrap();
+        value["body"] = "{{#if ticket_id}}unclosed".into();
+        assert!(parse(&value.to_string()).is_err());
+    }
+
+    #[test]
+    fn test_import_directory_reports_each_file() {
+        let dir = temp_dir("import");
+        fs::write(dir.join("a-email.json"), template_json("Email delivery")).unwrap();
+        fs::write(dir.join("b-duplicate.json"), template_json("email delivery")).unwrap();
+        fs::write(dir.join("c-existing.json"), template_json("Network/VPN")).unwrap();
+        fs::write(dir.join("d-broken.json"), "{ not json").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let mut conn = setup();
+        let results = import_directory(&mut conn, &dir).unwrap();
+
+        let files: Vec<&str> = results.iter().map(|r| r.file_name.as_str()).collect();
+        assert_eq!(files, vec!["a-email.json", "b-duplicate.json", "c-existing.json", "d-broken.json"]);
+        assert!(results[0].template_id.is_some());
+        assert!(results[1..].iter().all(|r| r.template_id.is_none() && r.error.is_some()));
+
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM templates", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 2);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
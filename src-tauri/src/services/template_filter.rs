@@ -0,0 +1,144 @@
+/// Narrow the template picker to categories that fit a ticket's components and labels
+use crate::models::{JiraTicket, Template};
+use std::collections::{HashMap, HashSet};
+
+/// Templates whose category is mapped from the ticket's components or labels
+///
+/// Matching is case-insensitive, and a component or label named like a category
+/// selects it without a mapping entry. When nothing matches, every template is
+/// returned so the agent is never left without a choice.
+pub fn relevant(templates: Vec<Template>, ticket: &JiraTicket, category_map: &HashMap<String, Vec<String>>) -> Vec<Template> {
+    let map: HashMap<String, &Vec<String>> = category_map
+        .iter()
+        .map(|(name, categories)| (name.to_lowercase(), categories))
+        .collect();
+
+    let mut categories = HashSet::new();
+    for name in ticket.components.iter().chain(&ticket.labels) {
+        let name = name.to_lowercase();
+        if let Some(mapped) = map.get(&name) {
+            categories.extend(mapped.iter().map(|c| c.to_lowercase()));
+        }
+        categories.insert(name);
+    }
+
+    let matching: Vec<Template> = templates
+        .iter()
+        .filter(|t| categories.contains(&t.category.to_lowercase()))
+        .cloned()
+        .collect();
+
+    if matching.is_empty() {
+        templates
+    } else {
+        matching
+    }
+}
+
+/// Move the default template, then pinned ones in preference order, ahead of the rest
+///
+/// Listed templates are flagged `pinned`; ids that no longer exist are ignored.
+pub fn pin_first(templates: Vec<Template>, default_id: Option<i64>, pinned_ids: &[i64]) -> Vec<Template> {
+    let order: Vec<i64> = default_id.into_iter().chain(pinned_ids.iter().copied()).collect();
+    let rank = |id: i64| order.iter().position(|pinned| *pinned == id);
+
+    let mut templates: Vec<Template> = templates
+        .into_iter()
+        .map(|template| Template {
+            pinned: rank(template.id).is_some(),
+            ..template
+        })
+        .collect();
+
+    // Stable, so unpinned templates keep their category/name order
+    templates.sort_by_key(|template| rank(template.id).unwrap_or(usize::MAX));
+    templates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, category: &str) -> Template {
+        Template {
+            id: 0,
+            name: name.to_string(),
+            description: String::new(),
+            category: category.to_string(),
+            checklist_items: vec![],
+            l2_team: None,
+            body: None,
+            expected_artifacts: vec![],
+            pinned: false,
+        }
+    }
+
+    fn ticket(components: &[&str], labels: &[&str]) -> JiraTicket {
+        JiraTicket {
+            key: "IT-1".to_string(),
+            summary: String::new(),
+            description: None,
+            status: "Open".to_string(),
+            status_category: None,
+            issue_type: None,
+            reporter: None,
+            assignee: None,
+            comments: vec![],
+            linked_issues: vec![],
+            subtasks: vec![],
+            components: components.iter().map(|s| s.to_string()).collect(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            partial: false,
+            missing_sections: vec![],
+        }
+    }
+
+    fn names(templates: Vec<Template>) -> Vec<String> {
+        templates.into_iter().map(|t| t.name).collect()
+    }
+
+    fn templates() -> Vec<Template> {
+        vec![
+            template("VPN drops", "Network"),
+            template("App crash", "Application"),
+            template("Access request", "Access"),
+        ]
+    }
+
+    #[test]
+    fn test_mapped_components_and_labels() {
+        let map = HashMap::from([
+            ("VPN".to_string(), vec!["network".to_string()]),
+            ("sso".to_string(), vec!["Access".to_string()]),
+        ]);
+
+        let picked = relevant(templates(), &ticket(&["vpn"], &["SSO"]), &map);
+        assert_eq!(names(picked), vec!["VPN drops", "Access request"]);
+    }
+
+    #[test]
+    fn test_name_matching_category_needs_no_mapping() {
+        let picked = relevant(templates(), &ticket(&[], &["application"]), &HashMap::new());
+        assert_eq!(names(picked), vec!["App crash"]);
+    }
+
+    #[test]
+    fn test_pin_first() {
+        let templates: Vec<Template> = templates()
+            .into_iter()
+            .enumerate()
+            .map(|(i, t)| Template { id: i as i64 + 1, ..t })
+            .collect();
+
+        let listed = pin_first(templates, Some(3), &[2, 99, 3]);
+        let pinned: Vec<bool> = listed.iter().map(|t| t.pinned).collect();
+        assert_eq!(names(listed), vec!["Access request", "App crash", "VPN drops"]);
+        assert_eq!(pinned, vec![true, true, false]);
+    }
+
+    #[test]
+    fn test_no_match_keeps_every_template() {
+        let picked = relevant(templates(), &ticket(&["Printers"], &[]), &HashMap::new());
+        assert_eq!(picked.len(), 3);
+    }
+}
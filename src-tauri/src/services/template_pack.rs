@@ -0,0 +1,239 @@
+/// Signed template packs (`.thpack`) for distributing templates to agents
+///
+/// A pack is a zip of template JSON files (the `template_files` schema) and a
+/// manifest with each file's SHA-256, signed with HMAC-SHA256 under a key
+/// shared between whoever builds packs and the agents importing them. Import
+/// checks the signature and every checksum before anything is read, so a pack
+/// edited after export is rejected as a whole.
+use crate::error::{AppError, AppResult};
+use crate::models::{BundleFile, TemplatePackManifest};
+use crate::services::bundle::sha256_hex;
+use crate::services::template_files::TemplateFile;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+pub const PACK_FORMAT_VERSION: u32 = 1;
+pub const PACK_EXTENSION: &str = "thpack";
+
+const MANIFEST: &str = "manifest.json";
+const SIGNATURE: &str = "manifest.sig";
+
+pub fn write_pack(templates: &[TemplateFile], key: &str, output_path: &Path) -> AppResult<TemplatePackManifest> {
+    let file = std::fs::File::create(output_path)
+        .map_err(|e| AppError::File(format!("Cannot create {}: {}", output_path.display(), e)))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut files: Vec<BundleFile> = Vec::new();
+    for template in templates {
+        let json = serde_json::to_vec_pretty(template)
+            .map_err(|e| AppError::Validation(format!("Failed to serialize template: {}", e)))?;
+        let name = unique_file_name(&template.name, &files);
+        zip.start_file(name.as_str(), options).map_err(zip_error)?;
+        zip.write_all(&json)?;
+        files.push(BundleFile {
+            sha256: sha256_hex(&json),
+            size_bytes: json.len() as u64,
+            path: name,
+        });
+    }
+
+    let manifest = TemplatePackManifest {
+        format_version: PACK_FORMAT_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        files,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize manifest: {}", e)))?;
+    zip.start_file(MANIFEST, options).map_err(zip_error)?;
+    zip.write_all(&manifest_json)?;
+    zip.start_file(SIGNATURE, options).map_err(zip_error)?;
+    zip.write_all(sign(key, &manifest_json).as_bytes())?;
+    zip.finish().map_err(zip_error)?;
+
+    Ok(manifest)
+}
+
+/// Verify a pack's signature and checksums; returns each template file's name and JSON
+pub fn read_pack(path: &Path, key: &str) -> AppResult<Vec<(String, String)>> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| AppError::File(format!("Cannot open {}: {}", path.display(), e)))?;
+    let mut zip = zip::ZipArchive::new(file).map_err(|e| invalid(&e.to_string()))?;
+
+    let manifest_json = read_entry(&mut zip, MANIFEST)?;
+    let signature = read_entry(&mut zip, SIGNATURE)?;
+    if !verify(key, &manifest_json, &signature) {
+        return Err(invalid("signature does not match; it was changed after export or signed with another key"));
+    }
+
+    let manifest: TemplatePackManifest = serde_json::from_slice(&manifest_json)
+        .map_err(|e| invalid(&format!("unreadable manifest: {}", e)))?;
+    if manifest.format_version > PACK_FORMAT_VERSION {
+        return Err(invalid(&format!(
+            "format version {} is newer than this app supports ({})",
+            manifest.format_version, PACK_FORMAT_VERSION
+        )));
+    }
+
+    let unlisted = zip
+        .file_names()
+        .find(|name| *name != MANIFEST && *name != SIGNATURE && !manifest.files.iter().any(|f| f.path == *name))
+        .map(str::to_string);
+    if let Some(name) = unlisted {
+        return Err(invalid(&format!("{} is not in the manifest", name)));
+    }
+
+    let mut templates = Vec::new();
+    for listed in &manifest.files {
+        let bytes = read_entry(&mut zip, &listed.path)?;
+        if sha256_hex(&bytes) != listed.sha256 {
+            return Err(invalid(&format!("checksum mismatch for {}", listed.path)));
+        }
+        let json = String::from_utf8(bytes).map_err(|_| invalid(&format!("{} is not UTF-8", listed.path)))?;
+        templates.push((listed.path.clone(), json));
+    }
+    Ok(templates)
+}
+
+fn read_entry(zip: &mut zip::ZipArchive<std::fs::File>, name: &str) -> AppResult<Vec<u8>> {
+    let mut entry = zip
+        .by_name(name)
+        .map_err(|_| invalid(&format!("{} is missing", name)))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn sign(key: &str, manifest: &[u8]) -> String {
+    STANDARD.encode(mac(key, manifest).finalize().into_bytes())
+}
+
+fn verify(key: &str, manifest: &[u8], signature: &[u8]) -> bool {
+    let Ok(signature) = STANDARD.decode(signature.trim_ascii()) else {
+        return false;
+    };
+    mac(key, manifest).verify_slice(&signature).is_ok()
+}
+
+fn mac(key: &str, manifest: &[u8]) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(manifest);
+    mac
+}
+
+/// `templates/<slug>.json`, suffixed when two templates share a slug
+fn unique_file_name(template_name: &str, existing: &[BundleFile]) -> String {
+    let slug: String = template_name
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let slug = if slug.is_empty() { "template".to_string() } else { slug };
+
+    let mut candidate = format!("templates/{}.json", slug);
+    let mut n = 1;
+    while existing.iter().any(|f| f.path == candidate) {
+        n += 1;
+        candidate = format!("templates/{}-{}.json", slug, n);
+    }
+    candidate
+}
+
+fn invalid(reason: &str) -> AppError {
+    AppError::Validation(format!("Template pack rejected: {}", reason))
+}
+
+fn zip_error(err: zip::result::ZipError) -> AppError {
+    AppError::File(format!("Failed to write template pack: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ChecklistItem;
+    use std::path::PathBuf;
+
+    const KEY: &str = "pack-key";
+
+    fn template(name: &str) -> TemplateFile {
+        TemplateFile {
+            name: name.to_string(),
+            description: String::new(),
+            category: "Network".to_string(),
+            checklist_items: vec![ChecklistItem {
+                text: "Restarted the VPN client".to_string(),
+                checked: false,
+                link: None,
+            }],
+            l2_team: None,
+            body: None,
+            expected_artifacts: Vec::new(),
+        }
+    }
+
+    fn pack_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("template-pack-{}-{}.{}", name, std::process::id(), PACK_EXTENSION))
+    }
+
+    /// Rewrite a pack, replacing one entry's bytes
+    fn tamper(path: &Path, entry: &str, bytes: &[u8]) {
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(path).unwrap()).unwrap();
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            entries.push((file.name().to_string(), content));
+        }
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for (name, content) in entries {
+            zip.start_file(name.as_str(), SimpleFileOptions::default()).unwrap();
+            zip.write_all(if name == entry { bytes } else { &content }).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = pack_path("round-trip");
+        let manifest = write_pack(&[template("Network/VPN"), template("Network VPN")], KEY, &path).unwrap();
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["templates/network-vpn.json", "templates/network-vpn-2.json"]);
+
+        let templates = read_pack(&path, KEY).unwrap();
+        assert_eq!(templates.len(), 2);
+        assert!(templates[0].1.contains("Restarted the VPN client"));
+        assert!(read_pack(&path, "other-key").is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_template_rejected() {
+        let path = pack_path("tampered");
+        write_pack(&[template("Network/VPN")], KEY, &path).unwrap();
+        let edited = serde_json::to_vec_pretty(&template("Network/VPN (edited)")).unwrap();
+        tamper(&path, "templates/network-vpn.json", &edited);
+
+        let err = read_pack(&path, KEY).unwrap_err().to_string();
+        assert!(err.contains("checksum mismatch"), "{}", err);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_tampered_manifest_rejected() {
+        let path = pack_path("manifest");
+        let mut manifest = write_pack(&[template("Network/VPN")], KEY, &path).unwrap();
+        manifest.files[0].sha256 = sha256_hex(b"something else");
+        tamper(&path, MANIFEST, &serde_json::to_vec_pretty(&manifest).unwrap());
+
+        let err = read_pack(&path, KEY).unwrap_err().to_string();
+        assert!(err.contains("signature"), "{}", err);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,109 @@
+/// Cache of the agent's open Jira tickets for the ticket picker
+///
+/// A background task refreshes the list on an interval; the picker reads the
+/// cache, so it opens instantly and keeps working for a while when Jira or
+/// the network is down. Refreshes are rate-limited by the stored fetch time,
+/// which also holds across restarts.
+use crate::error::{AppError, AppResult};
+use crate::models::{MyOpenTickets, OpenTicket};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::time::Duration;
+
+pub const DEFAULT_JQL: &str = "assignee = currentUser() AND statusCategory != Done ORDER BY updated DESC";
+pub const MAX_TICKETS: u32 = 100;
+/// Floor for the background interval, however low it's configured
+pub const MIN_PREFETCH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Floor between manual refreshes from the picker
+pub const MIN_MANUAL_INTERVAL: Duration = Duration::from_secs(60);
+
+pub fn save(conn: &Connection, tickets: &[OpenTicket], fetched_at: DateTime<Utc>) -> AppResult<()> {
+    let json = serde_json::to_string(tickets)
+        .map_err(|e| AppError::Validation(format!("Failed to serialize tickets: {}", e)))?;
+    conn.execute(
+        "INSERT INTO open_ticket_cache (id, tickets, fetched_at) VALUES (1, ?1, ?2)
+        ON CONFLICT(id) DO UPDATE SET tickets = ?1, fetched_at = ?2",
+        params![json, fetched_at.to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+pub fn load(conn: &Connection) -> AppResult<MyOpenTickets> {
+    let row: Option<(String, String)> = conn
+        .query_row("SELECT tickets, fetched_at FROM open_ticket_cache WHERE id = 1", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .optional()?;
+
+    Ok(match row {
+        // An unreadable cache is treated as empty; the next refresh overwrites it
+        Some((json, fetched_at)) => MyOpenTickets {
+            tickets: serde_json::from_str(&json).unwrap_or_default(),
+            fetched_at: Some(fetched_at),
+        },
+        None => MyOpenTickets { tickets: Vec::new(), fetched_at: None },
+    })
+}
+
+/// Whether at least `interval` has passed since the last fetch
+///
+/// A fetch time in the future means the clock moved back; that counts as due
+/// rather than blocking refreshes until the clock catches up.
+pub fn is_due(fetched_at: Option<&str>, now: DateTime<Utc>, interval: Duration) -> bool {
+    let Some(last) = fetched_at.and_then(|at| DateTime::parse_from_rfc3339(at).ok()) else {
+        return true;
+    };
+    let elapsed = now.signed_duration_since(last.with_timezone(&Utc));
+    elapsed.to_std().map(|elapsed| elapsed >= interval).unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(include_str!("../../migrations/024_open_ticket_cache.sql")).unwrap();
+        conn
+    }
+
+    fn ticket(key: &str) -> OpenTicket {
+        OpenTicket {
+            key: key.to_string(),
+            summary: "VPN drops".to_string(),
+            status: "In Progress".to_string(),
+            priority: Some("High".to_string()),
+            updated: None,
+        }
+    }
+
+    #[test]
+    fn test_save_replaces_cache() {
+        let conn = setup();
+        assert!(load(&conn).unwrap().fetched_at.is_none());
+
+        let now = Utc::now();
+        save(&conn, &[ticket("IT-1"), ticket("IT-2")], now).unwrap();
+        save(&conn, &[ticket("IT-3")], now).unwrap();
+
+        let cached = load(&conn).unwrap();
+        assert_eq!(cached.tickets.len(), 1);
+        assert_eq!(cached.tickets[0].key, "IT-3");
+        assert_eq!(cached.fetched_at, Some(now.to_rfc3339()));
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = Utc::now();
+        let recent = (now - chrono::Duration::seconds(30)).to_rfc3339();
+        let old = (now - chrono::Duration::minutes(10)).to_rfc3339();
+
+        assert!(is_due(None, now, MIN_PREFETCH_INTERVAL));
+        assert!(!is_due(Some(&recent), now, MIN_MANUAL_INTERVAL));
+        assert!(is_due(Some(&old), now, MIN_PREFETCH_INTERVAL));
+        assert!(is_due(Some("garbage"), now, MIN_PREFETCH_INTERVAL));
+
+        let future = (now + chrono::Duration::hours(2)).to_rfc3339();
+        assert!(is_due(Some(&future), now, MIN_PREFETCH_INTERVAL));
+    }
+}
@@ -0,0 +1,75 @@
+/// Pre-post check that the ticket still needs an escalation comment
+use crate::models::JiraTicket;
+
+/// Reasons the escalation may no longer be needed; empty when it's fine to post
+pub fn check(ticket: &JiraTicket, closed_statuses: &[String], l2_assignees: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    if closed_statuses.iter().any(|s| s.trim().eq_ignore_ascii_case(ticket.status.trim())) {
+        warnings.push(format!("{} is already {}", ticket.key, ticket.status));
+    }
+
+    if let Some(assignee) = &ticket.assignee {
+        let is_l2 = l2_assignees.iter().map(|a| a.trim()).any(|a| {
+            a.eq_ignore_ascii_case(assignee.display_name.trim())
+                || assignee.email.as_deref().is_some_and(|email| a.eq_ignore_ascii_case(email))
+        });
+        if is_l2 {
+            warnings.push(format!(
+                "{} is already assigned to {} on the L2 team",
+                ticket.key, assignee.display_name
+            ));
+        }
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::JiraUser;
+
+    fn ticket(status: &str, assignee: Option<&str>) -> JiraTicket {
+        JiraTicket {
+            key: "IT-42".to_string(),
+            summary: "VPN drops".to_string(),
+            description: None,
+            status: status.to_string(),
+            status_category: None,
+            issue_type: None,
+            reporter: None,
+            assignee: assignee.map(|name| JiraUser {
+                display_name: name.to_string(),
+                email: Some(format!("{}@example.com", name.to_lowercase())),
+                avatar_url: None,
+            }),
+            comments: vec![],
+            linked_issues: vec![],
+            subtasks: vec![],
+            components: vec![],
+            labels: vec![],
+            partial: false,
+            missing_sections: vec![],
+        }
+    }
+
+    #[test]
+    fn test_open_ticket_passes() {
+        let closed = vec!["Closed".to_string(), "Resolved".to_string()];
+        assert!(check(&ticket("In Progress", Some("Alex")), &closed, &["Network Team".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn test_closed_and_l2_assigned() {
+        let closed = vec!["Resolved".to_string()];
+        let l2 = vec!["network team".to_string(), "sam@example.com".to_string()];
+
+        let warnings = check(&ticket("resolved", Some("Sam")), &closed, &l2);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("already resolved"));
+        assert!(warnings[1].contains("assigned to Sam"));
+
+        assert_eq!(check(&ticket("Open", Some("Network Team")), &closed, &l2).len(), 1);
+    }
+}
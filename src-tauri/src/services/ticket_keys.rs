@@ -0,0 +1,154 @@
+/// Ticket key validation and extraction from pasted URLs
+///
+/// The accepted key format is a regex from preferences so on-prem Jira
+/// (`ABC_OPS-123`) and numeric ServiceNow ids work alongside Jira Cloud.
+use crate::error::{AppError, AppResult};
+use crate::models::TicketKeyPreset;
+use regex::Regex;
+
+/// Jira Cloud: project key of letters/digits, then `-<number>`
+pub const JIRA_CLOUD_PATTERN: &str = r"[A-Z][A-Z0-9]+-\d+";
+/// Jira Server/Data Center also allows underscores in project keys
+pub const JIRA_SERVER_PATTERN: &str = r"[A-Z][A-Z0-9_]+-\d+";
+/// Numeric-only ids (e.g. ServiceNow)
+pub const NUMERIC_PATTERN: &str = r"\d+";
+
+/// Named formats offered in settings
+pub fn presets() -> Vec<TicketKeyPreset> {
+    [
+        ("Jira Cloud", JIRA_CLOUD_PATTERN),
+        ("Jira Server / Data Center", JIRA_SERVER_PATTERN),
+        ("Numeric (ServiceNow)", NUMERIC_PATTERN),
+    ]
+    .into_iter()
+    .map(|(name, pattern)| TicketKeyPreset {
+        name: name.to_string(),
+        pattern: pattern.to_string(),
+    })
+    .collect()
+}
+
+pub struct TicketKeyRules {
+    pattern: String,
+    exact: Regex,
+    search: Regex,
+}
+
+impl TicketKeyRules {
+    pub fn new(pattern: &str) -> AppResult<Self> {
+        let pattern = pattern.trim();
+        if pattern.is_empty() {
+            return Err(AppError::Validation("Ticket key pattern cannot be empty".into()));
+        }
+
+        let exact = Regex::new(&format!("^(?:{})$", pattern))
+            .map_err(|e| AppError::Validation(format!("Invalid ticket key pattern: {}", e)))?;
+        let search = Regex::new(&format!(r"\b(?:{})\b", pattern))
+            .map_err(|e| AppError::Validation(format!("Invalid ticket key pattern: {}", e)))?;
+
+        Ok(Self {
+            pattern: pattern.to_string(),
+            exact,
+            search,
+        })
+    }
+
+    /// Validate a ticket key, or extract one from a pasted ticket URL
+    pub fn parse(&self, input: &str) -> AppResult<String> {
+        let input = input.trim();
+
+        if input.starts_with("http://") || input.starts_with("https://") {
+            return self.extract_from_url(input).ok_or_else(|| {
+                AppError::Validation(format!("No ticket key found in URL {}", input))
+            });
+        }
+
+        self.validate(input)
+    }
+
+    fn validate(&self, input: &str) -> AppResult<String> {
+        if self.exact.is_match(input) {
+            return Ok(input.to_string());
+        }
+
+        // Jira keys are case-insensitive; accept "proj-12" as "PROJ-12"
+        let upper = input.to_uppercase();
+        if self.exact.is_match(&upper) {
+            return Ok(upper);
+        }
+
+        Err(AppError::Validation(format!(
+            "Invalid ticket ID \"{}\" (expected format {})",
+            input, self.pattern
+        )))
+    }
+
+    fn extract_from_url(&self, url: &str) -> Option<String> {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+
+        // Explicit parameters first (board views use ?selectedIssue=KEY-1)
+        for pair in query.split('&') {
+            if let Some((name, value)) = pair.split_once('=') {
+                if matches!(name, "selectedIssue" | "issueKey" | "number" | "id") {
+                    if let Ok(key) = self.validate(value) {
+                        return Some(key);
+                    }
+                }
+            }
+        }
+
+        // Then /browse/KEY-1 and any path segment that is itself a key
+        path.rsplit('/')
+            .find_map(|segment| self.validate(segment).ok())
+            .or_else(|| self.search.find(path).map(|m| m.as_str().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jira_cloud_keys() {
+        let rules = TicketKeyRules::new(JIRA_CLOUD_PATTERN).unwrap();
+        assert_eq!(rules.parse("SUP-123").unwrap(), "SUP-123");
+        assert_eq!(rules.parse(" sup-123 ").unwrap(), "SUP-123");
+        assert!(rules.parse("ABC_OPS-123").is_err());
+        assert!(rules.parse("123").is_err());
+    }
+
+    #[test]
+    fn test_jira_server_keys() {
+        let rules = TicketKeyRules::new(JIRA_SERVER_PATTERN).unwrap();
+        assert_eq!(rules.parse("ABC_OPS-123").unwrap(), "ABC_OPS-123");
+    }
+
+    #[test]
+    fn test_numeric_ids() {
+        let rules = TicketKeyRules::new(NUMERIC_PATTERN).unwrap();
+        assert_eq!(rules.parse("4815162342").unwrap(), "4815162342");
+        assert!(rules.parse("SUP-1").is_err());
+    }
+
+    #[test]
+    fn test_extract_from_urls() {
+        let rules = TicketKeyRules::new(JIRA_SERVER_PATTERN).unwrap();
+        assert_eq!(
+            rules.parse("https://jira.example.com/browse/ABC_OPS-123").unwrap(),
+            "ABC_OPS-123"
+        );
+        assert_eq!(
+            rules
+                .parse("https://example.atlassian.net/jira/software/projects/SUP/boards/1?selectedIssue=SUP-42")
+                .unwrap(),
+            "SUP-42"
+        );
+        assert!(rules.parse("https://example.atlassian.net/jira/dashboards").is_err());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(TicketKeyRules::new("[A-Z").is_err());
+        assert!(TicketKeyRules::new("  ").is_err());
+    }
+}
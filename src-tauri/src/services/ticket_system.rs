@@ -1,5 +1,5 @@
 use crate::error::AppResult;
-use crate::models::JiraTicket;
+use crate::models::{BackendCapabilities, JiraTicket};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -8,4 +8,6 @@ pub trait TicketSystemClient {
     async fn fetch_ticket(&self, id: &str) -> AppResult<JiraTicket>;
     async fn post_comment(&self, id: &str, body: &str) -> AppResult<()>;
     async fn test_connection(&self) -> AppResult<String>;
+    /// Optional actions this backend can perform for the configured account; callers skip or reject the rest
+    async fn capabilities(&self) -> AppResult<BackendCapabilities>;
 }
@@ -0,0 +1,177 @@
+/// Candidate attachments from configured "watch folders"
+///
+/// Diagnostic tools drop their output in known directories; rather than have
+/// agents browse for the right logs, files there are offered when their name
+/// (or the folder they sit in) mentions the ticket key, or when they were
+/// written recently. Relative attachment paths resolve against the same folders.
+use crate::models::CandidateAttachment;
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// How many folder levels below a watch folder are searched
+const MAX_DEPTH: usize = 2;
+pub const MAX_CANDIDATES: usize = 50;
+
+/// Files matching the ticket key (any age) first, then other files modified within `window`, newest first
+pub fn candidates(folders: &[String], ticket_key: &str, window: Duration, now: SystemTime) -> Vec<CandidateAttachment> {
+    let key = ticket_key.to_lowercase();
+    let since = now.checked_sub(window).unwrap_or(SystemTime::UNIX_EPOCH);
+    let mut found = Vec::new();
+
+    for folder in folders.iter().map(|f| f.trim()).filter(|f| !f.is_empty()) {
+        collect(Path::new(folder), &key, since, 0, false, &mut found);
+    }
+
+    found.sort_by(|a, b| {
+        b.matches_ticket
+            .cmp(&a.matches_ticket)
+            .then_with(|| b.modified_at.cmp(&a.modified_at))
+    });
+    found.truncate(MAX_CANDIDATES);
+    found
+}
+
+fn collect(
+    dir: &Path,
+    key: &str,
+    since: SystemTime,
+    depth: usize,
+    in_ticket_dir: bool,
+    found: &mut Vec<CandidateAttachment>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            log::warn!("Skipping watch folder {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else { continue };
+        let named_for_ticket = in_ticket_dir || mentions_key(&name.to_lowercase(), key);
+
+        if metadata.is_dir() {
+            if depth < MAX_DEPTH {
+                collect(&path, key, since, depth + 1, named_for_ticket, found);
+            }
+            continue;
+        }
+
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+        if !named_for_ticket && modified < since {
+            continue;
+        }
+
+        found.push(CandidateAttachment {
+            file_path: path.to_string_lossy().into_owned(),
+            file_name: name,
+            size_bytes: metadata.len(),
+            modified_at: DateTime::<Utc>::from(modified).to_rfc3339(),
+            matches_ticket: named_for_ticket,
+        });
+    }
+}
+
+/// Whether `name` contains `key` as a whole token, so "help-42" doesn't match "help-420.log"
+fn mentions_key(name: &str, key: &str) -> bool {
+    if key.is_empty() {
+        return false;
+    }
+
+    name.match_indices(key).any(|(start, _)| {
+        let before = name[..start].chars().next_back();
+        let after = name[start + key.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// Resolve a relative attachment path against the first watch folder that has it
+///
+/// Absolute paths, and relative ones no folder contains, are returned unchanged.
+/// A path that leaves its folder (`../`, or a symlink pointing out) never resolves.
+pub fn resolve(folders: &[String], path: &str) -> String {
+    if Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+
+    folders
+        .iter()
+        .filter_map(|folder| fs::canonicalize(folder.trim()).ok())
+        .filter_map(|folder| {
+            let candidate = fs::canonicalize(folder.join(path)).ok()?;
+            (candidate.starts_with(&folder) && candidate.is_file()).then_some(candidate)
+        })
+        .next()
+        .map(|candidate| candidate.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir;
+
+    #[test]
+    fn test_candidates_match_key_or_window() {
+        let dir = temp_dir("candidates");
+        fs::write(dir.join("diag-HELP-42.zip"), b"zip").unwrap();
+        fs::write(dir.join("client.log"), b"log").unwrap();
+        fs::write(dir.join("diag-help-420.zip"), b"zip").unwrap();
+        fs::create_dir_all(dir.join("help-42")).unwrap();
+        fs::write(dir.join("help-42").join("trace.txt"), b"trace").unwrap();
+        fs::write(dir.join(".DS_Store"), b"").unwrap();
+        let folders = vec![dir.to_string_lossy().into_owned()];
+
+        // Window in the past: only key matches qualify
+        let later = SystemTime::now() + Duration::from_secs(7200);
+        let found = candidates(&folders, "HELP-42", Duration::from_secs(3600), later);
+        let mut names: Vec<&str> = found.iter().map(|c| c.file_name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["diag-HELP-42.zip", "trace.txt"]);
+
+        // Window covering now: recent files come after the key matches
+        let found = candidates(&folders, "HELP-42", Duration::from_secs(3600), SystemTime::now());
+        assert_eq!(found.len(), 4);
+        assert!(found[0].matches_ticket && found[1].matches_ticket);
+        assert!(found[2..].iter().all(|c| !c.matches_ticket));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_mentions_key() {
+        assert!(mentions_key("diag-help-42.zip", "help-42"));
+        assert!(mentions_key("help-42", "help-42"));
+        assert!(!mentions_key("diag-help-420.zip", "help-42"));
+        assert!(!mentions_key("xhelp-42.log", "help-42"));
+        assert!(!mentions_key("anything", ""));
+    }
+
+    #[test]
+    fn test_resolve_relative_paths() {
+        let dir = temp_dir("resolve");
+        let watched = dir.join("watched");
+        fs::create_dir_all(&watched).unwrap();
+        fs::write(watched.join("vpn.log"), b"log").unwrap();
+        fs::write(dir.join("secret.txt"), b"secret").unwrap();
+        let folders = vec!["/nonexistent-watch-folder".to_string(), watched.to_string_lossy().into_owned()];
+
+        let expected = fs::canonicalize(watched.join("vpn.log")).unwrap();
+        assert_eq!(resolve(&folders, "vpn.log"), expected.to_string_lossy());
+        assert_eq!(resolve(&folders, "missing.log"), "missing.log");
+        assert_eq!(resolve(&folders, "/var/log/system.log"), "/var/log/system.log");
+
+        // Paths that climb out of the folder stay unresolved
+        assert_eq!(resolve(&folders, "../secret.txt"), "../secret.txt");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
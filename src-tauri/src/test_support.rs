@@ -0,0 +1,55 @@
+/// Fixtures shared by unit tests across modules
+use crate::models::{Escalation, EscalationStatus};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+impl Escalation {
+    /// An empty draft for `TEST-1`; tests override the fields they care about
+    pub fn fixture() -> Self {
+        Escalation {
+            id: 1,
+            ticket_id: "TEST-1".to_string(),
+            template_id: None,
+            problem_summary: String::new(),
+            checklist: Vec::new(),
+            current_status: String::new(),
+            next_steps: String::new(),
+            llm_summary: None,
+            llm_confidence: None,
+            markdown_output: None,
+            status: EscalationStatus::Draft,
+            posted_at: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            attachments: Vec::new(),
+            private_notes: None,
+            locked_by: None,
+            locked_at: None,
+            ticket_snapshot: None,
+            summary_language: None,
+            summary_translation: None,
+            parent_escalation_id: None,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            render_version: 1,
+            l2_teams: Vec::new(),
+            environment_info: None,
+            incident_id: None,
+        }
+    }
+}
+
+/// A fresh, empty directory under the system temp dir, unique to this call
+pub fn temp_dir(name: &str) -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "ticket-handoff-{}-{}-{}",
+        name,
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
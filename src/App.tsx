@@ -1,5 +1,9 @@
-import { BrowserRouter, Routes, Route, Link } from 'react-router';
+import { useEffect, useState } from 'react';
+import { BrowserRouter, Routes, Route, Link, Navigate } from 'react-router';
 import { ToastProvider } from './contexts/ToastContext';
+import DatabaseRecovery from './components/DatabaseRecovery';
+import { getStartupStatus, onDbReady } from './lib/tauri';
+import type { StartupStatus } from './types';
 import Home from './pages/Home';
 import NewEscalation from './pages/NewEscalation';
 import History from './pages/History';
@@ -7,6 +11,28 @@ import Settings from './pages/Settings';
 import './styles/main.css';
 
 function App() {
+  const [startup, setStartup] = useState<StartupStatus | null>(null);
+
+  const loadStartupStatus = () => {
+    getStartupStatus()
+      .then(setStartup)
+      .catch(() => setStartup(null));
+  };
+
+  useEffect(() => {
+    // Subscribe before asking, so a db-ready sent in between isn't missed
+    const unlisten = onDbReady(setStartup);
+    loadStartupStatus();
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  // Migrations are still running in the background
+  const initializing = startup?.initializing ?? false;
+  // Settings-only mode: the database failed to open at launch
+  const degraded = startup?.degraded ?? false;
+
   return (
     <ToastProvider>
       <BrowserRouter>
@@ -15,15 +41,19 @@ function App() {
           <div className="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8">
             <div className="flex justify-between h-16">
               <div className="flex space-x-8">
-                <Link to="/" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-900">
-                  Home
-                </Link>
-                <Link to="/new" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-500 hover:text-gray-900">
-                  New Escalation
-                </Link>
-                <Link to="/history" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-500 hover:text-gray-900">
-                  History
-                </Link>
+                {!degraded && !initializing && (
+                  <>
+                    <Link to="/" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-900">
+                      Home
+                    </Link>
+                    <Link to="/new" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-500 hover:text-gray-900">
+                      New Escalation
+                    </Link>
+                    <Link to="/history" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-500 hover:text-gray-900">
+                      History
+                    </Link>
+                  </>
+                )}
                 <Link to="/settings" className="inline-flex items-center px-1 pt-1 text-sm font-medium text-gray-500 hover:text-gray-900">
                   Settings
                 </Link>
@@ -32,12 +62,22 @@ function App() {
           </div>
         </nav>
         <main className="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8 py-8">
-          <Routes>
-            <Route path="/" element={<Home />} />
-            <Route path="/new" element={<NewEscalation />} />
-            <Route path="/history" element={<History />} />
-            <Route path="/settings" element={<Settings />} />
-          </Routes>
+          {degraded && startup && <DatabaseRecovery status={startup} onRecovered={loadStartupStatus} />}
+          {initializing ? (
+            <p className="text-sm text-gray-500">Opening the database…</p>
+          ) : degraded ? (
+            <Routes>
+              <Route path="/settings" element={<Settings />} />
+              <Route path="*" element={<Navigate to="/settings" replace />} />
+            </Routes>
+          ) : (
+            <Routes>
+              <Route path="/" element={<Home />} />
+              <Route path="/new" element={<NewEscalation />} />
+              <Route path="/history" element={<History />} />
+              <Route path="/settings" element={<Settings />} />
+            </Routes>
+          )}
         </main>
       </div>
     </BrowserRouter>
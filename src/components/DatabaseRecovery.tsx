@@ -0,0 +1,61 @@
+import { useState } from 'react';
+import { repairOrResetDatabase } from '../lib/tauri';
+import type { StartupStatus } from '../types';
+
+interface DatabaseRecoveryProps {
+  status: StartupStatus;
+  onRecovered: () => void;
+}
+
+export default function DatabaseRecovery({ status, onRecovered }: DatabaseRecoveryProps) {
+  const [working, setWorking] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const recover = async (reset: boolean) => {
+    if (
+      reset &&
+      !window.confirm('Start with an empty database? The current file is kept as a backup next to it.')
+    ) {
+      return;
+    }
+
+    setWorking(true);
+    setError(null);
+    try {
+      await repairOrResetDatabase(reset);
+      onRecovered();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setWorking(false);
+    }
+  };
+
+  return (
+    <div className="mb-6 rounded-md border border-red-200 bg-red-50 p-4 text-red-800">
+      <h2 className="font-semibold">The escalation database could not be opened</h2>
+      <p className="mt-1 text-sm">
+        Only Settings is available until the database is repaired or reset.
+      </p>
+      {status.error && <pre className="mt-2 whitespace-pre-wrap text-xs">{status.error}</pre>}
+      {status.dbPath && <p className="mt-2 text-xs">Database: {status.dbPath}</p>}
+      {error && <p className="mt-2 text-sm font-medium">{error}</p>}
+      <div className="mt-4 flex space-x-3">
+        <button
+          onClick={() => recover(false)}
+          disabled={working}
+          className="rounded-md bg-red-600 px-3 py-1.5 text-sm font-medium text-white hover:bg-red-700 disabled:opacity-50"
+        >
+          Try to repair
+        </button>
+        <button
+          onClick={() => recover(true)}
+          disabled={working}
+          className="rounded-md border border-red-300 px-3 py-1.5 text-sm font-medium hover:bg-red-100 disabled:opacity-50"
+        >
+          Reset database
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -1,46 +1,235 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type {
+  Attachment,
+  AttachmentCleanupReport,
+  BackendCapabilities,
+  BounceReport,
+  BundleManifest,
+  AttachmentQuotaCheck,
+  CandidateAttachment,
+  DailySummary,
+  DataIntegrityReport,
+  DraftContext,
   Template,
+  TemplateImpact,
+  TemplateImportResult,
+  TemplatePackManifest,
+  NextStepBlock,
+  NextStepBlockInput,
+  EnvironmentInfo,
   Escalation,
+  FieldChange,
   EscalationInput,
   EscalationSummary,
+  InterruptedJob,
+  JiraAccount,
+  JiraIdentity,
+  JiraLinkedIssue,
   JiraTicket,
+  MyOpenTickets,
+  EscalationQueue,
+  KbDraft,
+  LintWarning,
   LLMSummaryResult,
+  LlmUsageReport,
   ApiConfig,
   ChecklistItem,
+  ChecklistItemStat,
+  DatabaseRecoveryReport,
+  EscalationCommentRef,
+  MaintenanceReport,
+  OutputFormat,
+  OutputSizeReport,
+  Preferences,
+  PrioritySuggestion,
+  QualityScore,
+  ScreeningReport,
+  StartupStatus,
+  TemplateRenderResult,
+  TicketKeyPreset,
+  UnacknowledgedEscalation,
+  TranslationResult,
+  OnboardingState,
+  OnboardingStep,
 } from '../types';
 
 // Templates
 export const listTemplates = () => invoke<Template[]>('list_templates');
 export const getTemplate = (id: number) => invoke<Template>('get_template', { id });
+export const getTemplatesForTicket = (key: string) => invoke<Template[]>('get_templates_for_ticket', { key });
+export const importTemplatesFromDirectory = (path: string) =>
+  invoke<TemplateImportResult[]>('import_templates_from_directory', { path });
+export const exportTemplatePack = (path: string) =>
+  invoke<TemplatePackManifest>('export_template_pack', { path });
+export const importTemplatePack = (path: string) =>
+  invoke<TemplateImportResult[]>('import_template_pack', { path });
+export const testRenderTemplate = (templateId: number, sampleInput?: EscalationInput) =>
+  invoke<TemplateRenderResult>('test_render_template', { templateId, sampleInput });
+export const getChecklistItemStats = (templateId: number) =>
+  invoke<ChecklistItemStat[]>('get_checklist_item_stats', { templateId });
+export const listNextStepBlocks = () => invoke<NextStepBlock[]>('list_next_step_blocks');
+export const saveNextStepBlock = (block: NextStepBlockInput) =>
+  invoke<NextStepBlock>('save_next_step_block', { block });
+export const deleteNextStepBlock = (id: number) => invoke<void>('delete_next_step_block', { id });
 
 // Escalations
 export const saveEscalation = (input: EscalationInput) =>
   invoke<number>('save_escalation', { input });
 export const getEscalation = (id: number) => invoke<Escalation>('get_escalation', { id });
-export const listEscalations = () => invoke<EscalationSummary[]>('list_escalations');
+export const listEscalations = (incidentId?: string) =>
+  invoke<EscalationSummary[]>('list_escalations', { incidentId });
+export const linkIncident = (escalationId: number, incidentId: string | null) =>
+  invoke<void>('link_incident', { escalationId, incidentId });
 export const deleteEscalation = (id: number) => invoke<void>('delete_escalation', { id });
 export const renderMarkdown = (input: EscalationInput) =>
   invoke<string>('render_markdown', { input });
-export const postEscalation = (id: number, filePaths: string[]) =>
-  invoke<void>('post_escalation', { id, filePaths });
-export const retryPostEscalation = (id: number, filePaths: string[]) =>
-  invoke<void>('retry_post_escalation', { id, filePaths });
+export const renderOutput = (input: EscalationInput, format?: OutputFormat) =>
+  invoke<string>('render_output', { input, format });
+export const renderWithVersion = (id: number, version?: number) =>
+  invoke<string>('render_with_version', { id, version });
+export const previewTemplateImpact = (templateId: number, proposed: Template) =>
+  invoke<TemplateImpact>('preview_template_impact', { templateId, proposed });
+export const scoreEscalation = (id: number) => invoke<QualityScore>('score_escalation', { id });
+export const lintEscalation = (id: number) => invoke<LintWarning[]>('lint_escalation', { id });
+export const suggestPriority = (id: number, useLlm?: boolean) =>
+  invoke<PrioritySuggestion>('suggest_priority', { id, useLlm });
+export const checkOutputSize = (id: number) => invoke<OutputSizeReport>('check_output_size', { id });
+export const checkExpectedArtifacts = (id: number) =>
+  invoke<string[]>('check_expected_artifacts', { id });
+export const generatePrintView = (id: number) => invoke<string>('generate_print_view', { id });
+export const draftKbArticle = (escalationId: number, resolution?: string) =>
+  invoke<KbDraft>('draft_kb_article', { escalationId, resolution });
+export const listKbDrafts = () => invoke<KbDraft[]>('list_kb_drafts');
+export const exportKbDraft = (id: number, path: string) => invoke<void>('export_kb_draft', { id, path });
+export const exportAnonymizedDataset = (path: string) =>
+  invoke<number>('export_anonymized_dataset', { path });
+export const createShareLink = (escalationId: number, ttlMinutes?: number) =>
+  invoke<string>('create_share_link', { escalationId, ttlMinutes });
+export const reescalate = (originalId: number) => invoke<number>('reescalate', { originalId });
+export const markBounced = (escalationId: number, reason: string) =>
+  invoke<void>('mark_bounced', { escalationId, reason });
+export const getFieldHistory = (id: number, field: string) =>
+  invoke<FieldChange[]>('get_field_history', { id, field });
+export const getBounceReport = () => invoke<BounceReport>('get_bounce_report');
+export const lockEscalation = (id: number) => invoke<void>('lock_escalation', { id });
+export const unlockEscalation = (id: number) => invoke<void>('unlock_escalation', { id });
+export const forceUnlock = (id: number) => invoke<boolean>('force_unlock', { id });
+export const exportHandoffBundle = (id: number, path: string, includePrivateNotes?: boolean) =>
+  invoke<BundleManifest>('export_handoff_bundle', { id, path, includePrivateNotes });
+export const saveClipboardImageAsAttachment = (escalationId: number) =>
+  invoke<Attachment>('save_clipboard_image_as_attachment', { escalationId });
+export const captureEnvironmentInfo = (escalationId?: number) =>
+  invoke<EnvironmentInfo>('capture_environment_info', { escalationId });
+export const postEscalation = (
+  id: number,
+  filePaths: string[],
+  watchTicket?: boolean,
+  priority?: string,
+  confirmFreeze?: boolean
+) => invoke<void>('post_escalation', { id, filePaths, watchTicket, priority, confirmFreeze });
+export const checkTicketStatus = (id: number) => invoke<string[]>('check_ticket_status', { id });
+export const retryPostEscalation = (
+  id: number,
+  filePaths: string[],
+  rerender?: boolean,
+  confirmFreeze?: boolean,
+  watchTicket?: boolean,
+  priority?: string
+) => invoke<void>('retry_post_escalation', { id, filePaths, rerender, confirmFreeze, watchTicket, priority });
+export const listInterruptedJobs = () => invoke<InterruptedJob[]>('list_interrupted_jobs');
+export const resumeInterruptedJob = (jobId: number, confirmFreeze?: boolean) =>
+  invoke<void>('resume_interrupted_job', { jobId, confirmFreeze });
+export const dismissInterruptedJob = (jobId: number) => invoke<void>('dismiss_interrupted_job', { jobId });
+export const retryFailedAttachments = (escalationId: number, confirmFreeze?: boolean) =>
+  invoke<void>('retry_failed_attachments', { escalationId, confirmFreeze });
+export const dryRunPost = (escalationId: number, sandboxKey?: string) =>
+  invoke<string>('dry_run_post', { escalationId, sandboxKey });
 
 // Tickets
 export const fetchJiraTicket = (ticketId: string) =>
   invoke<JiraTicket>('fetch_jira_ticket', { ticketId });
-export const postToJira = (ticketId: string, comment: string) =>
-  invoke<void>('post_to_jira', { ticketId, comment });
-export const attachFilesToJira = (ticketId: string, filePaths: string[]) =>
-  invoke<void>('attach_files_to_jira', { ticketId, filePaths });
+export const loadDraftContext = (ticketKey: string) =>
+  invoke<DraftContext>('load_draft_context', { ticketKey });
+export const postToJira = (ticketId: string, comment: string, confirmFreeze?: boolean) =>
+  invoke<void>('post_to_jira', { ticketId, comment, confirmFreeze });
+export const attachFilesToJira = (ticketId: string, filePaths: string[], confirmFreeze?: boolean) =>
+  invoke<void>('attach_files_to_jira', { ticketId, filePaths, confirmFreeze });
+export const searchJiraUsers = (query: string) =>
+  invoke<JiraAccount[]>('search_jira_users', { query });
+export const parseTicketReference = (input: string) =>
+  invoke<string>('parse_ticket_reference', { input });
+export const screenAttachments = (filePaths: string[]) =>
+  invoke<ScreeningReport>('screen_attachments', { filePaths });
+export const listMyOpenTickets = (refresh?: boolean) =>
+  invoke<MyOpenTickets>('list_my_open_tickets', { refresh });
+export const listEscalationQueue = (refresh?: boolean) =>
+  invoke<EscalationQueue>('list_escalation_queue', { refresh });
+export const listCandidateAttachments = (ticketKey: string) =>
+  invoke<CandidateAttachment[]>('list_candidate_attachments', { ticketKey });
+export const checkAttachmentQuota = (ticketId: string, filePaths: string[]) =>
+  invoke<AttachmentQuotaCheck>('check_attachment_quota', { ticketId, filePaths });
+export const findEscalationComments = (key: string) =>
+  invoke<EscalationCommentRef[]>('find_escalation_comments', { key });
+export const createJiraTicket = (
+  project: string,
+  summary: string,
+  description: string,
+  issueType?: string
+) => invoke<string>('create_jira_ticket', { project, summary, description, issueType });
+export const addRemoteLink = (key: string, url: string, title: string) =>
+  invoke<void>('add_remote_link', { key, url, title });
 
 // LLM
-export const summarizeWithLlm = (checklist: ChecklistItem[], problemSummary: string) =>
-  invoke<LLMSummaryResult>('summarize_with_llm', { checklist, problemSummary });
+export const summarizeWithLlm = (
+  checklist: ChecklistItem[],
+  problemSummary: string,
+  linkedIssues?: JiraLinkedIssue[]
+) => invoke<LLMSummaryResult>('summarize_with_llm', { checklist, problemSummary, linkedIssues });
+export const translateProblemSummary = (problemSummary: string) =>
+  invoke<TranslationResult>('translate_problem_summary', { problemSummary });
+export const getLlmUsageReport = (from?: string, to?: string) =>
+  invoke<LlmUsageReport>('get_llm_usage_report', { from, to });
 
 // Settings
 export const saveApiConfig = (config: ApiConfig) =>
   invoke<void>('save_api_config', { config });
 export const getApiConfig = () => invoke<ApiConfig | null>('get_api_config');
 export const testJiraConnection = () => invoke<string>('test_jira_connection');
+export const getBackendCapabilities = () =>
+  invoke<BackendCapabilities>('get_backend_capabilities');
+export const getCurrentIdentity = () => invoke<JiraIdentity | null>('get_current_identity');
+export const getPreferences = () => invoke<Preferences>('get_preferences');
+export const savePreferences = (preferences: Preferences) =>
+  invoke<void>('save_preferences', { preferences });
+export const getTicketKeyPresets = () => invoke<TicketKeyPreset[]>('get_ticket_key_presets');
+export const saveSlackSigningSecret = (secret: string) =>
+  invoke<void>('save_slack_signing_secret', { secret });
+export const saveTemplatePackKey = (key: string) =>
+  invoke<void>('save_template_pack_key', { key });
+
+// Maintenance
+export const runDbMaintenance = () => invoke<MaintenanceReport>('run_db_maintenance');
+export const checkDataIntegrity = (repair: boolean) =>
+  invoke<DataIntegrityReport>('check_data_integrity', { repair });
+export const cleanAttachmentStorage = (dryRun: boolean, retentionDays?: number) =>
+  invoke<AttachmentCleanupReport>('clean_attachment_storage', { retentionDays, dryRun });
+export const getStartupStatus = () => invoke<StartupStatus>('get_startup_status');
+export const onDbReady = (handler: (status: StartupStatus) => void) =>
+  listen<StartupStatus>('db-ready', (event) => handler(event.payload));
+export const repairOrResetDatabase = (reset: boolean) =>
+  invoke<DatabaseRecoveryReport>('repair_or_reset_database', { reset });
+
+// Activity
+export const getDailySummary = (date?: string, narrate?: boolean) =>
+  invoke<DailySummary>('get_daily_summary', { date, narrate });
+
+// Acknowledgments
+export const syncAcknowledgments = () => invoke<number[]>('sync_acknowledgments');
+export const listUnacknowledged = (olderThanHours?: number) =>
+  invoke<UnacknowledgedEscalation[]>('list_unacknowledged', { olderThanHours });
+
+// Onboarding
+export const getOnboardingState = () => invoke<OnboardingState>('get_onboarding_state');
+export const completeOnboardingStep = (step: OnboardingStep, skip?: boolean) =>
+  invoke<OnboardingState>('complete_onboarding_step', { step, skip });
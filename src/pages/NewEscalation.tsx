@@ -323,6 +323,11 @@ export default function NewEscalation() {
               {ticket.status && (
                 <div className="mt-1 text-green-700">Status: {ticket.status}</div>
               )}
+              {ticket.partial && (
+                <div className="mt-1 text-amber-700">
+                  Jira didn't return some sections: {ticket.missingSections.join(', ')}
+                </div>
+              )}
             </div>
           )}
 
@@ -5,11 +5,68 @@ export interface Template {
   category: string;
   checklistItems: ChecklistItem[];
   l2Team: string | null;
+  body?: string | null;
+  expectedArtifacts: string[];
+  pinned: boolean;
+}
+
+export interface NextStepBlock {
+  id: number;
+  name: string;
+  body: string;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface NextStepBlockInput {
+  id?: number | null;
+  name: string;
+  body: string;
+}
+
+export interface TemplateImportResult {
+  fileName: string;
+  templateName: string | null;
+  templateId: number | null;
+  error: string | null;
+}
+
+export interface TemplateImpact {
+  templateId: number;
+  drafts: DraftImpact[];
+}
+
+export interface DraftImpact {
+  escalationId: number;
+  ticketId: string;
+  changed: boolean;
+  diff: { op: 'same' | 'added' | 'removed'; text: string }[];
+  error: string | null;
+}
+
+export interface TemplatePackManifest {
+  formatVersion: number;
+  exportedAt: string;
+  files: { path: string; sizeBytes: number; sha256: string }[];
+}
+
+export interface TemplateRenderResult {
+  markdown: string | null;
+  errors: string[];
 }
 
 export interface ChecklistItem {
   text: string;
   checked: boolean;
+  link?: string | null;
+}
+
+export interface ChecklistItemStat {
+  text: string;
+  inTemplate: boolean;
+  timesUsed: number;
+  timesChecked: number;
+  skipRate: number;
 }
 
 export interface Escalation {
@@ -23,18 +80,121 @@ export interface Escalation {
   llmSummary: string | null;
   llmConfidence: string | null;
   markdownOutput: string | null;
-  status: 'draft' | 'posted' | 'post_failed';
+  status: 'draft' | 'posted' | 'posted_with_errors' | 'post_failed' | 'bounced';
   postedAt: string | null;
   createdAt: string;
   updatedAt: string;
+  attachments: Attachment[];
+  privateNotes: string | null;
+  ticketSnapshot: TicketSnapshot | null;
+  summaryLanguage: string | null;
+  summaryTranslation: string | null;
+  lockedBy: string | null;
+  lockedAt: string | null;
+  parentEscalationId: number | null;
+  acknowledgedAt: string | null;
+  acknowledgedBy: string | null;
+  renderVersion: number;
+  l2Teams: TeamAsk[];
+  environmentInfo: EnvironmentInfo | null;
+  incidentId: string | null;
+}
+
+export interface UnacknowledgedEscalation {
+  id: number;
+  ticketId: string;
+  problemSummary: string;
+  postedAt: string;
+  hoursWaiting: number;
+}
+
+export interface OpenTicket {
+  key: string;
+  summary: string;
+  status: string;
+  priority: string | null;
+  updated: string | null;
+}
+
+export interface MyOpenTickets {
+  tickets: OpenTicket[];
+  fetchedAt: string | null;
+}
+
+export interface EscalationQueue {
+  enabled: boolean;
+  tickets: OpenTicket[];
+  fetchedAt: string | null;
+}
+
+export interface CandidateAttachment {
+  filePath: string;
+  fileName: string;
+  sizeBytes: number;
+  modifiedAt: string;
+  matchesTicket: boolean;
+}
+
+export interface AttachmentQuotaCheck {
+  uploadLimitBytes: number;
+  existingBytes: number;
+  newBytes: number;
+  warnings: string[];
+  rejections: string[];
+}
+
+export interface TicketSnapshot {
+  summary: string;
+  status: string;
+  assignee: string | null;
+  capturedAt?: string | null;
+}
+
+export interface Attachment {
+  id: number;
+  escalationId: number;
+  filePath: string;
+  description: string | null;
+  createdAt: string;
+  purgedAt?: string | null;
+  artifact: string | null;
+  annotations: ImageAnnotations | null;
+}
+
+export interface AttachmentInput {
+  filePath: string;
+  description: string | null;
+  artifact?: string | null;
+  annotations?: ImageAnnotations | null;
+}
+
+export interface ImageRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface ImageArrow {
+  fromX: number;
+  fromY: number;
+  toX: number;
+  toY: number;
+}
+
+export interface ImageAnnotations {
+  crop?: ImageRect | null;
+  arrows?: ImageArrow[];
+  redactions?: ImageRect[];
 }
 
 export interface EscalationSummary {
   id: number;
   ticketId: string;
   problemSummary: string;
-  status: 'draft' | 'posted' | 'post_failed';
+  status: 'draft' | 'posted' | 'posted_with_errors' | 'post_failed' | 'bounced';
   createdAt: string;
+  incidentId: string | null;
 }
 
 export interface EscalationInput {
@@ -46,6 +206,117 @@ export interface EscalationInput {
   nextSteps: string;
   llmSummary: string | null;
   llmConfidence: string | null;
+  attachments?: AttachmentInput[];
+  privateNotes?: string | null;
+  ticketSnapshot?: TicketSnapshot | null;
+  summaryLanguage?: string | null;
+  summaryTranslation?: string | null;
+  parentEscalationId?: number | null;
+  previousEscalation?: PreviousEscalation | null;
+  renderVersion?: number | null;
+  l2Teams?: TeamAsk[];
+  environmentInfo?: EnvironmentInfo | null;
+  incidentId?: string | null;
+}
+
+export interface EnvironmentInfo {
+  capturedAt: string;
+  items: { label: string; value: string | null; error?: string }[];
+}
+
+export interface TeamAsk {
+  team: string;
+  asks: string;
+  mentions?: string[];
+}
+
+export interface FieldChange {
+  field: string;
+  oldHash: string | null;
+  newHash: string | null;
+  actor: string | null;
+  changedAt: string;
+}
+
+export interface PreviousEscalation {
+  id: number;
+  ticketId: string;
+  problemSummary: string;
+  currentStatus: string;
+  nextSteps: string;
+  postedAt: string | null;
+}
+
+export interface QualityFactor {
+  name: string;
+  points: number;
+  maxPoints: number;
+  note: string;
+}
+
+export type LintKind = 'spelling' | 'terminology';
+
+export interface LintWarning {
+  field: string;
+  kind: LintKind;
+  found: string;
+  suggestion: string;
+  offset: number;
+}
+
+export interface PrioritySuggestion {
+  priority: string;
+  reasons: string[];
+  source: 'heuristic' | 'llm';
+}
+
+export interface SectionSize {
+  name: string;
+  chars: number;
+  words: number;
+  limit: number | null;
+  overBudget: boolean;
+}
+
+export interface OutputSizeReport {
+  totalChars: number;
+  totalWords: number;
+  maxChars: number;
+  overBudget: boolean;
+  sections: SectionSize[];
+}
+
+export interface QualityScore {
+  score: number;
+  factors: QualityFactor[];
+}
+
+export interface BounceReport {
+  postedCount: number;
+  bouncedCount: number;
+  bounceRate: number;
+  avgScoreBounced: number | null;
+  avgScoreAccepted: number | null;
+  topReasons: { reason: string; count: number }[];
+}
+
+export interface BundleManifest {
+  formatVersion: number;
+  escalationId: number;
+  ticketId: string;
+  status: Escalation['status'];
+  createdAt: string;
+  exportedAt: string;
+  files: { path: string; sizeBytes: number; sha256: string }[];
+  missingAttachments: string[];
+}
+
+export type StatusCategory = 'to_do' | 'in_progress' | 'done';
+
+export interface JiraUser {
+  displayName: string;
+  email: string | null;
+  avatarUrl: string | null;
 }
 
 export interface JiraTicket {
@@ -53,15 +324,207 @@ export interface JiraTicket {
   summary: string;
   description: string | null;
   status: string;
-  reporter: { displayName: string; email: string | null } | null;
-  assignee: { displayName: string; email: string | null } | null;
+  statusCategory: StatusCategory | null;
+  issueType: string | null;
+  reporter: JiraUser | null;
+  assignee: JiraUser | null;
   comments: { author: string; body: string; created: string }[];
+  linkedIssues: JiraLinkedIssue[];
+  subtasks: JiraLinkedIssue[];
+  components: string[];
+  labels: string[];
+  partial: boolean;
+  missingSections: string[];
+}
+
+export interface JiraLinkedIssue {
+  key: string;
+  summary: string;
+  status: string;
+  relation: string;
+}
+
+export interface SimilarEscalation {
+  escalation: EscalationSummary;
+  score: number;
+}
+
+export interface DraftContext {
+  ticket: JiraTicket;
+  templates: Template[];
+  similarEscalations: SimilarEscalation[];
+}
+
+export interface FreezeWindow {
+  label: string;
+  start: string;
+  end: string;
+  days?: string[];
+}
+
+export interface EnvironmentCommand {
+  label: string;
+  program: string;
+  args?: string[];
+}
+
+export interface L2Team {
+  name: string;
+  component?: string | null;
+  assigneeAccountId?: string | null;
+  mentionAccountIds: string[];
+}
+
+export interface JiraAccount {
+  accountId: string;
+  displayName: string;
+  email: string | null;
+  active: boolean;
+}
+
+export interface EscalationCommentRef {
+  commentId: string;
+  escalationId: number;
+  author: string;
+  created: string;
 }
 
 export interface LLMSummaryResult {
   summary: string;
   confidence: string;
   confidenceReason: string;
+  warnings: string[];
+}
+
+export interface LlmUsageReport {
+  from: string;
+  to: string;
+  totalCalls: number;
+  failedCalls: number;
+  timedOutCalls: number;
+  rows: LlmUsageRow[];
+}
+
+export interface LlmUsageRow {
+  provider: string;
+  model: string;
+  operation: string;
+  agent: string | null;
+  calls: number;
+  failures: number;
+  timeouts: number;
+  promptChars: number;
+  avgDurationMs: number;
+  maxDurationMs: number;
+}
+
+export interface TranslationResult {
+  language: string | null;
+  translation: string | null;
+}
+
+export interface ScreeningIssue {
+  severity: 'warning' | 'block';
+  code: string;
+  message: string;
+}
+
+export interface ScreeningResult {
+  filePath: string;
+  allowed: boolean;
+  issues: ScreeningIssue[];
+}
+
+export interface ScreeningReport {
+  results: ScreeningResult[];
+  blockedCount: number;
+}
+
+export interface AttachmentCleanupReport {
+  dryRun: boolean;
+  retentionDays: number;
+  fileCount: number;
+  reclaimableBytes: number;
+  skippedUnstaged: number;
+}
+
+export interface JiraIdentity {
+  accountId: string;
+  displayName: string;
+  email: string | null;
+  avatarUrl: string | null;
+  fetchedAt: string | null;
+}
+
+export interface BackendCapabilities {
+  backend: string;
+  attachments: boolean;
+  internalComments: boolean;
+  transitions: boolean;
+  watchers: boolean;
+  remoteLinks: boolean;
+  priority: boolean;
+  components: boolean;
+  assign: boolean;
+}
+
+export type OutputFormat = 'markdown' | 'adf' | 'jira_wiki' | 'plain_text';
+
+export interface InterruptedJob {
+  id: number;
+  kind: 'post' | 'retry_post' | 'retry_attachments';
+  escalationId: number;
+  filePaths: string[];
+  startedAt: string;
+  interruptedAt: string | null;
+}
+
+export interface DailySummary {
+  date: string;
+  ticketsFetched: string[];
+  draftsCreated: string[];
+  escalationsPosted: string[];
+  escalationsBounced: string[];
+  markdown: string;
+  narrative: string | null;
+}
+
+export interface KbDraft {
+  id: number;
+  escalationId: number;
+  title: string;
+  markdown: string;
+  createdAt: string;
+}
+
+export interface StartupStatus {
+  initializing: boolean;
+  degraded: boolean;
+  error: string | null;
+  dbPath: string | null;
+}
+
+export interface DatabaseRecoveryReport {
+  action: 'reopened' | 'salvaged' | 'reset';
+  backupPath: string | null;
+}
+
+export interface MaintenanceReport {
+  integrityOk: boolean;
+  integrityMessages: string[];
+  sizeBeforeBytes: number;
+  sizeAfterBytes: number;
+  durationMs: number;
+  ranAt: string;
+  compressedEscalations: number;
+  orphansRemoved: number;
+}
+
+export interface DataIntegrityReport {
+  orphans: { table: string; count: number }[];
+  danglingParents: number;
+  total: number;
+  repaired: boolean;
 }
 
 export interface ApiConfig {
@@ -71,3 +534,70 @@ export interface ApiConfig {
   ollamaEndpoint: string;
   ollamaModel: string;
 }
+
+export interface TicketKeyPreset {
+  name: string;
+  pattern: string;
+}
+
+export interface Preferences {
+  correlationMarker: string;
+  autoAddRemoteLinks: boolean;
+  watchPostedTickets: boolean;
+  minQualityScore: number | null;
+  blockedExtensions: string[];
+  clamavSocket: string | null;
+  ticketKeyPattern: string;
+  sandboxTicketKey: string | null;
+  attachmentRetentionDays: number;
+  embeddingModel: string | null;
+  localApiEnabled: boolean;
+  localApiBind: string;
+  uploadChunkSizeMb: number | null;
+  bilingualOutput: boolean;
+  terminology: string[];
+  ticketStatusGuard: 'off' | 'warn' | 'block';
+  closedStatuses: string[];
+  l2Assignees: string[];
+  outputFormat: OutputFormat;
+  maxCommentChars: number;
+  sectionCharLimits: Record<string, number>;
+  ollamaTimeoutSecs: number;
+  templateCategoryMap: Record<string, string[]>;
+  coldStorageAfterDays: number;
+  auditVerbosity: 'minimal' | 'standard' | 'full';
+  watchFolders: string[];
+  watchWindowHours: number;
+  defaultTemplateId: number | null;
+  pinnedTemplateIds: number[];
+  ticketPrefetchMinutes: number | null;
+  ticketPrefetchJql: string;
+  ackPhrases: string[];
+  ackLabels: string[];
+  unacknowledgedAfterHours: number;
+  ackSyncDays: number;
+  readOnlyMode: boolean;
+  ticketAttachmentBudgetMb: number | null;
+  l2Teams: L2Team[];
+  rerenderOnRetry: boolean;
+  escalationQueueJql: string | null;
+  escalationQueueMinutes: number;
+  environmentCommands: EnvironmentCommand[];
+  postingFreezeWindows: FreezeWindow[];
+}
+
+export type OnboardingStep = 'jira_config' | 'test_connection' | 'ollama' | 'first_template';
+
+export interface OnboardingStepStatus {
+  step: OnboardingStep;
+  required: boolean;
+  completed: boolean;
+  skipped: boolean;
+  completedAt: string | null;
+}
+
+export interface OnboardingState {
+  steps: OnboardingStepStatus[];
+  currentStep: OnboardingStep | null;
+  postingEnabled: boolean;
+}